@@ -60,6 +60,7 @@ impl Error {
             ErrorKind::ContentTruncated => "ContentTruncated",
             ErrorKind::ContentIncomplete => "ContentIncomplete",
             ErrorKind::InvalidInput => "InvalidInput",
+            ErrorKind::DataIntegrityCheckFailed => "DataIntegrityCheckFailed",
             _ => "Unexpected",
         })?;
         let message = env.new_string(format!("{:?}", self.inner))?;
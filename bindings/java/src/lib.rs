@@ -202,6 +202,7 @@ fn make_metadata<'a>(env: &mut JNIEnv<'a>, metadata: Metadata) -> Result<JObject
         EntryMode::FILE => 0,
         EntryMode::DIR => 1,
         EntryMode::Unknown => 2,
+        EntryMode::Symlink => 3,
     };
 
     let metakey = metadata.metakey();
@@ -129,6 +129,10 @@ public class Metadata {
          * DIR means the path can be listed.
          */
         DIR,
+        /**
+         * SYMLINK means the path is a symbolic link.
+         */
+        SYMLINK,
         /**
          * Unknown means we don't know what we can do on this path.
          */
@@ -140,6 +144,8 @@ public class Metadata {
                     return EntryMode.FILE;
                 case 1:
                     return EntryMode.DIR;
+                case 3:
+                    return EntryMode.SYMLINK;
                 default:
                     return EntryMode.UNKNOWN;
             }
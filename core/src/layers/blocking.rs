@@ -319,6 +319,10 @@ impl<I: oio::Write + 'static> oio::BlockingWrite for BlockingWrapper<I> {
         self.handle
             .block_on(poll_fn(|cx| self.inner.poll_close(cx)))
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
 }
 
 impl<I: oio::List> oio::BlockingList for BlockingWrapper<I> {
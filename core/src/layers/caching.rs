@@ -0,0 +1,421 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::VecDeque;
+use std::io::SeekFrom;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use bytes::BytesMut;
+use log::warn;
+
+use crate::raw::oio::WriteExt;
+use crate::raw::*;
+use crate::*;
+
+/// Add a local cache for reads from (usually remote) storage services.
+///
+/// # Behavior
+///
+/// - Whole file reads will be served from the cache operator if the `ETag`
+///   of the path (via `stat` on underlying services) matches the one stored
+///   in the sidecar file `<path>.etag` of the cache.
+/// - On cache miss, data will be read from underlying services and written
+///   into the cache at the same time. The sidecar file is written only after
+///   the whole content has been read, so partial content will never be served.
+/// - Range reads, conditional reads, reads on services without `ETag` and all
+///   blocking reads will not be cached.
+///
+/// # Notes
+///
+/// - Every read will call `stat` on underlying services to check the `ETag`.
+/// - The size index used by `with_max_size` is in memory. Files that are cached
+///   before current process started won't be counted or evicted.
+///
+/// # Examples
+///
+/// ```no_run
+/// use anyhow::Result;
+/// use opendal::layers::CachingLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// # fn main() -> Result<()> {
+/// // Local storage like `services::Fs` is usually used as the cache.
+/// let cache = Operator::new(services::Memory::default())?.finish();
+///
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(CachingLayer::new(cache).with_max_size(1024 * 1024 * 1024))
+///     .finish();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct CachingLayer {
+    cache: Operator,
+    max_size: Option<u64>,
+}
+
+impl CachingLayer {
+    /// Create a new CachingLayer that stores cached files in the given operator.
+    pub fn new(cache: Operator) -> Self {
+        Self {
+            cache,
+            max_size: None,
+        }
+    }
+
+    /// Set the max total size of cached files.
+    ///
+    /// Least recently used files will be evicted once the size exceeds.
+    ///
+    /// Default to unlimited.
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for CachingLayer {
+    type LayeredAccessor = CachingAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        CachingAccessor {
+            inner,
+            cache: self.cache.clone().into_inner(),
+            index: Arc::new(CacheIndex::new(self.max_size)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CachingAccessor<A: Accessor> {
+    inner: A,
+    cache: FusedAccessor,
+    index: Arc<CacheIndex>,
+}
+
+#[inline]
+fn etag_path_of(path: &str) -> String {
+    format!("{path}.etag")
+}
+
+impl<A: Accessor> CachingAccessor<A> {
+    /// Load the ETag stored in the sidecar file of given path.
+    async fn load_etag(&self, path: &str) -> Option<String> {
+        let (_, mut r) = self
+            .cache
+            .read(&etag_path_of(path), OpRead::new())
+            .await
+            .ok()?;
+
+        let mut buf = BytesMut::new();
+        loop {
+            let bs = oio::Read::read(&mut r, 4 * 1024).await.ok()?;
+            if bs.is_empty() {
+                break;
+            }
+            buf.extend_from_slice(&bs);
+        }
+
+        String::from_utf8(buf.to_vec()).ok()
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<A: Accessor> LayeredAccessor for CachingAccessor<A> {
+    type Inner = A;
+    type Reader = TwoWays<oio::Reader, CachingReader<A::Reader>>;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let cacheable = args.range().is_full()
+            && args.if_match().is_none()
+            && args.if_none_match().is_none()
+            && args.version().is_none();
+        if !cacheable {
+            return self
+                .inner
+                .read(path, args)
+                .await
+                .map(|(rp, r)| (rp, TwoWays::Two(CachingReader::new(r, None))));
+        }
+
+        let meta = self.inner.stat(path, OpStat::new()).await?.into_metadata();
+        let etag = if meta.metakey().contains(Metakey::Etag) {
+            meta.etag().map(|v| v.to_string())
+        } else {
+            None
+        };
+        let Some(etag) = etag else {
+            return self
+                .inner
+                .read(path, args)
+                .await
+                .map(|(rp, r)| (rp, TwoWays::Two(CachingReader::new(r, None))));
+        };
+
+        if self.load_etag(path).await.as_deref() == Some(etag.as_str()) {
+            match self.cache.read(path, OpRead::new()).await {
+                Ok((rp, r)) => {
+                    self.index.touch(path);
+                    return Ok((rp, TwoWays::One(r)));
+                }
+                Err(err) => warn!("caching: read cached file {path} failed: {err}"),
+            }
+        }
+
+        let (rp, r) = self.inner.read(path, args).await?;
+
+        // Remove the old sidecar file first so that the partially written
+        // content will never be treated as valid.
+        let filler = match self
+            .cache
+            .delete(&etag_path_of(path), OpDelete::new())
+            .await
+        {
+            Ok(_) => match self.cache.write(path, OpWrite::new()).await {
+                Ok((_, w)) => Some(CacheFiller {
+                    cache: self.cache.clone(),
+                    index: self.index.clone(),
+                    path: path.to_string(),
+                    etag,
+                    writer: w,
+                    written: 0,
+                }),
+                Err(err) => {
+                    warn!("caching: create cache writer for {path} failed: {err}");
+                    None
+                }
+            },
+            Err(err) => {
+                warn!("caching: remove etag of {path} failed: {err}");
+                None
+            }
+        };
+
+        Ok((rp, TwoWays::Two(CachingReader::new(r, filler))))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+/// CachingReader reads from underlying reader and fills the cache if filler
+/// is set.
+pub struct CachingReader<R> {
+    inner: R,
+    filler: Option<CacheFiller>,
+}
+
+impl<R> CachingReader<R> {
+    fn new(inner: R, filler: Option<CacheFiller>) -> Self {
+        Self { inner, filler }
+    }
+}
+
+impl<R: oio::Read> oio::Read for CachingReader<R> {
+    async fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        // Content is not sequential anymore, stop filling the cache.
+        if let Some(mut filler) = self.filler.take() {
+            filler.abort().await;
+        }
+
+        self.inner.seek(pos).await
+    }
+
+    async fn read(&mut self, limit: usize) -> Result<Bytes> {
+        let bs = match self.inner.read(limit).await {
+            Ok(bs) => bs,
+            Err(err) => {
+                if let Some(mut filler) = self.filler.take() {
+                    filler.abort().await;
+                }
+                return Err(err);
+            }
+        };
+
+        if let Some(filler) = self.filler.as_mut() {
+            let res = if bs.is_empty() {
+                let filler = self.filler.take().expect("filler must be valid");
+                filler.finish().await
+            } else {
+                filler.write(bs.clone()).await
+            };
+
+            if let Err(err) = res {
+                warn!("caching: fill cache failed: {err}");
+                if let Some(mut filler) = self.filler.take() {
+                    filler.abort().await;
+                }
+            }
+        }
+
+        Ok(bs)
+    }
+}
+
+/// CacheFiller writes the content into cache and commits it by writing the
+/// ETag sidecar file after all content has been written.
+struct CacheFiller {
+    cache: FusedAccessor,
+    index: Arc<CacheIndex>,
+    path: String,
+    etag: String,
+    writer: oio::Writer,
+    written: u64,
+}
+
+impl CacheFiller {
+    async fn write(&mut self, bs: Bytes) -> Result<()> {
+        self.written += bs.len() as u64;
+        self.writer.write_all(bs).await
+    }
+
+    async fn abort(&mut self) {
+        if let Err(err) = self.writer.abort().await {
+            warn!(
+                "caching: abort cache writer for {} failed: {err}",
+                self.path
+            );
+        }
+    }
+
+    async fn finish(mut self) -> Result<()> {
+        self.writer.close().await?;
+
+        let (_, mut w) = self
+            .cache
+            .write(&etag_path_of(&self.path), OpWrite::new())
+            .await?;
+        w.write_all(Bytes::from(self.etag)).await?;
+        w.close().await?;
+
+        for path in self.index.insert(&self.path, self.written) {
+            for p in [etag_path_of(&path), path] {
+                if let Err(err) = self.cache.delete(&p, OpDelete::new()).await {
+                    warn!("caching: evict {p} failed: {err}");
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// CacheIndex tracks the size of cached files in least recently used order.
+#[derive(Debug)]
+struct CacheIndex {
+    max_size: Option<u64>,
+    state: Mutex<CacheIndexState>,
+}
+
+#[derive(Debug, Default)]
+struct CacheIndexState {
+    /// Cached files from least recently used to most recently used.
+    entries: VecDeque<(String, u64)>,
+    size: u64,
+}
+
+impl CacheIndex {
+    fn new(max_size: Option<u64>) -> Self {
+        Self {
+            max_size,
+            state: Mutex::default(),
+        }
+    }
+
+    /// Mark given path as most recently used.
+    fn touch(&self, path: &str) {
+        let mut state = self.state.lock().expect("lock must be valid");
+        if let Some(idx) = state.entries.iter().position(|(p, _)| p == path) {
+            let entry = state.entries.remove(idx).expect("entry must exist");
+            state.entries.push_back(entry);
+        }
+    }
+
+    /// Insert given path with size and return the paths that should be evicted.
+    fn insert(&self, path: &str, size: u64) -> Vec<String> {
+        let mut state = self.state.lock().expect("lock must be valid");
+        if let Some(idx) = state.entries.iter().position(|(p, _)| p == path) {
+            let (_, old) = state.entries.remove(idx).expect("entry must exist");
+            state.size -= old;
+        }
+        state.entries.push_back((path.to_string(), size));
+        state.size += size;
+
+        let mut evicted = Vec::new();
+        let Some(max_size) = self.max_size else {
+            return evicted;
+        };
+        // Keep at least the newly inserted file.
+        while state.size > max_size && state.entries.len() > 1 {
+            let (p, s) = state.entries.pop_front().expect("entry must exist");
+            state.size -= s;
+            evicted.push(p);
+        }
+
+        evicted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_index_evict() {
+        let index = CacheIndex::new(Some(10));
+
+        assert!(index.insert("a", 4).is_empty());
+        assert!(index.insert("b", 4).is_empty());
+        index.touch("a");
+        assert_eq!(index.insert("c", 4), vec!["b".to_string()]);
+        assert_eq!(
+            index.insert("d", 20),
+            vec!["a".to_string(), "c".to_string()]
+        );
+    }
+}
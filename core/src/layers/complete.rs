@@ -389,11 +389,15 @@ impl<A: Accessor> CompleteAccessor<A> {
             (true, false) => {
                 // Forward path that ends with /
                 if path.ends_with('/') {
-                    let p = FlatLister::new(self.inner.clone(), path);
+                    let p = FlatLister::new(self.inner.clone(), path)
+                        .with_skip_hidden(args.skip_hidden())
+                        .with_max_depth(args.max_depth());
                     Ok((RpList::default(), CompleteLister::Two(p)))
                 } else {
                     let parent = get_parent(path);
-                    let p = FlatLister::new(self.inner.clone(), parent);
+                    let p = FlatLister::new(self.inner.clone(), parent)
+                        .with_skip_hidden(args.skip_hidden())
+                        .with_max_depth(args.max_depth());
                     let p = PrefixLister::new(p, path);
                     Ok((RpList::default(), CompleteLister::Four(p)))
                 }
@@ -437,11 +441,15 @@ impl<A: Accessor> CompleteAccessor<A> {
             (true, false) => {
                 // Forward path that ends with /
                 if path.ends_with('/') {
-                    let p = FlatLister::new(self.inner.clone(), path);
+                    let p = FlatLister::new(self.inner.clone(), path)
+                        .with_skip_hidden(args.skip_hidden())
+                        .with_max_depth(args.max_depth());
                     Ok((RpList::default(), CompleteLister::Two(p)))
                 } else {
                     let parent = get_parent(path);
-                    let p = FlatLister::new(self.inner.clone(), parent);
+                    let p = FlatLister::new(self.inner.clone(), parent)
+                        .with_skip_hidden(args.skip_hidden())
+                        .with_max_depth(args.max_depth());
                     let p = PrefixLister::new(p, path);
                     Ok((RpList::default(), CompleteLister::Four(p)))
                 }
@@ -515,6 +523,24 @@ impl<A: Accessor> LayeredAccessor for CompleteAccessor<A> {
                 ),
             ));
         }
+        if args.if_not_exists() && !capability.write_with_if_not_exists {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with if_not_exists",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.acl().is_some() && !capability.write_with_acl {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with acl",
+                    self.info().scheme()
+                ),
+            ));
+        }
 
         // Calculate buffer size.
         let buffer_size = args.buffer().map(|mut size| {
@@ -562,6 +588,114 @@ impl<A: Accessor> LayeredAccessor for CompleteAccessor<A> {
         self.inner().rename(from, to, args).await
     }
 
+    async fn hard_link(&self, from: &str, to: &str, args: OpHardLink) -> Result<RpHardLink> {
+        let capability = self.meta.full_capability();
+        if !capability.hard_link {
+            return Err(self.new_unsupported_error(Operation::HardLink));
+        }
+
+        self.inner().hard_link(from, to, args).await
+    }
+
+    async fn symlink(&self, target: &str, link: &str, args: OpSymlink) -> Result<RpSymlink> {
+        let capability = self.meta.full_capability();
+        if !capability.symlink {
+            return Err(self.new_unsupported_error(Operation::Symlink));
+        }
+
+        self.inner().symlink(target, link, args).await
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        let capability = self.meta.full_capability();
+        if !capability.set_permissions {
+            return Err(self.new_unsupported_error(Operation::SetPermissions));
+        }
+
+        self.inner().set_permissions(path, args).await
+    }
+
+    async fn statvfs(&self, args: OpStatvfs) -> Result<RpStatvfs> {
+        let capability = self.meta.full_capability();
+        if !capability.statvfs {
+            return Err(self.new_unsupported_error(Operation::Statvfs));
+        }
+
+        self.inner().statvfs(args).await
+    }
+
+    async fn truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        let capability = self.meta.full_capability();
+        if !capability.truncate {
+            return Err(self.new_unsupported_error(Operation::Truncate));
+        }
+
+        self.inner().truncate(path, args).await
+    }
+
+    async fn fallocate(&self, path: &str, args: OpFallocate) -> Result<RpFallocate> {
+        let capability = self.meta.full_capability();
+        if !capability.fallocate {
+            return Err(self.new_unsupported_error(Operation::Fallocate));
+        }
+
+        self.inner().fallocate(path, args).await
+    }
+
+    async fn rename_noreplace(
+        &self,
+        from: &str,
+        to: &str,
+        args: OpRenameNoreplace,
+    ) -> Result<RpRenameNoreplace> {
+        let capability = self.meta.full_capability();
+        if !capability.rename_noreplace {
+            return Err(self.new_unsupported_error(Operation::RenameNoreplace));
+        }
+
+        self.inner().rename_noreplace(from, to, args).await
+    }
+
+    async fn setxattr(&self, path: &str, args: OpSetxattr) -> Result<RpSetxattr> {
+        let capability = self.meta.full_capability();
+        if !capability.xattr {
+            return Err(self.new_unsupported_error(Operation::Setxattr));
+        }
+
+        self.inner().setxattr(path, args).await
+    }
+
+    async fn getxattr(&self, path: &str, args: OpGetxattr) -> Result<RpGetxattr> {
+        let capability = self.meta.full_capability();
+        if !capability.xattr {
+            return Err(self.new_unsupported_error(Operation::Getxattr));
+        }
+
+        self.inner().getxattr(path, args).await
+    }
+
+    async fn listxattr(&self, path: &str, args: OpListxattr) -> Result<RpListxattr> {
+        let capability = self.meta.full_capability();
+        if !capability.xattr {
+            return Err(self.new_unsupported_error(Operation::Listxattr));
+        }
+
+        self.inner().listxattr(path, args).await
+    }
+
+    async fn list_buckets(&self, args: OpListBuckets) -> Result<RpListBuckets> {
+        let capability = self.meta.full_capability();
+        if !capability.list_buckets {
+            return Err(self.new_unsupported_error(Operation::ListBuckets));
+        }
+
+        self.inner().list_buckets(args).await
+    }
+
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         self.complete_stat(path, args).await
     }
@@ -625,6 +759,24 @@ impl<A: Accessor> LayeredAccessor for CompleteAccessor<A> {
                 ),
             ));
         }
+        if args.if_not_exists() && !capability.write_with_if_not_exists {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with if_not_exists",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        if args.acl().is_some() && !capability.write_with_acl {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                &format!(
+                    "service {} doesn't support operation write with acl",
+                    self.info().scheme()
+                ),
+            ));
+        }
 
         self.inner
             .blocking_write(path, args)
@@ -741,6 +893,13 @@ where
 
         Poll::Ready(Ok(()))
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner
+            .as_ref()
+            .map(|w| w.written_bytes())
+            .unwrap_or_default()
+    }
 }
 
 impl<W> oio::BlockingWrite for CompleteWriter<W>
@@ -765,6 +924,13 @@ where
         self.inner = None;
         Ok(())
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner
+            .as_ref()
+            .map(|w| w.written_bytes())
+            .unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
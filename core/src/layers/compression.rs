@@ -0,0 +1,848 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp::min;
+use std::io;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use bytes::Buf;
+use bytes::Bytes;
+use zstd::stream::raw::Decoder;
+use zstd::stream::raw::Encoder;
+use zstd::stream::raw::InBuffer;
+use zstd::stream::raw::Operation;
+use zstd::stream::raw::OutBuffer;
+use zstd::zstd_safe::CCtx;
+use zstd::zstd_safe::DCtx;
+
+use crate::raw::oio::BlockingRead;
+use crate::raw::oio::Read;
+use crate::raw::*;
+use crate::*;
+
+/// The suffix appended to the path of compressed files.
+const SUFFIX: &str = ".zst";
+/// The default compression level of zstd.
+const DEFAULT_LEVEL: i32 = 3;
+/// The magic number of zstd skippable frame that stores decompressed size.
+const SIZE_FRAME_MAGIC: u32 = 0x184D2A50;
+/// The size of skippable frame: magic (4 bytes) | frame size (4 bytes) | content size (8 bytes).
+const SIZE_FRAME_LEN: usize = 16;
+/// The size of compressed data to read from storage at once.
+const READ_SIZE: usize = 64 * 1024;
+
+/// Add transparent zstd compression for underlying services.
+///
+/// # Format
+///
+/// Files are stored with a `.zst` suffix appended to the path so that
+/// compressed and uncompressed files can coexist. The decompressed size is
+/// stored in a zstd skippable frame at the end of the file, so that `stat`
+/// can report it without decompressing the whole content, and the file can
+/// still be decompressed by other zstd tools.
+///
+/// # Notes
+///
+/// - `list` only returns dirs and compressed files with the suffix stripped,
+///   and the content length of listed files is the compressed size.
+/// - `stat` needs an extra read of the size frame.
+/// - `append`, `content_md5`, `seek` and `presign` are not supported.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::CompressionLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(CompressionLayer::new().with_level(9))
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompressionLayer {
+    level: i32,
+}
+
+impl Default for CompressionLayer {
+    fn default() -> Self {
+        Self {
+            level: DEFAULT_LEVEL,
+        }
+    }
+}
+
+impl CompressionLayer {
+    /// Create a new compression layer with default level `3`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the compression level of zstd.
+    pub fn with_level(mut self, level: i32) -> Self {
+        self.level = level;
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for CompressionLayer {
+    type LayeredAccessor = CompressionAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        CompressionAccessor {
+            inner,
+            level: self.level,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CompressionAccessor<A> {
+    inner: A,
+    level: i32,
+}
+
+/// Build the path of compressed file, dirs will be kept as is.
+fn compressed_path(path: &str) -> String {
+    if path.ends_with('/') {
+        path.to_string()
+    } else {
+        format!("{path}{SUFFIX}")
+    }
+}
+
+fn new_zstd_error(message: &'static str, err: io::Error) -> Error {
+    Error::new(ErrorKind::Unexpected, message).set_source(err)
+}
+
+fn size_frame(size: u64) -> [u8; SIZE_FRAME_LEN] {
+    let mut frame = [0; SIZE_FRAME_LEN];
+    frame[..4].copy_from_slice(&SIZE_FRAME_MAGIC.to_le_bytes());
+    frame[4..8].copy_from_slice(&8u32.to_le_bytes());
+    frame[8..].copy_from_slice(&size.to_le_bytes());
+    frame
+}
+
+fn parse_size_frame(path: &str, bs: &[u8]) -> Result<u64> {
+    if bs.len() != SIZE_FRAME_LEN
+        || bs[..4] != SIZE_FRAME_MAGIC.to_le_bytes()
+        || bs[4..8] != 8u32.to_le_bytes()
+    {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            "compressed file doesn't have size frame",
+        )
+        .with_context("path", path));
+    }
+
+    let mut size = [0; 8];
+    size.copy_from_slice(&bs[8..]);
+    Ok(u64::from_le_bytes(size))
+}
+
+impl<A: Accessor> CompressionAccessor<A> {
+    fn check_write(&self, args: &OpWrite) -> Result<()> {
+        if args.append() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "compression layer doesn't support append",
+            ));
+        }
+        if args.content_md5().is_some() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "compression layer doesn't support content_md5",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn compressor(&self) -> Result<Compressor> {
+        let encoder = Encoder::new(self.level)
+            .map_err(|err| new_zstd_error("create zstd encoder failed", err))?;
+
+        Ok(Compressor {
+            encoder,
+            size: 0,
+            finished: false,
+        })
+    }
+
+    fn decompressor(&self, range: BytesRange) -> Result<Decompressor> {
+        let decoder =
+            Decoder::new().map_err(|err| new_zstd_error("create zstd decoder failed", err))?;
+
+        Ok(Decompressor {
+            decoder,
+            input: Bytes::new(),
+            plain: Bytes::new(),
+            hint: 1,
+            eof: false,
+            skip: range.offset().unwrap_or_default(),
+            remaining: range.size(),
+        })
+    }
+
+    fn size_frame_range(path: &str, len: u64) -> Result<BytesRange> {
+        if len < SIZE_FRAME_LEN as u64 {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "compressed file doesn't have size frame",
+            )
+            .with_context("path", path));
+        }
+
+        Ok(BytesRange::new(
+            Some(len - SIZE_FRAME_LEN as u64),
+            Some(SIZE_FRAME_LEN as u64),
+        ))
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<A: Accessor> LayeredAccessor for CompressionAccessor<A> {
+    type Inner = A;
+    type Reader = CompressionReader<A::Reader>;
+    type BlockingReader = CompressionReader<A::BlockingReader>;
+    type Writer = CompressionWriter<A::Writer>;
+    type BlockingWriter = CompressionWriter<A::BlockingWriter>;
+    type Lister = CompressionLister<A::Lister>;
+    type BlockingLister = CompressionLister<A::BlockingLister>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn metadata(&self) -> AccessorInfo {
+        let mut meta = self.inner.info();
+
+        let cap = meta.full_capability_mut();
+        cap.read_can_seek = false;
+        cap.write_can_append = false;
+        cap.write_with_content_md5 = false;
+        cap.presign = false;
+        cap.presign_read = false;
+        cap.presign_stat = false;
+        cap.presign_write = false;
+
+        meta
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let mut range = args.range();
+        // Suffix range can't be converted without knowing the total size.
+        if range.offset().is_none() && range.size().is_some() {
+            let size = LayeredAccessor::stat(self, path, OpStat::default())
+                .await?
+                .into_metadata()
+                .content_length();
+            range = range.complete(size);
+        }
+
+        let dec = self.decompressor(range)?;
+        let (_, r) = self
+            .inner
+            .read(
+                &compressed_path(path),
+                args.with_range(BytesRange::default()),
+            )
+            .await?;
+
+        Ok((RpRead::new(), CompressionReader::new(r, dec)))
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let mut range = args.range();
+        // Suffix range can't be converted without knowing the total size.
+        if range.offset().is_none() && range.size().is_some() {
+            let size = LayeredAccessor::blocking_stat(self, path, OpStat::default())?
+                .into_metadata()
+                .content_length();
+            range = range.complete(size);
+        }
+
+        let dec = self.decompressor(range)?;
+        let (_, r) = self.inner.blocking_read(
+            &compressed_path(path),
+            args.with_range(BytesRange::default()),
+        )?;
+
+        Ok((RpRead::new(), CompressionReader::new(r, dec)))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.check_write(&args)?;
+
+        let comp = self.compressor()?;
+        let (rp, w) = self.inner.write(&compressed_path(path), args).await?;
+        Ok((rp, CompressionWriter::new(w, comp)))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.check_write(&args)?;
+
+        let comp = self.compressor()?;
+        let (rp, w) = self.inner.blocking_write(&compressed_path(path), args)?;
+        Ok((rp, CompressionWriter::new(w, comp)))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.inner
+            .copy(&compressed_path(from), &compressed_path(to), args)
+            .await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.inner
+            .rename(&compressed_path(from), &compressed_path(to), args)
+            .await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let p = compressed_path(path);
+        let mut meta = self.inner.stat(&p, args).await?.into_metadata();
+        if !meta.mode().is_file() {
+            return Ok(RpStat::new(meta));
+        }
+
+        let range = Self::size_frame_range(&p, meta.content_length())?;
+        let (_, mut r) = self.inner.read(&p, OpRead::new().with_range(range)).await?;
+        let bs = r.read_exact(SIZE_FRAME_LEN).await?;
+        meta.set_content_length(parse_size_frame(&p, &bs)?);
+
+        Ok(RpStat::new(meta))
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.inner.delete(&compressed_path(path), args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let (rp, l) = self.inner.list(path, args).await?;
+        Ok((rp, CompressionLister { inner: l }))
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let ops = args
+            .into_operation()
+            .into_iter()
+            .map(|(p, op)| (compressed_path(&p), op))
+            .collect();
+
+        let rp = self.inner.batch(OpBatch::new(ops)).await?;
+        let results = rp
+            .into_results()
+            .into_iter()
+            .map(|(p, res)| match p.strip_suffix(SUFFIX) {
+                Some(v) => (v.to_string(), res),
+                None => (p, res),
+            })
+            .collect();
+
+        Ok(RpBatch::new(results))
+    }
+
+    async fn presign(&self, _: &str, _: OpPresign) -> Result<RpPresign> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "compression layer doesn't support presign",
+        ))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.inner
+            .blocking_copy(&compressed_path(from), &compressed_path(to), args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.inner
+            .blocking_rename(&compressed_path(from), &compressed_path(to), args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let p = compressed_path(path);
+        let mut meta = self.inner.blocking_stat(&p, args)?.into_metadata();
+        if !meta.mode().is_file() {
+            return Ok(RpStat::new(meta));
+        }
+
+        let range = Self::size_frame_range(&p, meta.content_length())?;
+        let (_, mut r) = self
+            .inner
+            .blocking_read(&p, OpRead::new().with_range(range))?;
+        let mut bs = Vec::with_capacity(SIZE_FRAME_LEN);
+        r.read_to_end(&mut bs)?;
+        meta.set_content_length(parse_size_frame(&p, &bs)?);
+
+        Ok(RpStat::new(meta))
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.inner.blocking_delete(&compressed_path(path), args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        let (rp, l) = self.inner.blocking_list(path, args)?;
+        Ok((rp, CompressionLister { inner: l }))
+    }
+}
+
+struct Compressor {
+    encoder: Encoder<'static>,
+    /// The size of data before compression.
+    size: u64,
+    finished: bool,
+}
+
+impl Compressor {
+    /// Compress given data, returns the consumed size and compressed data.
+    fn compress(&mut self, input: &[u8]) -> Result<(usize, Bytes)> {
+        let mut out = Vec::with_capacity(CCtx::out_size());
+        let mut in_buf = InBuffer::around(input);
+        self.encoder
+            .run(&mut in_buf, &mut OutBuffer::around(&mut out))
+            .map_err(|err| new_zstd_error("zstd compress failed", err))?;
+
+        let n = in_buf.pos();
+        self.size += n as u64;
+        Ok((n, Bytes::from(out)))
+    }
+
+    /// Flush the remaining compressed data and the size frame.
+    ///
+    /// Returns `None` if all data has been flushed.
+    fn finish(&mut self) -> Result<Option<Bytes>> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        let mut out = Vec::with_capacity(CCtx::out_size());
+        let remaining = self
+            .encoder
+            .finish(&mut OutBuffer::around(&mut out), true)
+            .map_err(|err| new_zstd_error("zstd finish failed", err))?;
+        if remaining == 0 {
+            out.extend_from_slice(&size_frame(self.size));
+            self.finished = true;
+        }
+
+        Ok(Some(Bytes::from(out)))
+    }
+}
+
+struct Decompressor {
+    decoder: Decoder<'static>,
+    /// Compressed data that not consumed yet.
+    input: Bytes,
+    /// Decompressed data that not returned yet.
+    plain: Bytes,
+    /// The hint returned by zstd, `0` means current frame is complete.
+    hint: usize,
+    eof: bool,
+    /// Bytes to skip for range read.
+    skip: u64,
+    /// Bytes left to return if range size is specified.
+    remaining: Option<u64>,
+}
+
+impl Decompressor {
+    /// Push data read from storage, empty data means EOF.
+    fn push(&mut self, bs: Bytes) {
+        if bs.is_empty() {
+            self.eof = true;
+        } else {
+            self.input = bs;
+        }
+    }
+
+    /// Return at most `limit` bytes of decompressed data, or `None` if more
+    /// data is needed.
+    fn output(&mut self, limit: usize) -> Result<Option<Bytes>> {
+        loop {
+            if limit == 0 || self.remaining == Some(0) {
+                return Ok(Some(Bytes::new()));
+            }
+
+            if !self.plain.is_empty() {
+                let mut n = min(limit, self.plain.len());
+                if let Some(remaining) = self.remaining.as_mut() {
+                    n = min(n as u64, *remaining) as usize;
+                    *remaining -= n as u64;
+                }
+                return Ok(Some(self.plain.split_to(n)));
+            }
+
+            if self.input.is_empty() {
+                if !self.eof {
+                    return Ok(None);
+                }
+                if self.hint != 0 {
+                    return Err(Error::new(
+                        ErrorKind::ContentIncomplete,
+                        "compressed content is truncated",
+                    ));
+                }
+                return Ok(Some(Bytes::new()));
+            }
+
+            let mut out = Vec::with_capacity(DCtx::out_size());
+            let mut in_buf = InBuffer::around(&self.input);
+            self.hint = self
+                .decoder
+                .run(&mut in_buf, &mut OutBuffer::around(&mut out))
+                .map_err(|err| new_zstd_error("zstd decompress failed", err))?;
+            let consumed = in_buf.pos();
+            self.input.advance(consumed);
+
+            let mut plain = Bytes::from(out);
+            if self.skip > 0 {
+                let n = min(self.skip, plain.len() as u64) as usize;
+                plain.advance(n);
+                self.skip -= n as u64;
+            }
+            self.plain = plain;
+        }
+    }
+}
+
+/// CompressionReader will decompress content read from underlying reader.
+pub struct CompressionReader<R> {
+    inner: R,
+    dec: Decompressor,
+    /// Reused buffer for blocking read.
+    scratch: Vec<u8>,
+}
+
+impl<R> CompressionReader<R> {
+    fn new(inner: R, dec: Decompressor) -> Self {
+        Self {
+            inner,
+            dec,
+            scratch: Vec::new(),
+        }
+    }
+}
+
+/// # Safety
+///
+/// zstd decoder is not `Sync`, but we will only take `&mut Self` reference
+/// for CompressionReader.
+unsafe impl<R: Sync> Sync for CompressionReader<R> {}
+
+impl<R: oio::Read> oio::Read for CompressionReader<R> {
+    async fn read(&mut self, limit: usize) -> Result<Bytes> {
+        loop {
+            if let Some(bs) = self.dec.output(limit)? {
+                return Ok(bs);
+            }
+
+            let bs = self.inner.read(READ_SIZE).await?;
+            self.dec.push(bs);
+        }
+    }
+
+    async fn seek(&mut self, _: io::SeekFrom) -> Result<u64> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "compression layer doesn't support seek",
+        ))
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for CompressionReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        loop {
+            if let Some(bs) = self.dec.output(buf.len())? {
+                buf[..bs.len()].copy_from_slice(&bs);
+                return Ok(bs.len());
+            }
+
+            self.scratch.resize(READ_SIZE, 0);
+            let n = self.inner.read(&mut self.scratch)?;
+            self.dec.push(Bytes::copy_from_slice(&self.scratch[..n]));
+        }
+    }
+
+    fn seek(&mut self, _: io::SeekFrom) -> Result<u64> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "compression layer doesn't support seek",
+        ))
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        loop {
+            match self.dec.output(READ_SIZE) {
+                Ok(Some(bs)) if bs.is_empty() => return None,
+                Ok(Some(bs)) => return Some(Ok(bs)),
+                Ok(None) => {}
+                Err(err) => return Some(Err(err)),
+            }
+
+            match self.inner.next() {
+                Some(Ok(bs)) => self.dec.push(bs),
+                Some(Err(err)) => return Some(Err(err)),
+                None => self.dec.push(Bytes::new()),
+            }
+        }
+    }
+}
+
+/// CompressionWriter will compress content before writing into underlying writer.
+pub struct CompressionWriter<W> {
+    inner: W,
+    comp: Compressor,
+    /// Compressed data that not written yet.
+    pending: Option<Bytes>,
+}
+
+impl<W> CompressionWriter<W> {
+    fn new(inner: W, comp: Compressor) -> Self {
+        Self {
+            inner,
+            comp,
+            pending: None,
+        }
+    }
+}
+
+/// # Safety
+///
+/// zstd encoder is not `Sync`, but the encoder will only be accessed via
+/// `&mut Self` reference of CompressionWriter.
+unsafe impl<W: Sync> Sync for CompressionWriter<W> {}
+
+impl<W: oio::Write> CompressionWriter<W> {
+    /// Write the pending data into inner writer until it's done.
+    fn poll_flush_pending(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        while let Some(bs) = self.pending.as_mut() {
+            let n = ready!(self.inner.poll_write(cx, &*bs))?;
+            bs.advance(n);
+            if bs.is_empty() {
+                self.pending = None;
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: oio::Write> oio::Write for CompressionWriter<W> {
+    fn poll_write(&mut self, cx: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>> {
+        loop {
+            ready!(self.poll_flush_pending(cx))?;
+
+            let chunk = bs.chunk();
+            if chunk.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+
+            let (n, out) = self.comp.compress(chunk)?;
+            if !out.is_empty() {
+                self.pending = Some(out);
+            }
+            if n > 0 {
+                return Poll::Ready(Ok(n));
+            }
+        }
+    }
+
+    fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        loop {
+            ready!(self.poll_flush_pending(cx))?;
+
+            match self.comp.finish()? {
+                Some(out) if out.is_empty() => {}
+                Some(out) => self.pending = Some(out),
+                None => break,
+            }
+        }
+
+        self.inner.poll_close(cx)
+    }
+
+    fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.pending = None;
+
+        self.inner.poll_abort(cx)
+    }
+
+    fn written_bytes(&self) -> u64 {
+        self.comp.size
+    }
+}
+
+impl<W: oio::BlockingWrite> CompressionWriter<W> {
+    fn write_all(&mut self, mut bs: Bytes) -> Result<()> {
+        while !bs.is_empty() {
+            let n = self.inner.write(&bs)?;
+            bs.advance(n);
+        }
+
+        Ok(())
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for CompressionWriter<W> {
+    fn write(&mut self, bs: &dyn oio::WriteBuf) -> Result<usize> {
+        loop {
+            let chunk = bs.chunk();
+            if chunk.is_empty() {
+                return Ok(0);
+            }
+
+            let (n, out) = self.comp.compress(chunk)?;
+            self.write_all(out)?;
+            if n > 0 {
+                return Ok(n);
+            }
+        }
+    }
+
+    fn close(&mut self) -> Result<()> {
+        while let Some(out) = self.comp.finish()? {
+            self.write_all(out)?;
+        }
+
+        self.inner.close()
+    }
+
+    fn written_bytes(&self) -> u64 {
+        self.comp.size
+    }
+}
+
+/// CompressionLister will only return dirs and compressed files with suffix stripped.
+pub struct CompressionLister<L> {
+    inner: L,
+}
+
+impl<L> CompressionLister<L> {
+    fn convert(mut entry: oio::Entry) -> Option<oio::Entry> {
+        if entry.mode().is_dir() {
+            return Some(entry);
+        }
+
+        let path = entry.path().strip_suffix(SUFFIX)?.to_string();
+        if path.is_empty() || path.ends_with('/') {
+            return None;
+        }
+        entry.set_path(&path);
+        Some(entry)
+    }
+}
+
+impl<L: oio::List> oio::List for CompressionLister<L> {
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<oio::Entry>>> {
+        loop {
+            match ready!(self.inner.poll_next(cx))? {
+                Some(entry) => {
+                    if let Some(entry) = Self::convert(entry) {
+                        return Poll::Ready(Ok(Some(entry)));
+                    }
+                }
+                None => return Poll::Ready(Ok(None)),
+            }
+        }
+    }
+}
+
+impl<L: oio::BlockingList> oio::BlockingList for CompressionLister<L> {
+    fn next(&mut self) -> Result<Option<oio::Entry>> {
+        while let Some(entry) = self.inner.next()? {
+            if let Some(entry) = Self::convert(entry) {
+                return Ok(Some(entry));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    fn content(size: usize) -> Vec<u8> {
+        (0..size).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() -> Result<()> {
+        let inner = Operator::new(Memory::default())?.finish();
+        let op = inner.clone().layer(CompressionLayer::new());
+
+        for size in [0, 1, 1024 * 1024 + 7] {
+            let path = format!("test_{size}");
+            let data = content(size);
+            op.write(&path, data.clone()).await?;
+
+            // Stored file must be a valid zstd file with suffix.
+            let compressed = inner.read(&format!("{path}.zst")).await?;
+            let decompressed =
+                zstd::decode_all(compressed.as_slice()).expect("decompress must succeed");
+            assert_eq!(decompressed, data);
+
+            assert_eq!(op.stat(&path).await?.content_length(), size as u64);
+            assert_eq!(op.read(&path).await?, data);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_with_range() -> Result<()> {
+        let op = Operator::new(Memory::default())?
+            .finish()
+            .layer(CompressionLayer::new().with_level(1));
+
+        let data = content(1024 * 1024);
+        op.write("test", data.clone()).await?;
+
+        let bs = op.read_with("test").range(300_000..400_000).await?;
+        assert_eq!(bs, data[300_000..400_000]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_list() -> Result<()> {
+        let inner = Operator::new(Memory::default())?.finish();
+        let op = inner.clone().layer(CompressionLayer::new());
+
+        op.write("dir/compressed", "hello").await?;
+        inner.write("dir/uncompressed", "world").await?;
+
+        let entries = op.list("dir/").await?;
+        let paths: Vec<_> = entries
+            .iter()
+            .map(|e| e.path())
+            .filter(|p| !p.ends_with('/'))
+            .collect();
+        assert_eq!(paths, vec!["dir/compressed"]);
+
+        Ok(())
+    }
+}
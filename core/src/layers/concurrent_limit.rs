@@ -292,6 +292,10 @@ impl<R: oio::Write> oio::Write for ConcurrentLimitWrapper<R> {
     fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
         self.inner.poll_abort(cx)
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
 }
 
 impl<R: oio::BlockingWrite> oio::BlockingWrite for ConcurrentLimitWrapper<R> {
@@ -302,6 +306,10 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for ConcurrentLimitWrapper<R> {
     fn close(&mut self) -> Result<()> {
         self.inner.close()
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
 }
 
 impl<R: oio::List> oio::List for ConcurrentLimitWrapper<R> {
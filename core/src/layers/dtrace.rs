@@ -470,6 +470,10 @@ impl<R: oio::Write> oio::Write for DtraceLayerWrapper<R> {
                 err
             })
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
 }
 
 impl<R: oio::BlockingWrite> oio::BlockingWrite for DtraceLayerWrapper<R> {
@@ -501,4 +505,8 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for DtraceLayerWrapper<R> {
                 err
             })
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
 }
@@ -0,0 +1,757 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp::min;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::io;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use aes_gcm::aead::Aead;
+use aes_gcm::aead::AeadCore;
+use aes_gcm::aead::KeyInit;
+use aes_gcm::aead::OsRng;
+use aes_gcm::aead::Payload;
+use aes_gcm::Aes256Gcm;
+use aes_gcm::Nonce;
+use async_trait::async_trait;
+use bytes::Buf;
+use bytes::Bytes;
+use bytes::BytesMut;
+
+use crate::raw::*;
+use crate::*;
+
+/// The size of plaintext in every encrypted chunk.
+const CHUNK_SIZE: usize = 64 * 1024;
+/// The size of nonce that prepended to every encrypted chunk.
+const NONCE_SIZE: usize = 12;
+/// The size of authentication tag that appended to every encrypted chunk.
+const TAG_SIZE: usize = 16;
+/// The size of a full encrypted chunk on storage.
+const ENCRYPTED_CHUNK_SIZE: usize = CHUNK_SIZE + NONCE_SIZE + TAG_SIZE;
+
+/// Add transparent encryption for underlying services with AES-256-GCM.
+///
+/// # Format
+///
+/// Content will be split into chunks of 64 KiB. Every chunk is encrypted
+/// with a random nonce and stored as `nonce (12 bytes) | ciphertext | tag (16 bytes)`,
+/// so the n-th chunk always starts at `n * (64 KiB + 28)` on storage. The
+/// index of chunk and whether it's the last one are authenticated too, so
+/// reordering or truncating chunks will be detected.
+///
+/// Reading content that has been tampered will return
+/// [`ErrorKind::DataIntegrityCheckFailed`].
+///
+/// # Notes
+///
+/// - `stat` returns the plaintext size calculated from the size on storage,
+///   but `list` still returns the size on storage.
+/// - `append`, `content_md5`, `seek` and `presign` are not supported.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::EncryptionLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(EncryptionLayer::new([0; 32]))
+///     .finish();
+/// ```
+#[derive(Clone)]
+pub struct EncryptionLayer {
+    cipher: Aes256Gcm,
+}
+
+impl EncryptionLayer {
+    /// Create a new encryption layer with given 256 bits key.
+    pub fn new(key: [u8; 32]) -> Self {
+        Self {
+            cipher: Aes256Gcm::new(&key.into()),
+        }
+    }
+}
+
+impl Debug for EncryptionLayer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptionLayer").finish_non_exhaustive()
+    }
+}
+
+impl<A: Accessor> Layer<A> for EncryptionLayer {
+    type LayeredAccessor = EncryptionAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        EncryptionAccessor {
+            inner,
+            cipher: self.cipher.clone(),
+        }
+    }
+}
+
+pub struct EncryptionAccessor<A> {
+    inner: A,
+    cipher: Aes256Gcm,
+}
+
+impl<A: Debug> Debug for EncryptionAccessor<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptionAccessor")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Accessor> EncryptionAccessor<A> {
+    /// Build the decryptor for given plaintext range and convert the range
+    /// into the range on storage.
+    ///
+    /// We always read to the end on storage so that the last chunk can be
+    /// detected.
+    fn prepare_read(&self, range: BytesRange) -> (BytesRange, Decryptor) {
+        let offset = range.offset().unwrap_or_default();
+        let index = offset / CHUNK_SIZE as u64;
+
+        let dec = Decryptor {
+            cipher: self.cipher.clone(),
+            index,
+            skip: (offset % CHUNK_SIZE as u64) as usize,
+            remaining: range.size(),
+            buf: BytesMut::new(),
+            plain: Bytes::new(),
+            eof: false,
+            finished: false,
+        };
+
+        (
+            BytesRange::new(Some(index * ENCRYPTED_CHUNK_SIZE as u64), None),
+            dec,
+        )
+    }
+
+    fn check_write(&self, args: &OpWrite) -> Result<()> {
+        if args.append() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "encryption layer doesn't support append",
+            ));
+        }
+        if args.content_md5().is_some() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "encryption layer doesn't support content_md5",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn encryptor(&self) -> Encryptor {
+        Encryptor {
+            cipher: self.cipher.clone(),
+            index: 0,
+            buf: BytesMut::new(),
+            finished: false,
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<A: Accessor> LayeredAccessor for EncryptionAccessor<A> {
+    type Inner = A;
+    type Reader = EncryptionReader<A::Reader>;
+    type BlockingReader = EncryptionReader<A::BlockingReader>;
+    type Writer = EncryptionWriter<A::Writer>;
+    type BlockingWriter = EncryptionWriter<A::BlockingWriter>;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn metadata(&self) -> AccessorInfo {
+        let mut meta = self.inner.info();
+
+        let cap = meta.full_capability_mut();
+        cap.read_can_seek = false;
+        cap.write_can_append = false;
+        cap.write_with_content_md5 = false;
+        cap.presign = false;
+        cap.presign_read = false;
+        cap.presign_stat = false;
+        cap.presign_write = false;
+
+        meta
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let mut range = args.range();
+        // Suffix range can't be converted without knowing the total size.
+        if range.offset().is_none() && range.size().is_some() {
+            let size = LayeredAccessor::stat(self, path, OpStat::default())
+                .await?
+                .into_metadata()
+                .content_length();
+            range = range.complete(size);
+        }
+
+        let (wire_range, dec) = self.prepare_read(range);
+        let (_, r) = self.inner.read(path, args.with_range(wire_range)).await?;
+
+        Ok((RpRead::new(), EncryptionReader::new(r, dec)))
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let mut range = args.range();
+        // Suffix range can't be converted without knowing the total size.
+        if range.offset().is_none() && range.size().is_some() {
+            let size = LayeredAccessor::blocking_stat(self, path, OpStat::default())?
+                .into_metadata()
+                .content_length();
+            range = range.complete(size);
+        }
+
+        let (wire_range, dec) = self.prepare_read(range);
+        let (_, r) = self
+            .inner
+            .blocking_read(path, args.with_range(wire_range))?;
+
+        Ok((RpRead::new(), EncryptionReader::new(r, dec)))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.check_write(&args)?;
+
+        let (rp, w) = self.inner.write(path, args).await?;
+        Ok((rp, EncryptionWriter::new(w, self.encryptor())))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.check_write(&args)?;
+
+        let (rp, w) = self.inner.blocking_write(path, args)?;
+        Ok((rp, EncryptionWriter::new(w, self.encryptor())))
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.inner
+            .stat(path, args)
+            .await
+            .map(|rp| rp.map_metadata(plaintext_metadata))
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.inner
+            .blocking_stat(path, args)
+            .map(|rp| rp.map_metadata(plaintext_metadata))
+    }
+
+    async fn presign(&self, _: &str, _: OpPresign) -> Result<RpPresign> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "encryption layer doesn't support presign",
+        ))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+/// Convert the content length on storage into the plaintext size.
+fn plaintext_metadata(mut meta: Metadata) -> Metadata {
+    if meta.mode().is_file() {
+        let size = meta.content_length();
+        let chunks = size.div_ceil(ENCRYPTED_CHUNK_SIZE as u64);
+        meta.set_content_length(size.saturating_sub(chunks * (NONCE_SIZE + TAG_SIZE) as u64));
+    }
+    meta
+}
+
+/// Build the associated data of chunk so that its position is authenticated.
+fn associated_data(index: u64, last: bool) -> [u8; 9] {
+    let mut aad = [0; 9];
+    aad[..8].copy_from_slice(&index.to_be_bytes());
+    aad[8] = last as u8;
+    aad
+}
+
+struct Encryptor {
+    cipher: Aes256Gcm,
+    index: u64,
+    /// Plaintext that not encrypted yet.
+    buf: BytesMut,
+    finished: bool,
+}
+
+impl Encryptor {
+    /// Accept at most one chunk of data from given buf.
+    fn push(&mut self, bs: &dyn oio::WriteBuf) -> usize {
+        let chunk = bs.chunk();
+        let n = min(chunk.len(), CHUNK_SIZE);
+        self.buf.extend_from_slice(&chunk[..n]);
+        n
+    }
+
+    /// Seal a full chunk if we are sure that it's not the last one.
+    fn seal_full(&mut self) -> Result<Option<Bytes>> {
+        if self.buf.len() <= CHUNK_SIZE {
+            return Ok(None);
+        }
+
+        let plain = self.buf.split_to(CHUNK_SIZE);
+        self.seal(&plain, false).map(Some)
+    }
+
+    /// Seal all remaining data as the last chunk.
+    ///
+    /// The last chunk will be written even if it's empty so that truncation
+    /// can be detected.
+    fn seal_last(&mut self) -> Result<Option<Bytes>> {
+        if self.finished {
+            return Ok(None);
+        }
+        self.finished = true;
+
+        let plain = self.buf.split();
+        self.seal(&plain, true).map(Some)
+    }
+
+    fn seal(&mut self, plain: &[u8], last: bool) -> Result<Bytes> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let aad = associated_data(self.index, last);
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plain,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| {
+                Error::new(ErrorKind::Unexpected, "encrypt chunk failed")
+                    .with_context("chunk", self.index.to_string())
+            })?;
+        self.index += 1;
+
+        let mut bs = BytesMut::with_capacity(NONCE_SIZE + ciphertext.len());
+        bs.extend_from_slice(&nonce);
+        bs.extend_from_slice(&ciphertext);
+        Ok(bs.freeze())
+    }
+}
+
+struct Decryptor {
+    cipher: Aes256Gcm,
+    index: u64,
+    /// Bytes to skip in the first decrypted chunk.
+    skip: usize,
+    /// Bytes left to return if range size is specified.
+    remaining: Option<u64>,
+    /// Ciphertext that not decrypted yet.
+    buf: BytesMut,
+    /// Plaintext that not returned yet.
+    plain: Bytes,
+    eof: bool,
+    finished: bool,
+}
+
+impl Decryptor {
+    /// Push data read from storage, empty data means EOF.
+    fn push(&mut self, bs: &[u8]) {
+        if bs.is_empty() {
+            self.eof = true;
+        } else {
+            self.buf.extend_from_slice(bs);
+        }
+    }
+
+    /// Return at most `limit` bytes of plaintext, or `None` if more data is
+    /// needed to decrypt next chunk.
+    fn output(&mut self, limit: usize) -> Result<Option<Bytes>> {
+        loop {
+            if limit == 0 || self.remaining == Some(0) {
+                return Ok(Some(Bytes::new()));
+            }
+
+            if !self.plain.is_empty() {
+                let mut n = min(limit, self.plain.len());
+                if let Some(remaining) = self.remaining.as_mut() {
+                    n = min(n as u64, *remaining) as usize;
+                    *remaining -= n as u64;
+                }
+                return Ok(Some(self.plain.split_to(n)));
+            }
+
+            if self.finished {
+                return Ok(Some(Bytes::new()));
+            }
+
+            // A full chunk followed by more data must not be the last one.
+            if self.buf.len() > ENCRYPTED_CHUNK_SIZE {
+                let chunk = self.buf.split_to(ENCRYPTED_CHUNK_SIZE);
+                self.open(&chunk, false)?;
+                continue;
+            }
+
+            if self.eof {
+                let chunk = self.buf.split();
+                self.open(&chunk, true)?;
+                self.finished = true;
+                continue;
+            }
+
+            return Ok(None);
+        }
+    }
+
+    fn open(&mut self, chunk: &[u8], last: bool) -> Result<()> {
+        if chunk.len() < NONCE_SIZE + TAG_SIZE {
+            return Err(Error::new(
+                ErrorKind::DataIntegrityCheckFailed,
+                "encrypted content is truncated",
+            )
+            .with_context("chunk", self.index.to_string()));
+        }
+
+        let aad = associated_data(self.index, last);
+        let plain = self
+            .cipher
+            .decrypt(
+                Nonce::from_slice(&chunk[..NONCE_SIZE]),
+                Payload {
+                    msg: &chunk[NONCE_SIZE..],
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| {
+                Error::new(ErrorKind::DataIntegrityCheckFailed, "decrypt chunk failed")
+                    .with_context("chunk", self.index.to_string())
+            })?;
+        self.index += 1;
+
+        let mut plain = Bytes::from(plain);
+        if self.skip > 0 {
+            let n = min(self.skip, plain.len());
+            plain.advance(n);
+            self.skip -= n;
+        }
+        self.plain = plain;
+
+        Ok(())
+    }
+}
+
+/// EncryptionReader will decrypt content read from underlying reader.
+pub struct EncryptionReader<R> {
+    inner: R,
+    dec: Decryptor,
+    /// Reused buffer for blocking read.
+    scratch: Vec<u8>,
+}
+
+impl<R> EncryptionReader<R> {
+    fn new(inner: R, dec: Decryptor) -> Self {
+        Self {
+            inner,
+            dec,
+            scratch: Vec::new(),
+        }
+    }
+}
+
+impl<R: oio::Read> oio::Read for EncryptionReader<R> {
+    async fn read(&mut self, limit: usize) -> Result<Bytes> {
+        loop {
+            if let Some(bs) = self.dec.output(limit)? {
+                return Ok(bs);
+            }
+
+            let bs = self.inner.read(ENCRYPTED_CHUNK_SIZE).await?;
+            self.dec.push(&bs);
+        }
+    }
+
+    async fn seek(&mut self, _: io::SeekFrom) -> Result<u64> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "encryption layer doesn't support seek",
+        ))
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for EncryptionReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        loop {
+            if let Some(bs) = self.dec.output(buf.len())? {
+                buf[..bs.len()].copy_from_slice(&bs);
+                return Ok(bs.len());
+            }
+
+            self.scratch.resize(ENCRYPTED_CHUNK_SIZE, 0);
+            let n = self.inner.read(&mut self.scratch)?;
+            self.dec.push(&self.scratch[..n]);
+        }
+    }
+
+    fn seek(&mut self, _: io::SeekFrom) -> Result<u64> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "encryption layer doesn't support seek",
+        ))
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        loop {
+            match self.dec.output(CHUNK_SIZE) {
+                Ok(Some(bs)) if bs.is_empty() => return None,
+                Ok(Some(bs)) => return Some(Ok(bs)),
+                Ok(None) => {}
+                Err(err) => return Some(Err(err)),
+            }
+
+            match self.inner.next() {
+                Some(Ok(bs)) => self.dec.push(&bs),
+                Some(Err(err)) => return Some(Err(err)),
+                None => self.dec.push(&[]),
+            }
+        }
+    }
+}
+
+/// EncryptionWriter will encrypt content before writing into underlying writer.
+pub struct EncryptionWriter<W> {
+    inner: W,
+    enc: Encryptor,
+    /// Encrypted chunk that not written yet.
+    sealed: Option<Bytes>,
+    written: u64,
+}
+
+impl<W> EncryptionWriter<W> {
+    fn new(inner: W, enc: Encryptor) -> Self {
+        Self {
+            inner,
+            enc,
+            sealed: None,
+            written: 0,
+        }
+    }
+}
+
+impl<W: oio::Write> EncryptionWriter<W> {
+    /// Write the sealed chunk into inner writer until it's done.
+    fn poll_flush_sealed(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        while let Some(bs) = self.sealed.as_mut() {
+            let n = ready!(self.inner.poll_write(cx, &*bs))?;
+            bs.advance(n);
+            if bs.is_empty() {
+                self.sealed = None;
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: oio::Write> oio::Write for EncryptionWriter<W> {
+    fn poll_write(&mut self, cx: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>> {
+        loop {
+            ready!(self.poll_flush_sealed(cx))?;
+
+            match self.enc.seal_full()? {
+                Some(sealed) => self.sealed = Some(sealed),
+                None => break,
+            }
+        }
+
+        let n = self.enc.push(bs);
+        self.written += n as u64;
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        loop {
+            ready!(self.poll_flush_sealed(cx))?;
+
+            if let Some(sealed) = self.enc.seal_full()? {
+                self.sealed = Some(sealed);
+                continue;
+            }
+            match self.enc.seal_last()? {
+                Some(sealed) => self.sealed = Some(sealed),
+                None => break,
+            }
+        }
+
+        self.inner.poll_close(cx)
+    }
+
+    fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.sealed = None;
+        self.enc.buf.clear();
+
+        self.inner.poll_abort(cx)
+    }
+
+    fn written_bytes(&self) -> u64 {
+        self.written
+    }
+}
+
+impl<W: oio::BlockingWrite> EncryptionWriter<W> {
+    fn write_sealed(&mut self, mut bs: Bytes) -> Result<()> {
+        while !bs.is_empty() {
+            let n = self.inner.write(&bs)?;
+            bs.advance(n);
+        }
+
+        Ok(())
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for EncryptionWriter<W> {
+    fn write(&mut self, bs: &dyn oio::WriteBuf) -> Result<usize> {
+        while let Some(sealed) = self.enc.seal_full()? {
+            self.write_sealed(sealed)?;
+        }
+
+        let n = self.enc.push(bs);
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        while let Some(sealed) = self.enc.seal_full()? {
+            self.write_sealed(sealed)?;
+        }
+        if let Some(sealed) = self.enc.seal_last()? {
+            self.write_sealed(sealed)?;
+        }
+
+        self.inner.close()
+    }
+
+    fn written_bytes(&self) -> u64 {
+        self.written
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    fn content(size: usize) -> Vec<u8> {
+        (0..size).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() -> Result<()> {
+        let inner = Operator::new(Memory::default())?.finish();
+        let op = inner.clone().layer(EncryptionLayer::new([1; 32]));
+
+        for size in [0, 1, CHUNK_SIZE, CHUNK_SIZE * 2 + 7] {
+            let path = format!("test_{size}");
+            let data = content(size);
+            op.write(&path, data.clone()).await?;
+
+            // Content on storage must be encrypted.
+            let encrypted = inner.read(&path).await?;
+            assert_ne!(encrypted, data);
+            assert_eq!(op.stat(&path).await?.content_length(), size as u64);
+
+            assert_eq!(op.read(&path).await?, data);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_with_range() -> Result<()> {
+        let op = Operator::new(Memory::default())?
+            .finish()
+            .layer(EncryptionLayer::new([1; 32]));
+
+        let data = content(CHUNK_SIZE * 3);
+        op.write("test", data.clone()).await?;
+
+        let offset = CHUNK_SIZE + 100;
+        let bs = op
+            .read_with("test")
+            .range(offset as u64..(offset + CHUNK_SIZE) as u64)
+            .await?;
+        assert_eq!(bs, data[offset..offset + CHUNK_SIZE]);
+
+        let bs = op.read_with("test").range(0..10).await?;
+        assert_eq!(bs, data[..10]);
+
+        // `..10` is a suffix range which reads the last 10 bytes.
+        let bs = op.read_with("test").range(..10).await?;
+        assert_eq!(bs, data[data.len() - 10..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_tampered() -> Result<()> {
+        let inner = Operator::new(Memory::default())?.finish();
+        let op = inner.clone().layer(EncryptionLayer::new([1; 32]));
+
+        op.write("test", content(CHUNK_SIZE + 1)).await?;
+
+        let mut encrypted = inner.read("test").await?;
+        encrypted[NONCE_SIZE + 1] ^= 1;
+        inner.write("test", encrypted).await?;
+
+        let err = op.read("test").await.expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::DataIntegrityCheckFailed);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_truncated() -> Result<()> {
+        let inner = Operator::new(Memory::default())?.finish();
+        let op = inner.clone().layer(EncryptionLayer::new([1; 32]));
+
+        op.write("test", content(CHUNK_SIZE + 1)).await?;
+
+        let mut encrypted = inner.read("test").await?;
+        encrypted.truncate(ENCRYPTED_CHUNK_SIZE);
+        inner.write("test", encrypted).await?;
+
+        let err = op.read("test").await.expect_err("read must fail");
+        assert_eq!(err.kind(), ErrorKind::DataIntegrityCheckFailed);
+
+        Ok(())
+    }
+}
@@ -162,6 +162,143 @@ impl<A: Accessor> LayeredAccessor for ErrorContextAccessor<A> {
             .await
     }
 
+    async fn hard_link(&self, from: &str, to: &str, args: OpHardLink) -> Result<RpHardLink> {
+        self.inner
+            .hard_link(from, to, args)
+            .map_err(|err| {
+                err.with_operation(Operation::HardLink)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("from", from)
+                    .with_context("to", to)
+            })
+            .await
+    }
+
+    async fn symlink(&self, target: &str, link: &str, args: OpSymlink) -> Result<RpSymlink> {
+        self.inner
+            .symlink(target, link, args)
+            .map_err(|err| {
+                err.with_operation(Operation::Symlink)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("target", target)
+                    .with_context("link", link)
+            })
+            .await
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        self.inner
+            .set_permissions(path, args)
+            .map_err(|err| {
+                err.with_operation(Operation::SetPermissions)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("path", path)
+            })
+            .await
+    }
+
+    async fn statvfs(&self, args: OpStatvfs) -> Result<RpStatvfs> {
+        self.inner
+            .statvfs(args)
+            .map_err(|err| {
+                err.with_operation(Operation::Statvfs)
+                    .with_context("service", self.meta.scheme())
+            })
+            .await
+    }
+
+    async fn truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        self.inner
+            .truncate(path, args)
+            .map_err(|err| {
+                err.with_operation(Operation::Truncate)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("path", path)
+            })
+            .await
+    }
+
+    async fn fallocate(&self, path: &str, args: OpFallocate) -> Result<RpFallocate> {
+        self.inner
+            .fallocate(path, args)
+            .map_err(|err| {
+                err.with_operation(Operation::Fallocate)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("path", path)
+            })
+            .await
+    }
+
+    async fn rename_noreplace(
+        &self,
+        from: &str,
+        to: &str,
+        args: OpRenameNoreplace,
+    ) -> Result<RpRenameNoreplace> {
+        self.inner
+            .rename_noreplace(from, to, args)
+            .map_err(|err| {
+                err.with_operation(Operation::RenameNoreplace)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("from", from)
+                    .with_context("to", to)
+            })
+            .await
+    }
+
+    async fn setxattr(&self, path: &str, args: OpSetxattr) -> Result<RpSetxattr> {
+        let name = args.name().to_string();
+        self.inner
+            .setxattr(path, args)
+            .map_err(|err| {
+                err.with_operation(Operation::Setxattr)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("path", path)
+                    .with_context("name", name)
+            })
+            .await
+    }
+
+    async fn getxattr(&self, path: &str, args: OpGetxattr) -> Result<RpGetxattr> {
+        let name = args.name().to_string();
+        self.inner
+            .getxattr(path, args)
+            .map_err(|err| {
+                err.with_operation(Operation::Getxattr)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("path", path)
+                    .with_context("name", name)
+            })
+            .await
+    }
+
+    async fn listxattr(&self, path: &str, args: OpListxattr) -> Result<RpListxattr> {
+        self.inner
+            .listxattr(path, args)
+            .map_err(|err| {
+                err.with_operation(Operation::Listxattr)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("path", path)
+            })
+            .await
+    }
+
+    async fn list_buckets(&self, args: OpListBuckets) -> Result<RpListBuckets> {
+        let project = args.project().to_string();
+        self.inner
+            .list_buckets(args)
+            .map_err(|err| {
+                err.with_operation(Operation::ListBuckets)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("project", project)
+            })
+            .await
+    }
+
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         self.inner
             .stat(path, args)
@@ -364,6 +501,15 @@ impl<T: oio::Read> oio::Read for ErrorContextWrapper<T> {
                 .with_context("path", &self.path)
         })
     }
+
+    async fn read_at(&self, offset: u64, limit: usize) -> Result<Bytes> {
+        self.inner.read_at(offset, limit).await.map_err(|err| {
+            err.with_operation(ReadOperation::ReadAt)
+                .with_context("service", self.scheme)
+                .with_context("path", &self.path)
+                .with_context("offset", offset.to_string())
+        })
+    }
 }
 
 impl<T: oio::BlockingRead> oio::BlockingRead for ErrorContextWrapper<T> {
@@ -422,6 +568,10 @@ impl<T: oio::Write> oio::Write for ErrorContextWrapper<T> {
                 .with_context("path", &self.path)
         })
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
 }
 
 impl<T: oio::BlockingWrite> oio::BlockingWrite for ErrorContextWrapper<T> {
@@ -441,6 +591,10 @@ impl<T: oio::BlockingWrite> oio::BlockingWrite for ErrorContextWrapper<T> {
                 .with_context("path", &self.path)
         })
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
 }
 
 #[async_trait::async_trait]
@@ -0,0 +1,249 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use async_trait::async_trait;
+
+use crate::raw::*;
+use crate::*;
+
+/// Add a read-only guard for the underlying storage services.
+///
+/// All operations that could mutate the storage (`write`, `delete`, `rename`,
+/// `copy`, `create_dir`, `batch`, `hard_link`, `symlink`, `set_permissions`,
+/// `truncate`, `fallocate`, `rename_noreplace`, `setxattr` and presigned
+/// `write`) will be refused with [`ErrorKind::PermissionDenied`] while `read`,
+/// `stat`, `list` and other read only operations are passed through as is.
+///
+/// # Notes
+///
+/// This layer only works at OpenDAL level, it doesn't change the permissions
+/// of the underlying storage. Capabilities of mutating operations will be
+/// disabled so that callers can check them before calling.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::ImmutableLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(ImmutableLayer)
+///     .finish();
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ImmutableLayer;
+
+impl<A: Accessor> Layer<A> for ImmutableLayer {
+    type LayeredAccessor = ImmutableAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        ImmutableAccessor { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImmutableAccessor<A: Accessor> {
+    inner: A,
+}
+
+impl<A: Accessor> ImmutableAccessor<A> {
+    fn new_permission_denied_error(&self, op: Operation) -> Error {
+        Error::new(
+            ErrorKind::PermissionDenied,
+            "operation is not allowed on immutable storage",
+        )
+        .with_operation(op)
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<A: Accessor> LayeredAccessor for ImmutableAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    /// Disable all mutating capabilities of underlying storage services.
+    fn metadata(&self) -> AccessorInfo {
+        let mut meta = self.inner.info();
+
+        let cap = meta.full_capability_mut();
+        cap.write = false;
+        cap.create_dir = false;
+        cap.delete = false;
+        cap.copy = false;
+        cap.rename = false;
+        cap.batch = false;
+        cap.batch_delete = false;
+        cap.hard_link = false;
+        cap.symlink = false;
+        cap.set_permissions = false;
+        cap.truncate = false;
+        cap.fallocate = false;
+        cap.rename_noreplace = false;
+        cap.presign_write = false;
+        // `xattr` is kept since `getxattr` and `listxattr` are still allowed,
+        // only `setxattr` will be refused.
+
+        meta
+    }
+
+    async fn create_dir(&self, _: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        Err(self.new_permission_denied_error(Operation::CreateDir))
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, _: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        Err(self.new_permission_denied_error(Operation::Write))
+    }
+
+    async fn copy(&self, _: &str, _: &str, _: OpCopy) -> Result<RpCopy> {
+        Err(self.new_permission_denied_error(Operation::Copy))
+    }
+
+    async fn rename(&self, _: &str, _: &str, _: OpRename) -> Result<RpRename> {
+        Err(self.new_permission_denied_error(Operation::Rename))
+    }
+
+    async fn delete(&self, _: &str, _: OpDelete) -> Result<RpDelete> {
+        Err(self.new_permission_denied_error(Operation::Delete))
+    }
+
+    async fn hard_link(&self, _: &str, _: &str, _: OpHardLink) -> Result<RpHardLink> {
+        Err(self.new_permission_denied_error(Operation::HardLink))
+    }
+
+    async fn symlink(&self, _: &str, _: &str, _: OpSymlink) -> Result<RpSymlink> {
+        Err(self.new_permission_denied_error(Operation::Symlink))
+    }
+
+    async fn set_permissions(&self, _: &str, _: OpSetPermissions) -> Result<RpSetPermissions> {
+        Err(self.new_permission_denied_error(Operation::SetPermissions))
+    }
+
+    async fn truncate(&self, _: &str, _: OpTruncate) -> Result<RpTruncate> {
+        Err(self.new_permission_denied_error(Operation::Truncate))
+    }
+
+    async fn fallocate(&self, _: &str, _: OpFallocate) -> Result<RpFallocate> {
+        Err(self.new_permission_denied_error(Operation::Fallocate))
+    }
+
+    async fn rename_noreplace(
+        &self,
+        _: &str,
+        _: &str,
+        _: OpRenameNoreplace,
+    ) -> Result<RpRenameNoreplace> {
+        Err(self.new_permission_denied_error(Operation::RenameNoreplace))
+    }
+
+    async fn setxattr(&self, _: &str, _: OpSetxattr) -> Result<RpSetxattr> {
+        Err(self.new_permission_denied_error(Operation::Setxattr))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn batch(&self, _: OpBatch) -> Result<RpBatch> {
+        Err(self.new_permission_denied_error(Operation::Batch))
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        if let PresignOperation::Write(_) = args.operation() {
+            return Err(self.new_permission_denied_error(Operation::Presign));
+        }
+
+        self.inner.presign(path, args).await
+    }
+
+    fn blocking_create_dir(&self, _: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        Err(self.new_permission_denied_error(Operation::BlockingCreateDir))
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, _: &str, _: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        Err(self.new_permission_denied_error(Operation::BlockingWrite))
+    }
+
+    fn blocking_copy(&self, _: &str, _: &str, _: OpCopy) -> Result<RpCopy> {
+        Err(self.new_permission_denied_error(Operation::BlockingCopy))
+    }
+
+    fn blocking_rename(&self, _: &str, _: &str, _: OpRename) -> Result<RpRename> {
+        Err(self.new_permission_denied_error(Operation::BlockingRename))
+    }
+
+    fn blocking_delete(&self, _: &str, _: OpDelete) -> Result<RpDelete> {
+        Err(self.new_permission_denied_error(Operation::BlockingDelete))
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_immutable() {
+        let op = Operator::new(Memory::default()).unwrap().finish();
+        op.write("test", "Hello, World!").await.unwrap();
+
+        let op = op.layer(ImmutableLayer);
+
+        let bs = op.read("test").await.unwrap();
+        assert_eq!(bs, b"Hello, World!");
+
+        let err = op.write("test", "Hello").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        let err = op.delete("test").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        let err = op.create_dir("dir/").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        let err = op.truncate("test", 5).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        let cap = op.info().full_capability();
+        assert!(!cap.truncate);
+        assert!(!cap.presign_write);
+    }
+}
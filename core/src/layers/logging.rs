@@ -1275,6 +1275,10 @@ impl<W: oio::Write> oio::Write for LoggingWriter<W> {
             }
         }
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
 }
 
 impl<W: oio::BlockingWrite> oio::BlockingWrite for LoggingWriter<W> {
@@ -1342,6 +1346,10 @@ impl<W: oio::BlockingWrite> oio::BlockingWrite for LoggingWriter<W> {
             }
         }
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
 }
 
 pub struct LoggingLister<P> {
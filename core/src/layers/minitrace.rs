@@ -346,6 +346,10 @@ impl<R: oio::Write> oio::Write for MinitraceWrapper<R> {
         let _span = LocalSpan::enter_with_local_parent(WriteOperation::Close.into_static());
         self.inner.poll_close(cx)
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
 }
 
 impl<R: oio::BlockingWrite> oio::BlockingWrite for MinitraceWrapper<R> {
@@ -360,6 +364,10 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for MinitraceWrapper<R> {
         let _span = LocalSpan::enter_with_local_parent(WriteOperation::BlockingClose.into_static());
         self.inner.close()
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
 }
 
 impl<R: oio::List> oio::List for MinitraceWrapper<R> {
@@ -0,0 +1,513 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Display;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+use bytes::Buf;
+use bytes::Bytes;
+use log::warn;
+
+use crate::raw::oio::WriteOperation;
+use crate::raw::*;
+use crate::*;
+
+/// Mirror all changes to a secondary storage for disaster recovery.
+///
+/// # Behavior
+///
+/// - `write`, `create_dir`, `delete`, `copy`, `rename`, `batch` and other
+///   mutating operations like `truncate` and `setxattr` will be sent to both
+///   the underlying service (as primary) and the secondary at the same time.
+/// - Failures on the secondary will be logged as warnings and never returned,
+///   so the primary will not be blocked by the secondary.
+/// - `read`, `stat` and `list` are served by the primary only.
+///
+/// # Notes
+///
+/// Data written to the primary will be written to the secondary before
+/// accepting more data, so a slow secondary will slow down writing too.
+///
+/// # Examples
+///
+/// ```no_run
+/// use anyhow::Result;
+/// use opendal::layers::MirrorLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// # fn main() -> Result<()> {
+/// let secondary = Operator::new(services::Memory::default())?.finish();
+///
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(MirrorLayer::new(secondary))
+///     .finish();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct MirrorLayer {
+    secondary: Operator,
+}
+
+impl MirrorLayer {
+    /// Create a new MirrorLayer that mirrors all changes to given operator.
+    pub fn new(secondary: Operator) -> Self {
+        Self { secondary }
+    }
+}
+
+impl<A: Accessor> Layer<A> for MirrorLayer {
+    type LayeredAccessor = MirrorAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        MirrorAccessor {
+            inner,
+            secondary: self.secondary.clone().into_inner(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MirrorAccessor<A: Accessor> {
+    inner: A,
+    secondary: FusedAccessor,
+}
+
+/// Log the error of secondary and ignore it.
+fn check_secondary<T>(op: impl Display, path: &str, res: Result<T>) -> Option<T> {
+    match res {
+        Ok(v) => Some(v),
+        Err(err) => {
+            warn!("mirror: {op} {path} on secondary failed: {err}");
+            None
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<A: Accessor> LayeredAccessor for MirrorAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = MirrorWriter<A::Writer, oio::Writer>;
+    type BlockingWriter = MirrorWriter<A::BlockingWriter, oio::BlockingWriter>;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let (primary, secondary) = futures::future::join(
+            self.inner.create_dir(path, args.clone()),
+            self.secondary.create_dir(path, args),
+        )
+        .await;
+        check_secondary(Operation::CreateDir, path, secondary);
+
+        primary
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let (primary, secondary) = futures::future::join(
+            self.inner.write(path, args.clone()),
+            self.secondary.write(path, args),
+        )
+        .await;
+        let (rp, w) = primary?;
+        let secondary = check_secondary(Operation::Write, path, secondary).map(|(_, w)| w);
+
+        Ok((rp, MirrorWriter::new(path, w, secondary)))
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let (primary, secondary) = futures::future::join(
+            self.inner.copy(from, to, args.clone()),
+            self.secondary.copy(from, to, args),
+        )
+        .await;
+        check_secondary(Operation::Copy, to, secondary);
+
+        primary
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let (primary, secondary) = futures::future::join(
+            self.inner.rename(from, to, args.clone()),
+            self.secondary.rename(from, to, args),
+        )
+        .await;
+        check_secondary(Operation::Rename, to, secondary);
+
+        primary
+    }
+
+    async fn hard_link(&self, from: &str, to: &str, args: OpHardLink) -> Result<RpHardLink> {
+        let (primary, secondary) = futures::future::join(
+            self.inner.hard_link(from, to, args.clone()),
+            self.secondary.hard_link(from, to, args),
+        )
+        .await;
+        check_secondary(Operation::HardLink, to, secondary);
+
+        primary
+    }
+
+    async fn symlink(&self, target: &str, link: &str, args: OpSymlink) -> Result<RpSymlink> {
+        let (primary, secondary) = futures::future::join(
+            self.inner.symlink(target, link, args.clone()),
+            self.secondary.symlink(target, link, args),
+        )
+        .await;
+        check_secondary(Operation::Symlink, link, secondary);
+
+        primary
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        let (primary, secondary) = futures::future::join(
+            self.inner.set_permissions(path, args.clone()),
+            self.secondary.set_permissions(path, args),
+        )
+        .await;
+        check_secondary(Operation::SetPermissions, path, secondary);
+
+        primary
+    }
+
+    async fn truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        let (primary, secondary) = futures::future::join(
+            self.inner.truncate(path, args.clone()),
+            self.secondary.truncate(path, args),
+        )
+        .await;
+        check_secondary(Operation::Truncate, path, secondary);
+
+        primary
+    }
+
+    async fn fallocate(&self, path: &str, args: OpFallocate) -> Result<RpFallocate> {
+        let (primary, secondary) = futures::future::join(
+            self.inner.fallocate(path, args.clone()),
+            self.secondary.fallocate(path, args),
+        )
+        .await;
+        check_secondary(Operation::Fallocate, path, secondary);
+
+        primary
+    }
+
+    async fn rename_noreplace(
+        &self,
+        from: &str,
+        to: &str,
+        args: OpRenameNoreplace,
+    ) -> Result<RpRenameNoreplace> {
+        let (primary, secondary) = futures::future::join(
+            self.inner.rename_noreplace(from, to, args.clone()),
+            self.secondary.rename_noreplace(from, to, args),
+        )
+        .await;
+        check_secondary(Operation::RenameNoreplace, to, secondary);
+
+        primary
+    }
+
+    async fn setxattr(&self, path: &str, args: OpSetxattr) -> Result<RpSetxattr> {
+        let (primary, secondary) = futures::future::join(
+            self.inner.setxattr(path, args.clone()),
+            self.secondary.setxattr(path, args),
+        )
+        .await;
+        check_secondary(Operation::Setxattr, path, secondary);
+
+        primary
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let (primary, secondary) = futures::future::join(
+            self.inner.delete(path, args.clone()),
+            self.secondary.delete(path, args),
+        )
+        .await;
+        check_secondary(Operation::Delete, path, secondary);
+
+        primary
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let (primary, secondary) =
+            futures::future::join(self.inner.batch(args.clone()), self.secondary.batch(args)).await;
+        check_secondary(Operation::Batch, "/", secondary);
+
+        primary
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let rp = self.inner.blocking_create_dir(path, args.clone())?;
+        let res = self.secondary.blocking_create_dir(path, args);
+        check_secondary(Operation::BlockingCreateDir, path, res);
+
+        Ok(rp)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let (rp, w) = self.inner.blocking_write(path, args.clone())?;
+        let res = self.secondary.blocking_write(path, args);
+        let secondary = check_secondary(Operation::BlockingWrite, path, res).map(|(_, w)| w);
+
+        Ok((rp, MirrorWriter::new(path, w, secondary)))
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let rp = self.inner.blocking_copy(from, to, args.clone())?;
+        let res = self.secondary.blocking_copy(from, to, args);
+        check_secondary(Operation::BlockingCopy, to, res);
+
+        Ok(rp)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let rp = self.inner.blocking_rename(from, to, args.clone())?;
+        let res = self.secondary.blocking_rename(from, to, args);
+        check_secondary(Operation::BlockingRename, to, res);
+
+        Ok(rp)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let rp = self.inner.blocking_delete(path, args.clone())?;
+        let res = self.secondary.blocking_delete(path, args);
+        check_secondary(Operation::BlockingDelete, path, res);
+
+        Ok(rp)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+/// MirrorWriter writes data into both primary and secondary writers.
+///
+/// The secondary will be dropped once it returns an error.
+pub struct MirrorWriter<W, S> {
+    path: String,
+    primary: W,
+    secondary: Option<S>,
+
+    /// Data that has been written to primary but not secondary yet.
+    pending: Option<Bytes>,
+    /// Result of closing or aborting primary.
+    primary_result: Option<Result<()>>,
+}
+
+impl<W, S> MirrorWriter<W, S> {
+    fn new(path: &str, primary: W, secondary: Option<S>) -> Self {
+        Self {
+            path: path.to_string(),
+            primary,
+            secondary,
+            pending: None,
+            primary_result: None,
+        }
+    }
+}
+
+impl<W: oio::Write> MirrorWriter<W, oio::Writer> {
+    /// Write pending data into secondary until it's done or failed.
+    fn poll_write_secondary(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        loop {
+            let Some(bs) = self.pending.as_mut() else {
+                return Poll::Ready(());
+            };
+            let Some(w) = self.secondary.as_mut() else {
+                self.pending = None;
+                return Poll::Ready(());
+            };
+
+            match ready!(w.poll_write(cx, &*bs)) {
+                Ok(n) => {
+                    bs.advance(n);
+                    if bs.is_empty() {
+                        self.pending = None;
+                    }
+                }
+                Err(err) => {
+                    check_secondary::<()>(WriteOperation::Write, &self.path, Err(err));
+                    self.secondary = None;
+                    self.pending = None;
+                }
+            }
+        }
+    }
+}
+
+impl<W: oio::Write> oio::Write for MirrorWriter<W, oio::Writer> {
+    fn poll_write(&mut self, cx: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>> {
+        ready!(self.poll_write_secondary(cx));
+
+        let n = ready!(self.primary.poll_write(cx, bs))?;
+        if self.secondary.is_some() {
+            self.pending = Some(bs.bytes(n));
+        }
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        ready!(self.poll_write_secondary(cx));
+
+        if self.primary_result.is_none() {
+            if let Poll::Ready(res) = self.primary.poll_close(cx) {
+                self.primary_result = Some(res);
+            }
+        }
+        if let Some(w) = self.secondary.as_mut() {
+            let res = ready!(w.poll_close(cx));
+            check_secondary(WriteOperation::Close, &self.path, res);
+            self.secondary = None;
+        }
+
+        match self.primary_result.take() {
+            Some(res) => Poll::Ready(res),
+            None => Poll::Pending,
+        }
+    }
+
+    fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.pending = None;
+
+        if self.primary_result.is_none() {
+            if let Poll::Ready(res) = self.primary.poll_abort(cx) {
+                self.primary_result = Some(res);
+            }
+        }
+        if let Some(w) = self.secondary.as_mut() {
+            let res = ready!(w.poll_abort(cx));
+            check_secondary(WriteOperation::Abort, &self.path, res);
+            self.secondary = None;
+        }
+
+        match self.primary_result.take() {
+            Some(res) => Poll::Ready(res),
+            None => Poll::Pending,
+        }
+    }
+
+    fn written_bytes(&self) -> u64 {
+        self.primary.written_bytes()
+    }
+}
+
+impl<W: oio::BlockingWrite> oio::BlockingWrite for MirrorWriter<W, oio::BlockingWriter> {
+    fn write(&mut self, bs: &dyn oio::WriteBuf) -> Result<usize> {
+        let n = self.primary.write(bs)?;
+
+        if let Some(w) = self.secondary.as_mut() {
+            let mut data = bs.bytes(n);
+            while !data.is_empty() {
+                match w.write(&data) {
+                    Ok(written) => data.advance(written),
+                    Err(err) => {
+                        check_secondary::<()>(WriteOperation::BlockingWrite, &self.path, Err(err));
+                        self.secondary = None;
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(n)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.primary.close()?;
+
+        if let Some(mut w) = self.secondary.take() {
+            check_secondary(WriteOperation::BlockingClose, &self.path, w.close());
+        }
+
+        Ok(())
+    }
+
+    fn written_bytes(&self) -> u64 {
+        self.primary.written_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layers::ImmutableLayer;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_mirror() -> Result<()> {
+        let secondary = Operator::new(Memory::default())?.finish();
+        let op = Operator::new(Memory::default())?
+            .layer(MirrorLayer::new(secondary.clone()))
+            .finish();
+
+        op.write("test", "hello").await?;
+        assert_eq!(op.read("test").await?, b"hello");
+        assert_eq!(secondary.read("test").await?, b"hello");
+
+        op.delete("test").await?;
+        assert!(!op.is_exist("test").await?);
+        assert!(!secondary.is_exist("test").await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mirror_secondary_failed() -> Result<()> {
+        let secondary = Operator::new(Memory::default())?
+            .layer(ImmutableLayer)
+            .finish();
+        let op = Operator::new(Memory::default())?
+            .layer(MirrorLayer::new(secondary.clone()))
+            .finish();
+
+        op.write("test", "hello").await?;
+        assert_eq!(op.read("test").await?, b"hello");
+        assert!(!secondary.is_exist("test").await?);
+
+        Ok(())
+    }
+}
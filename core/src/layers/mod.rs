@@ -43,16 +43,56 @@ mod blocking;
 #[cfg(feature = "layers-blocking")]
 pub use blocking::BlockingLayer;
 
+#[cfg(feature = "layers-caching")]
+mod caching;
+#[cfg(feature = "layers-caching")]
+pub use caching::CachingLayer;
+
 #[cfg(feature = "layers-chaos")]
 mod chaos;
 #[cfg(feature = "layers-chaos")]
 pub use chaos::ChaosLayer;
 
+#[cfg(feature = "layers-compression")]
+mod compression;
+#[cfg(feature = "layers-compression")]
+pub use compression::CompressionLayer;
+
+#[cfg(feature = "layers-encryption")]
+mod encryption;
+#[cfg(feature = "layers-encryption")]
+pub use encryption::EncryptionLayer;
+
+#[cfg(feature = "layers-immutable")]
+mod immutable;
+#[cfg(feature = "layers-immutable")]
+pub use immutable::ImmutableLayer;
+
 #[cfg(feature = "layers-metrics")]
 mod metrics;
 #[cfg(feature = "layers-metrics")]
 pub use self::metrics::MetricsLayer;
 
+#[cfg(feature = "layers-mirror")]
+mod mirror;
+#[cfg(feature = "layers-mirror")]
+pub use mirror::MirrorLayer;
+
+#[cfg(feature = "layers-namespace")]
+mod namespace;
+#[cfg(feature = "layers-namespace")]
+pub use namespace::NamespaceLayer;
+
+#[cfg(feature = "layers-path-normalization")]
+mod path_normalization;
+#[cfg(feature = "layers-path-normalization")]
+pub use path_normalization::PathNormalizationLayer;
+
+#[cfg(feature = "layers-prefix")]
+mod prefix;
+#[cfg(feature = "layers-prefix")]
+pub use prefix::PrefixLayer;
+
 #[cfg(feature = "layers-prometheus")]
 mod prometheus;
 #[cfg(feature = "layers-prometheus")]
@@ -63,6 +103,11 @@ mod prometheus_client;
 #[cfg(feature = "layers-prometheus-client")]
 pub use self::prometheus_client::PrometheusClientLayer;
 
+#[cfg(feature = "layers-quota")]
+mod quota;
+#[cfg(feature = "layers-quota")]
+pub use quota::QuotaLayer;
+
 mod retry;
 pub use self::retry::RetryInterceptor;
 pub use self::retry::RetryLayer;
@@ -72,6 +117,11 @@ mod tracing;
 #[cfg(feature = "layers-tracing")]
 pub use self::tracing::TracingLayer;
 
+#[cfg(feature = "layers-versioning")]
+mod versioning;
+#[cfg(feature = "layers-versioning")]
+pub use versioning::VersioningLayer;
+
 #[cfg(feature = "layers-minitrace")]
 mod minitrace;
 #[cfg(feature = "layers-minitrace")]
@@ -0,0 +1,342 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::prefix::join;
+use super::prefix::normalize_prefix;
+use super::prefix::strip;
+use super::prefix::PrefixLister;
+use crate::raw::*;
+use crate::*;
+
+/// Partition the underlying services by a namespace resolved at runtime.
+///
+/// # Behavior
+///
+/// - The namespace function will be called for every operation, and its
+///   result will be used as the prefix like [`PrefixLayer`](super::PrefixLayer).
+/// - The namespace is resolved when the operation starts, so readers, writers
+///   and listers created by it keep using the same namespace.
+///
+/// # Notes
+///
+/// An empty namespace refers to the root of underlying services, make sure
+/// the namespace function always returns a valid id.
+///
+/// # Examples
+///
+/// Read tenant id from a task local variable:
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::NamespaceLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// tokio::task_local! {
+///     static TENANT: String;
+/// }
+///
+/// # async fn test() -> Result<()> {
+/// let op = Operator::new(services::Memory::default())?
+///     .layer(NamespaceLayer::new(|| TENANT.with(|v| v.clone())))
+///     .finish();
+///
+/// TENANT
+///     .scope("tenant_a".to_string(), async {
+///         // Written to `tenant_a/test`.
+///         op.write("test", "Hello").await
+///     })
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct NamespaceLayer<F> {
+    namespace: Arc<F>,
+}
+
+impl<F> Clone for NamespaceLayer<F> {
+    fn clone(&self) -> Self {
+        Self {
+            namespace: self.namespace.clone(),
+        }
+    }
+}
+
+impl<F> NamespaceLayer<F>
+where
+    F: Fn() -> String + Send + Sync + 'static,
+{
+    /// Create a new NamespaceLayer with the function to resolve namespace.
+    pub fn new(namespace: F) -> Self {
+        Self {
+            namespace: Arc::new(namespace),
+        }
+    }
+}
+
+impl<A: Accessor, F> Layer<A> for NamespaceLayer<F>
+where
+    F: Fn() -> String + Send + Sync + 'static,
+{
+    type LayeredAccessor = NamespaceAccessor<A, F>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        NamespaceAccessor {
+            inner,
+            namespace: self.namespace.clone(),
+        }
+    }
+}
+
+pub struct NamespaceAccessor<A: Accessor, F> {
+    inner: A,
+    namespace: Arc<F>,
+}
+
+impl<A: Accessor, F> Debug for NamespaceAccessor<A, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NamespaceAccessor")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Accessor, F> NamespaceAccessor<A, F>
+where
+    F: Fn() -> String + Send + Sync + 'static,
+{
+    /// Resolve current namespace into a normalized prefix.
+    fn prefix(&self) -> Arc<String> {
+        Arc::new(normalize_prefix(&(self.namespace.as_ref())()))
+    }
+
+    fn join(&self, path: &str) -> String {
+        join(&self.prefix(), path)
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<A: Accessor, F> LayeredAccessor for NamespaceAccessor<A, F>
+where
+    F: Fn() -> String + Send + Sync + 'static,
+{
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = PrefixLister<A::Lister>;
+    type BlockingLister = PrefixLister<A::BlockingLister>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.inner.create_dir(&self.join(path), args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(&self.join(path), args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(&self.join(path), args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let prefix = self.prefix();
+        self.inner
+            .copy(&join(&prefix, from), &join(&prefix, to), args)
+            .await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let prefix = self.prefix();
+        self.inner
+            .rename(&join(&prefix, from), &join(&prefix, to), args)
+            .await
+    }
+
+    async fn hard_link(&self, from: &str, to: &str, args: OpHardLink) -> Result<RpHardLink> {
+        let prefix = self.prefix();
+        self.inner
+            .hard_link(&join(&prefix, from), &join(&prefix, to), args)
+            .await
+    }
+
+    async fn symlink(&self, target: &str, link: &str, args: OpSymlink) -> Result<RpSymlink> {
+        self.inner.symlink(target, &self.join(link), args).await
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        self.inner.set_permissions(&self.join(path), args).await
+    }
+
+    async fn truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        self.inner.truncate(&self.join(path), args).await
+    }
+
+    async fn fallocate(&self, path: &str, args: OpFallocate) -> Result<RpFallocate> {
+        self.inner.fallocate(&self.join(path), args).await
+    }
+
+    async fn rename_noreplace(
+        &self,
+        from: &str,
+        to: &str,
+        args: OpRenameNoreplace,
+    ) -> Result<RpRenameNoreplace> {
+        let prefix = self.prefix();
+        self.inner
+            .rename_noreplace(&join(&prefix, from), &join(&prefix, to), args)
+            .await
+    }
+
+    async fn setxattr(&self, path: &str, args: OpSetxattr) -> Result<RpSetxattr> {
+        self.inner.setxattr(&self.join(path), args).await
+    }
+
+    async fn getxattr(&self, path: &str, args: OpGetxattr) -> Result<RpGetxattr> {
+        self.inner.getxattr(&self.join(path), args).await
+    }
+
+    async fn listxattr(&self, path: &str, args: OpListxattr) -> Result<RpListxattr> {
+        self.inner.listxattr(&self.join(path), args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.inner.stat(&self.join(path), args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.inner.delete(&self.join(path), args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let prefix = self.prefix();
+        self.inner
+            .list(&join(&prefix, path), args)
+            .await
+            .map(|(rp, l)| (rp, PrefixLister::new(l, prefix)))
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let prefix = self.prefix();
+        let ops = args
+            .into_operation()
+            .into_iter()
+            .map(|(path, op)| (join(&prefix, &path), op))
+            .collect();
+
+        let results = self
+            .inner
+            .batch(OpBatch::new(ops))
+            .await?
+            .into_results()
+            .into_iter()
+            .map(|(path, res)| (strip(&prefix, &path), res))
+            .collect();
+        Ok(RpBatch::new(results))
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.inner.presign(&self.join(path), args).await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.inner.blocking_create_dir(&self.join(path), args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(&self.join(path), args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(&self.join(path), args)
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let prefix = self.prefix();
+        self.inner
+            .blocking_copy(&join(&prefix, from), &join(&prefix, to), args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let prefix = self.prefix();
+        self.inner
+            .blocking_rename(&join(&prefix, from), &join(&prefix, to), args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.inner.blocking_stat(&self.join(path), args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.inner.blocking_delete(&self.join(path), args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        let prefix = self.prefix();
+        self.inner
+            .blocking_list(&join(&prefix, path), args)
+            .map(|(rp, l)| (rp, PrefixLister::new(l, prefix)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    tokio::task_local! {
+        static TENANT: String;
+    }
+
+    #[tokio::test]
+    async fn test_namespace() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        let tenant_op = op
+            .clone()
+            .layer(NamespaceLayer::new(|| TENANT.with(|v| v.clone())));
+
+        for tenant in ["a", "b"] {
+            TENANT
+                .scope(tenant.to_string(), tenant_op.write("test", tenant))
+                .await?;
+        }
+        assert_eq!(op.read("a/test").await?, b"a");
+        assert_eq!(op.read("b/test").await?, b"b");
+
+        let entries = TENANT.scope("a".to_string(), tenant_op.list("/")).await?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path(), "test");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,289 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use async_trait::async_trait;
+use log::warn;
+
+use crate::raw::*;
+use crate::*;
+
+/// Add consistent path semantics for the underlying storage services.
+///
+/// All incoming paths will be normalized before forwarding to the inner
+/// accessor:
+///
+/// - All leading `/` will be trimmed: `///abc` => `abc`
+/// - Internal `//` will be replaced by `/`: `abc//def` => `abc/def`
+/// - Trailing `/` will be trimmed for `read`, `write`, `copy`, `rename`,
+///   `rename_noreplace`, `hard_link`, `symlink`, `truncate` and `fallocate`
+///   since they can only work on files: `abc/` => `abc`
+///
+/// A warning will be logged every time the path is changed.
+///
+/// # Notes
+///
+/// [`Operator`] already normalizes paths before calling the accessor, so
+/// this layer is mostly useful for paths built dynamically and passed to
+/// the raw [`Accessor`] directly. `stat` and `delete` keep the trailing `/`
+/// since it's used to address directories. The target of `symlink` is
+/// forwarded as is since it's the content of link instead of a path in
+/// the storage.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::PathNormalizationLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(PathNormalizationLayer)
+///     .finish();
+/// ```
+#[derive(Debug, Copy, Clone, Default)]
+pub struct PathNormalizationLayer;
+
+impl<A: Accessor> Layer<A> for PathNormalizationLayer {
+    type LayeredAccessor = PathNormalizationAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        PathNormalizationAccessor { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PathNormalizationAccessor<A: Accessor> {
+    inner: A,
+}
+
+/// Normalize given path, trim the trailing `/` if `is_file` is true.
+fn normalize(path: &str, is_file: bool) -> String {
+    let mut p = normalize_path(path);
+    if is_file && p != "/" {
+        p.truncate(p.trim_end_matches('/').len());
+    }
+    p
+}
+
+impl<A: Accessor> PathNormalizationAccessor<A> {
+    fn normalize(&self, op: Operation, path: &str, is_file: bool) -> String {
+        let p = normalize(path, is_file);
+        if p != path {
+            warn!(
+                "service={} operation={} path={} -> {}: path has been normalized",
+                self.inner.info().scheme(),
+                op,
+                path,
+                p
+            );
+        }
+        p
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<A: Accessor> LayeredAccessor for PathNormalizationAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let path = self.normalize(Operation::CreateDir, path, false);
+        self.inner.create_dir(&path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let path = self.normalize(Operation::Read, path, true);
+        self.inner.read(&path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let path = self.normalize(Operation::Write, path, true);
+        self.inner.write(&path, args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let from = self.normalize(Operation::Copy, from, true);
+        let to = self.normalize(Operation::Copy, to, true);
+        self.inner.copy(&from, &to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let from = self.normalize(Operation::Rename, from, true);
+        let to = self.normalize(Operation::Rename, to, true);
+        self.inner.rename(&from, &to, args).await
+    }
+
+    async fn hard_link(&self, from: &str, to: &str, args: OpHardLink) -> Result<RpHardLink> {
+        let from = self.normalize(Operation::HardLink, from, true);
+        let to = self.normalize(Operation::HardLink, to, true);
+        self.inner.hard_link(&from, &to, args).await
+    }
+
+    async fn symlink(&self, target: &str, link: &str, args: OpSymlink) -> Result<RpSymlink> {
+        let link = self.normalize(Operation::Symlink, link, true);
+        self.inner.symlink(target, &link, args).await
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        let path = self.normalize(Operation::SetPermissions, path, false);
+        self.inner.set_permissions(&path, args).await
+    }
+
+    async fn truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        let path = self.normalize(Operation::Truncate, path, true);
+        self.inner.truncate(&path, args).await
+    }
+
+    async fn fallocate(&self, path: &str, args: OpFallocate) -> Result<RpFallocate> {
+        let path = self.normalize(Operation::Fallocate, path, true);
+        self.inner.fallocate(&path, args).await
+    }
+
+    async fn rename_noreplace(
+        &self,
+        from: &str,
+        to: &str,
+        args: OpRenameNoreplace,
+    ) -> Result<RpRenameNoreplace> {
+        let from = self.normalize(Operation::RenameNoreplace, from, true);
+        let to = self.normalize(Operation::RenameNoreplace, to, true);
+        self.inner.rename_noreplace(&from, &to, args).await
+    }
+
+    async fn setxattr(&self, path: &str, args: OpSetxattr) -> Result<RpSetxattr> {
+        let path = self.normalize(Operation::Setxattr, path, false);
+        self.inner.setxattr(&path, args).await
+    }
+
+    async fn getxattr(&self, path: &str, args: OpGetxattr) -> Result<RpGetxattr> {
+        let path = self.normalize(Operation::Getxattr, path, false);
+        self.inner.getxattr(&path, args).await
+    }
+
+    async fn listxattr(&self, path: &str, args: OpListxattr) -> Result<RpListxattr> {
+        let path = self.normalize(Operation::Listxattr, path, false);
+        self.inner.listxattr(&path, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let path = self.normalize(Operation::Stat, path, false);
+        self.inner.stat(&path, args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let path = self.normalize(Operation::Delete, path, false);
+        self.inner.delete(&path, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let path = self.normalize(Operation::List, path, false);
+        self.inner.list(&path, args).await
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let ops = args
+            .into_operation()
+            .into_iter()
+            .map(|(path, op)| (self.normalize(Operation::Batch, &path, false), op))
+            .collect();
+        self.inner.batch(OpBatch::new(ops)).await
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        let path = self.normalize(Operation::Presign, path, false);
+        self.inner.presign(&path, args).await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let path = self.normalize(Operation::BlockingCreateDir, path, false);
+        self.inner.blocking_create_dir(&path, args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let path = self.normalize(Operation::BlockingRead, path, true);
+        self.inner.blocking_read(&path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let path = self.normalize(Operation::BlockingWrite, path, true);
+        self.inner.blocking_write(&path, args)
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let from = self.normalize(Operation::BlockingCopy, from, true);
+        let to = self.normalize(Operation::BlockingCopy, to, true);
+        self.inner.blocking_copy(&from, &to, args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let from = self.normalize(Operation::BlockingRename, from, true);
+        let to = self.normalize(Operation::BlockingRename, to, true);
+        self.inner.blocking_rename(&from, &to, args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let path = self.normalize(Operation::BlockingStat, path, false);
+        self.inner.blocking_stat(&path, args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let path = self.normalize(Operation::BlockingDelete, path, false);
+        self.inner.blocking_delete(&path, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        let path = self.normalize(Operation::BlockingList, path, false);
+        self.inner.blocking_list(&path, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let cases = vec![
+            ("file path", "abc", true, "abc"),
+            ("leading slash", "/abc", true, "abc"),
+            ("double slash", "abc//def", true, "abc/def"),
+            ("trailing slash of file", "abc/def/", true, "abc/def"),
+            ("trailing slash of dir", "//abc//def/", false, "abc/def/"),
+            ("root", "/", true, "/"),
+        ];
+
+        for (name, input, is_file, expect) in cases {
+            assert_eq!(normalize(input, is_file), expect, "{name}")
+        }
+    }
+}
@@ -0,0 +1,357 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use async_trait::async_trait;
+
+use crate::raw::*;
+use crate::*;
+
+/// Operate in a namespace of the underlying services by adding a prefix to
+/// all paths.
+///
+/// # Behavior
+///
+/// - The prefix will be normalized to a dir path like `app/`.
+/// - Every path passed to the underlying services will be joined with the
+///   prefix: `abc` => `app/abc`, and the root `/` => `app/`.
+/// - The prefix will be stripped from paths of listed entries.
+///
+/// # Notes
+///
+/// The `target` of `symlink` is kept as is, since it's resolved relative
+/// to the link by underlying services.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::PrefixLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(PrefixLayer::new("app_a"))
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct PrefixLayer {
+    prefix: Arc<String>,
+}
+
+impl PrefixLayer {
+    /// Create a new PrefixLayer with given prefix.
+    pub fn new(prefix: &str) -> Self {
+        Self {
+            prefix: Arc::new(normalize_prefix(prefix)),
+        }
+    }
+}
+
+/// Normalize given prefix to a dir path, the root will be an empty string.
+pub(super) fn normalize_prefix(prefix: &str) -> String {
+    let mut prefix = normalize_path(prefix);
+    if prefix == "/" {
+        prefix.clear();
+    } else if !prefix.ends_with('/') {
+        prefix.push('/');
+    }
+    prefix
+}
+
+/// Join the normalized prefix with given path.
+pub(super) fn join(prefix: &str, path: &str) -> String {
+    if path == "/" {
+        if prefix.is_empty() {
+            return path.to_string();
+        }
+        return prefix.to_string();
+    }
+
+    format!("{prefix}{path}")
+}
+
+impl<A: Accessor> Layer<A> for PrefixLayer {
+    type LayeredAccessor = PrefixAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        PrefixAccessor {
+            inner,
+            prefix: self.prefix.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PrefixAccessor<A: Accessor> {
+    inner: A,
+    prefix: Arc<String>,
+}
+
+impl<A: Accessor> PrefixAccessor<A> {
+    fn join(&self, path: &str) -> String {
+        join(&self.prefix, path)
+    }
+}
+
+/// Strip the prefix from given path.
+pub(super) fn strip(prefix: &str, path: &str) -> String {
+    match path.strip_prefix(prefix) {
+        Some("") => "/".to_string(),
+        Some(p) => p.to_string(),
+        None => path.to_string(),
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<A: Accessor> LayeredAccessor for PrefixAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = PrefixLister<A::Lister>;
+    type BlockingLister = PrefixLister<A::BlockingLister>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.inner.create_dir(&self.join(path), args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(&self.join(path), args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner.write(&self.join(path), args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.inner
+            .copy(&self.join(from), &self.join(to), args)
+            .await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.inner
+            .rename(&self.join(from), &self.join(to), args)
+            .await
+    }
+
+    async fn hard_link(&self, from: &str, to: &str, args: OpHardLink) -> Result<RpHardLink> {
+        self.inner
+            .hard_link(&self.join(from), &self.join(to), args)
+            .await
+    }
+
+    async fn symlink(&self, target: &str, link: &str, args: OpSymlink) -> Result<RpSymlink> {
+        self.inner.symlink(target, &self.join(link), args).await
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        self.inner.set_permissions(&self.join(path), args).await
+    }
+
+    async fn truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        self.inner.truncate(&self.join(path), args).await
+    }
+
+    async fn fallocate(&self, path: &str, args: OpFallocate) -> Result<RpFallocate> {
+        self.inner.fallocate(&self.join(path), args).await
+    }
+
+    async fn rename_noreplace(
+        &self,
+        from: &str,
+        to: &str,
+        args: OpRenameNoreplace,
+    ) -> Result<RpRenameNoreplace> {
+        self.inner
+            .rename_noreplace(&self.join(from), &self.join(to), args)
+            .await
+    }
+
+    async fn setxattr(&self, path: &str, args: OpSetxattr) -> Result<RpSetxattr> {
+        self.inner.setxattr(&self.join(path), args).await
+    }
+
+    async fn getxattr(&self, path: &str, args: OpGetxattr) -> Result<RpGetxattr> {
+        self.inner.getxattr(&self.join(path), args).await
+    }
+
+    async fn listxattr(&self, path: &str, args: OpListxattr) -> Result<RpListxattr> {
+        self.inner.listxattr(&self.join(path), args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.inner.stat(&self.join(path), args).await
+    }
+
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.inner.delete(&self.join(path), args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner
+            .list(&self.join(path), args)
+            .await
+            .map(|(rp, l)| (rp, PrefixLister::new(l, self.prefix.clone())))
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let ops = args
+            .into_operation()
+            .into_iter()
+            .map(|(path, op)| (self.join(&path), op))
+            .collect();
+
+        let results = self
+            .inner
+            .batch(OpBatch::new(ops))
+            .await?
+            .into_results()
+            .into_iter()
+            .map(|(path, res)| (strip(&self.prefix, &path), res))
+            .collect();
+        Ok(RpBatch::new(results))
+    }
+
+    async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
+        self.inner.presign(&self.join(path), args).await
+    }
+
+    fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        self.inner.blocking_create_dir(&self.join(path), args)
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(&self.join(path), args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner.blocking_write(&self.join(path), args)
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.inner
+            .blocking_copy(&self.join(from), &self.join(to), args)
+    }
+
+    fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        self.inner
+            .blocking_rename(&self.join(from), &self.join(to), args)
+    }
+
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.inner.blocking_stat(&self.join(path), args)
+    }
+
+    fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        self.inner.blocking_delete(&self.join(path), args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner
+            .blocking_list(&self.join(path), args)
+            .map(|(rp, l)| (rp, PrefixLister::new(l, self.prefix.clone())))
+    }
+}
+
+/// PrefixLister strips the prefix from paths of listed entries.
+pub struct PrefixLister<L> {
+    inner: L,
+    prefix: Arc<String>,
+}
+
+impl<L> PrefixLister<L> {
+    pub(super) fn new(inner: L, prefix: Arc<String>) -> Self {
+        Self { inner, prefix }
+    }
+
+    fn convert(&self, mut entry: oio::Entry) -> oio::Entry {
+        let path = strip(&self.prefix, entry.path());
+        entry.set_path(&path);
+        entry
+    }
+}
+
+impl<L: oio::List> oio::List for PrefixLister<L> {
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<oio::Entry>>> {
+        let entry = ready!(self.inner.poll_next(cx))?;
+        Poll::Ready(Ok(entry.map(|e| self.convert(e))))
+    }
+}
+
+impl<L: oio::BlockingList> oio::BlockingList for PrefixLister<L> {
+    fn next(&mut self) -> Result<Option<oio::Entry>> {
+        let entry = self.inner.next()?;
+        Ok(entry.map(|e| self.convert(e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[test]
+    fn test_new_prefix() {
+        let cases = vec![
+            ("dir", "/app/", "app/"),
+            ("file", "app", "app/"),
+            ("nested", "//a//b", "a/b/"),
+            ("root", "/", ""),
+            ("empty", "", ""),
+        ];
+
+        for (name, input, expect) in cases {
+            assert_eq!(PrefixLayer::new(input).prefix.as_str(), expect, "{name}")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prefix() -> Result<()> {
+        let op = Operator::new(Memory::default())?.finish();
+        let app = op.clone().layer(PrefixLayer::new("app"));
+
+        app.write("dir/test", "Hello").await?;
+        assert_eq!(op.read("app/dir/test").await?, b"Hello");
+        assert_eq!(app.read("dir/test").await?, b"Hello");
+
+        let entries = app.list("dir/").await?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path(), "dir/test");
+
+        app.delete("dir/test").await?;
+        assert!(!op.is_exist("app/dir/test").await?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,372 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use crate::raw::*;
+use crate::*;
+
+/// The default interval to reset quota counters.
+const DEFAULT_RESET_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Enforce read and write byte quotas per path prefix.
+///
+/// # Behavior
+///
+/// - Every path is counted against the longest configured prefix it starts
+///   with. Paths that don't match any prefix are not limited.
+/// - Bytes read and written are counted together. Operations that would
+///   exceed the quota will return [`ErrorKind::PermissionDenied`].
+/// - All counters will be reset every reset interval, default to one day.
+///
+/// # Notes
+///
+/// - Counters are kept in memory and shared by all operators built with the
+///   same layer, they will be lost after current process exited.
+/// - Reads are counted after data is fetched from underlying services, so
+///   the read that exceeds the quota has already consumed bandwidth.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use anyhow::Result;
+/// use opendal::layers::QuotaLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let quotas = HashMap::from([("tenant_a/".to_string(), 1024 * 1024 * 1024)]);
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(QuotaLayer::new(quotas))
+///     .finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct QuotaLayer {
+    max_bytes_per_prefix: HashMap<String, u64>,
+    reset_interval: Duration,
+    counters: Arc<Mutex<Counters>>,
+}
+
+#[derive(Debug)]
+struct Counters {
+    used: HashMap<String, u64>,
+    reset_at: Instant,
+}
+
+impl QuotaLayer {
+    /// Create a new QuotaLayer with max bytes of every path prefix.
+    pub fn new(max_bytes_per_prefix: HashMap<String, u64>) -> Self {
+        Self {
+            max_bytes_per_prefix,
+            reset_interval: DEFAULT_RESET_INTERVAL,
+            counters: Arc::new(Mutex::new(Counters {
+                used: HashMap::new(),
+                reset_at: Instant::now() + DEFAULT_RESET_INTERVAL,
+            })),
+        }
+    }
+
+    /// Set the interval to reset all counters.
+    ///
+    /// Default to one day.
+    pub fn with_reset_interval(mut self, interval: Duration) -> Self {
+        self.reset_interval = interval;
+        self.counters.lock().expect("lock must succeed").reset_at = Instant::now() + interval;
+        self
+    }
+}
+
+impl<A: Accessor> Layer<A> for QuotaLayer {
+    type LayeredAccessor = QuotaAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        QuotaAccessor {
+            inner,
+            max_bytes_per_prefix: self.max_bytes_per_prefix.clone(),
+            reset_interval: self.reset_interval,
+            counters: self.counters.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct QuotaAccessor<A: Accessor> {
+    inner: A,
+    max_bytes_per_prefix: HashMap<String, u64>,
+    reset_interval: Duration,
+    counters: Arc<Mutex<Counters>>,
+}
+
+impl<A: Accessor> QuotaAccessor<A> {
+    /// Build the quota of the longest prefix that matches given path.
+    fn quota(&self, path: &str) -> Option<Quota> {
+        let (prefix, max) = self
+            .max_bytes_per_prefix
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())?;
+
+        Some(Quota {
+            prefix: prefix.clone(),
+            max: *max,
+            reset_interval: self.reset_interval,
+            counters: self.counters.clone(),
+        })
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<A: Accessor> LayeredAccessor for QuotaAccessor<A> {
+    type Inner = A;
+    type Reader = QuotaWrapper<A::Reader>;
+    type BlockingReader = QuotaWrapper<A::BlockingReader>;
+    type Writer = QuotaWrapper<A::Writer>;
+    type BlockingWriter = QuotaWrapper<A::BlockingWriter>;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner
+            .read(path, args)
+            .await
+            .map(|(rp, r)| (rp, QuotaWrapper::new(r, self.quota(path))))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.inner
+            .write(path, args)
+            .await
+            .map(|(rp, w)| (rp, QuotaWrapper::new(w, self.quota(path))))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner
+            .blocking_read(path, args)
+            .map(|(rp, r)| (rp, QuotaWrapper::new(r, self.quota(path))))
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        self.inner
+            .blocking_write(path, args)
+            .map(|(rp, w)| (rp, QuotaWrapper::new(w, self.quota(path))))
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+/// Quota of a matched prefix.
+struct Quota {
+    prefix: String,
+    max: u64,
+    reset_interval: Duration,
+    counters: Arc<Mutex<Counters>>,
+}
+
+impl Quota {
+    /// Check if `n` more bytes is allowed, and count them if `add` is true.
+    fn charge(&self, n: u64, add: bool) -> Result<()> {
+        let mut counters = self.counters.lock().expect("lock must succeed");
+
+        let now = Instant::now();
+        if now >= counters.reset_at {
+            counters.used.clear();
+            counters.reset_at = now + self.reset_interval;
+        }
+
+        let used = counters.used.entry(self.prefix.clone()).or_default();
+        if *used + n > self.max {
+            return Err(Error::new(ErrorKind::PermissionDenied, "quota exceeded")
+                .with_context("prefix", &self.prefix)
+                .with_context("max_bytes", self.max.to_string())
+                .with_context("used_bytes", used.to_string()));
+        }
+        if add {
+            *used += n;
+        }
+
+        Ok(())
+    }
+}
+
+/// QuotaWrapper counts bytes read or written by inner reader or writer.
+pub struct QuotaWrapper<R> {
+    inner: R,
+    quota: Option<Quota>,
+}
+
+impl<R> QuotaWrapper<R> {
+    fn new(inner: R, quota: Option<Quota>) -> Self {
+        Self { inner, quota }
+    }
+
+    fn charge(&self, n: usize, add: bool) -> Result<()> {
+        match &self.quota {
+            Some(quota) => quota.charge(n as u64, add),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<R: oio::Read> oio::Read for QuotaWrapper<R> {
+    async fn read(&mut self, limit: usize) -> Result<Bytes> {
+        let bs = self.inner.read(limit).await?;
+        self.charge(bs.len(), true)?;
+        Ok(bs)
+    }
+
+    async fn seek(&mut self, pos: io::SeekFrom) -> Result<u64> {
+        self.inner.seek(pos).await
+    }
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for QuotaWrapper<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.charge(n, true)?;
+        Ok(n)
+    }
+
+    fn seek(&mut self, pos: io::SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        match self.inner.next() {
+            Some(Ok(bs)) => Some(self.charge(bs.len(), true).map(|_| bs)),
+            v => v,
+        }
+    }
+}
+
+impl<R: oio::Write> oio::Write for QuotaWrapper<R> {
+    fn poll_write(&mut self, cx: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>> {
+        self.charge(bs.remaining(), false)?;
+
+        let n = ready!(self.inner.poll_write(cx, bs))?;
+        self.charge(n, true)?;
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_close(cx)
+    }
+
+    fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.inner.poll_abort(cx)
+    }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
+}
+
+impl<R: oio::BlockingWrite> oio::BlockingWrite for QuotaWrapper<R> {
+    fn write(&mut self, bs: &dyn oio::WriteBuf) -> Result<usize> {
+        self.charge(bs.remaining(), false)?;
+
+        let n = self.inner.write(bs)?;
+        self.charge(n, true)?;
+        Ok(n)
+    }
+
+    fn close(&mut self) -> Result<()> {
+        self.inner.close()
+    }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    fn new_operator(layer: QuotaLayer) -> Result<Operator> {
+        Ok(Operator::new(Memory::default())?.layer(layer).finish())
+    }
+
+    #[tokio::test]
+    async fn test_quota() -> Result<()> {
+        let op = new_operator(QuotaLayer::new(HashMap::from([
+            ("tenant/".to_string(), 10),
+            ("tenant/large/".to_string(), 100),
+        ])))?;
+
+        op.write("tenant/a", vec![0; 4]).await?;
+        let err = op
+            .write("tenant/b", vec![0; 7])
+            .await
+            .expect_err("write must exceed quota");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        // The longest prefix and paths without quota are counted separately.
+        op.write("tenant/large/a", vec![0; 50]).await?;
+        op.write("other/a", vec![0; 1000]).await?;
+
+        assert_eq!(op.read("tenant/a").await?.len(), 4);
+        let err = op
+            .read("tenant/a")
+            .await
+            .expect_err("read must exceed quota");
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_quota_reset() -> Result<()> {
+        let op = new_operator(
+            QuotaLayer::new(HashMap::from([("tenant/".to_string(), 10)]))
+                .with_reset_interval(Duration::from_millis(50)),
+        )?;
+
+        op.write("tenant/a", vec![0; 8]).await?;
+        assert!(op.write("tenant/b", vec![0; 8]).await.is_err());
+
+        std::thread::sleep(Duration::from_millis(100));
+        op.write("tenant/b", vec![0; 8]).await?;
+
+        Ok(())
+    }
+}
@@ -931,6 +931,13 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
             }
         }
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner
+            .as_ref()
+            .map(|w| w.written_bytes())
+            .unwrap_or_default()
+    }
 }
 
 impl<R: oio::BlockingWrite, I: RetryInterceptor> oio::BlockingWrite for RetryWrapper<R, I> {
@@ -969,6 +976,13 @@ impl<R: oio::BlockingWrite, I: RetryInterceptor> oio::BlockingWrite for RetryWra
             .call()
             .map_err(|e| e.set_persistent())
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner
+            .as_ref()
+            .map(|w| w.written_bytes())
+            .unwrap_or_default()
+    }
 }
 
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
@@ -15,12 +15,17 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::cmp;
+use std::future::Future;
 use std::io::SeekFrom;
 use std::num::NonZeroU32;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
 use std::thread;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -44,13 +49,23 @@ use crate::*;
 /// [Governor](https://docs.rs/governor/latest/governor/index.html).
 /// By setting the `bandwidth` and `burst`, we can control the byte flow rate of underlying services.
 ///
+/// Reads and writes share the same `bandwidth` by default. Use
+/// [`ThrottleLayer::with_read_bandwidth`] and [`ThrottleLayer::with_write_bandwidth`]
+/// to limit them separately.
+///
 /// # Note
 ///
 /// When setting the ThrottleLayer, always consider the largest possible operation size as the burst size,
 /// as **the burst size should be larger than any possible byte length to allow it to pass through**.
 ///
+/// Reads will fetch at most `burst` bytes at once, so they are not limited by burst size.
+///
 /// Read more about [Quota](https://docs.rs/governor/latest/governor/struct.Quota.html#examples)
 ///
+/// Async operations wait via tokio's timer, so they must be polled within a tokio runtime.
+/// Blocking operations wait via [`std::thread::sleep`] which blocks the calling thread, don't
+/// call them inside an async context.
+///
 /// # Examples
 ///
 /// This example limits bandwidth to 10 KiB/s and burst size to 10 MiB.
@@ -66,10 +81,29 @@ use crate::*;
 ///     .layer(ThrottleLayer::new(10 * 1024, 10000 * 1024))
 ///     .finish();
 /// ```
+///
+/// This example limits reads to 1 MiB/s and writes to 512 KiB/s.
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::ThrottleLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(
+///         ThrottleLayer::new(1024 * 1024, 10000 * 1024)
+///             .with_read_bandwidth(1024 * 1024)
+///             .with_write_bandwidth(512 * 1024),
+///     )
+///     .finish();
+/// ```
 #[derive(Clone)]
 pub struct ThrottleLayer {
     bandwidth: NonZeroU32,
     burst: NonZeroU32,
+    read_bandwidth: Option<NonZeroU32>,
+    write_bandwidth: Option<NonZeroU32>,
 }
 
 impl ThrottleLayer {
@@ -83,20 +117,53 @@ impl ThrottleLayer {
         Self {
             bandwidth: NonZeroU32::new(bandwidth).unwrap(),
             burst: NonZeroU32::new(burst).unwrap(),
+            read_bandwidth: None,
+            write_bandwidth: None,
         }
     }
+
+    /// Limit reads to given bytes per second separately, instead of sharing
+    /// `bandwidth` with writes.
+    pub fn with_read_bandwidth(mut self, bytes_per_sec: u32) -> Self {
+        assert!(bytes_per_sec > 0);
+        self.read_bandwidth = NonZeroU32::new(bytes_per_sec);
+        self
+    }
+
+    /// Limit writes to given bytes per second separately, instead of sharing
+    /// `bandwidth` with reads.
+    pub fn with_write_bandwidth(mut self, bytes_per_sec: u32) -> Self {
+        assert!(bytes_per_sec > 0);
+        self.write_bandwidth = NonZeroU32::new(bytes_per_sec);
+        self
+    }
+
+    fn new_rate_limiter(&self, bandwidth: NonZeroU32) -> SharedRateLimiter {
+        Arc::new(RateLimiter::direct(
+            Quota::per_second(bandwidth).allow_burst(self.burst),
+        ))
+    }
 }
 
 impl<A: Accessor> Layer<A> for ThrottleLayer {
     type LayeredAccessor = ThrottleAccessor<A>;
 
     fn layer(&self, accessor: A) -> Self::LayeredAccessor {
-        let rate_limiter = Arc::new(RateLimiter::direct(
-            Quota::per_second(self.bandwidth).allow_burst(self.burst),
-        ));
+        let rate_limiter = self.new_rate_limiter(self.bandwidth);
+        let read_limiter = match self.read_bandwidth {
+            Some(bandwidth) => self.new_rate_limiter(bandwidth),
+            None => rate_limiter.clone(),
+        };
+        let write_limiter = match self.write_bandwidth {
+            Some(bandwidth) => self.new_rate_limiter(bandwidth),
+            None => rate_limiter,
+        };
+
         ThrottleAccessor {
             inner: accessor,
-            rate_limiter,
+            burst: self.burst,
+            read_limiter,
+            write_limiter,
         }
     }
 }
@@ -109,7 +176,9 @@ type SharedRateLimiter = Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock,
 #[derive(Debug, Clone)]
 pub struct ThrottleAccessor<A: Accessor> {
     inner: A,
-    rate_limiter: SharedRateLimiter,
+    burst: NonZeroU32,
+    read_limiter: SharedRateLimiter,
+    write_limiter: SharedRateLimiter,
 }
 
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
@@ -128,21 +197,21 @@ impl<A: Accessor> LayeredAccessor for ThrottleAccessor<A> {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let limiter = self.rate_limiter.clone();
+        let limiter = self.read_limiter.clone();
 
         self.inner
             .read(path, args)
             .await
-            .map(|(rp, r)| (rp, ThrottleWrapper::new(r, limiter)))
+            .map(|(rp, r)| (rp, ThrottleWrapper::new(r, limiter, self.burst)))
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        let limiter = self.rate_limiter.clone();
+        let limiter = self.write_limiter.clone();
 
         self.inner
             .write(path, args)
             .await
-            .map(|(rp, w)| (rp, ThrottleWrapper::new(w, limiter)))
+            .map(|(rp, w)| (rp, ThrottleWrapper::new(w, limiter, self.burst)))
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
@@ -150,19 +219,19 @@ impl<A: Accessor> LayeredAccessor for ThrottleAccessor<A> {
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
-        let limiter = self.rate_limiter.clone();
+        let limiter = self.read_limiter.clone();
 
         self.inner
             .blocking_read(path, args)
-            .map(|(rp, r)| (rp, ThrottleWrapper::new(r, limiter)))
+            .map(|(rp, r)| (rp, ThrottleWrapper::new(r, limiter, self.burst)))
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
-        let limiter = self.rate_limiter.clone();
+        let limiter = self.write_limiter.clone();
 
         self.inner
             .blocking_write(path, args)
-            .map(|(rp, w)| (rp, ThrottleWrapper::new(w, limiter)))
+            .map(|(rp, w)| (rp, ThrottleWrapper::new(w, limiter, self.burst)))
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
@@ -173,21 +242,64 @@ impl<A: Accessor> LayeredAccessor for ThrottleAccessor<A> {
 pub struct ThrottleWrapper<R> {
     inner: R,
     limiter: SharedRateLimiter,
+    burst: NonZeroU32,
+
+    /// The sleep to wait for enough capacity before next write.
+    sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+    /// Whether the bytes of current write have been allowed by limiter.
+    ///
+    /// Inner writer could return `Pending`, the bytes must not be counted again.
+    permitted: bool,
 }
 
 impl<R> ThrottleWrapper<R> {
-    pub fn new(inner: R, rate_limiter: SharedRateLimiter) -> Self {
+    pub fn new(inner: R, rate_limiter: SharedRateLimiter, burst: NonZeroU32) -> Self {
         Self {
             inner,
             limiter: rate_limiter,
+            burst,
+            sleep: None,
+            permitted: false,
+        }
+    }
+
+    /// Check if `n` bytes are allowed to pass through.
+    ///
+    /// Returns the duration to wait if limiter can't accommodate them now.
+    fn check(&self, n: usize) -> Result<Option<Duration>> {
+        let Some(n) = NonZeroU32::new(n as u32) else {
+            return Ok(None);
+        };
+
+        match self.limiter.check_n(n) {
+            Ok(Ok(_)) => Ok(None),
+            // the query is valid but the Decider can not accommodate them.
+            Ok(Err(not_until)) => Ok(Some(
+                not_until.wait_time_from(DefaultClock::default().now()),
+            )),
+            // the query was invalid as the rate limit parameters can "never" accommodate the number of cells queried for.
+            Err(_) => Err(Error::new(
+                ErrorKind::RateLimited,
+                "InsufficientCapacity due to burst size being smaller than the request size",
+            )),
         }
     }
+
+    /// Cap the size of a read so that it can always pass through the limiter.
+    fn read_limit(&self, limit: usize) -> usize {
+        cmp::min(limit, self.burst.get() as usize)
+    }
 }
 
 impl<R: oio::Read> oio::Read for ThrottleWrapper<R> {
     async fn read(&mut self, limit: usize) -> Result<Bytes> {
-        // TODO: How can we handle buffer reads with a limiter?
-        self.inner.read(limit).await
+        let bs = self.inner.read(self.read_limit(limit)).await?;
+
+        // Bytes have been fetched, wait until they are allowed to pass through.
+        while let Some(wait) = self.check(bs.len())? {
+            tokio::time::sleep(wait).await;
+        }
+        Ok(bs)
     }
 
     async fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
@@ -197,8 +309,13 @@ impl<R: oio::Read> oio::Read for ThrottleWrapper<R> {
 
 impl<R: oio::BlockingRead> oio::BlockingRead for ThrottleWrapper<R> {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
-        // TODO: How can we handle buffer reads with a limiter?
-        self.inner.read(buf)
+        let limit = self.read_limit(buf.len());
+        let n = self.inner.read(&mut buf[..limit])?;
+
+        while let Some(wait) = self.check(n)? {
+            thread::sleep(wait);
+        }
+        Ok(n)
     }
 
     fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
@@ -206,34 +323,38 @@ impl<R: oio::BlockingRead> oio::BlockingRead for ThrottleWrapper<R> {
     }
 
     fn next(&mut self) -> Option<Result<Bytes>> {
-        self.inner.next()
+        let bs = match self.inner.next()? {
+            Ok(bs) => bs,
+            Err(err) => return Some(Err(err)),
+        };
+
+        loop {
+            match self.check(bs.len()) {
+                Ok(None) => return Some(Ok(bs)),
+                Ok(Some(wait)) => thread::sleep(wait),
+                Err(err) => return Some(Err(err)),
+            }
+        }
     }
 }
 
 impl<R: oio::Write> oio::Write for ThrottleWrapper<R> {
     fn poll_write(&mut self, cx: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>> {
-        let buf_length = NonZeroU32::new(bs.remaining() as u32).unwrap();
+        while !self.permitted {
+            if let Some(sleep) = self.sleep.as_mut() {
+                ready!(sleep.as_mut().poll(cx));
+                self.sleep = None;
+            }
 
-        loop {
-            match self.limiter.check_n(buf_length) {
-                Ok(res) => match res {
-                    Ok(_) => return self.inner.poll_write(cx, bs),
-                    // the query is valid but the Decider can not accommodate them.
-                    Err(not_until) => {
-                        let _ = not_until.wait_time_from(DefaultClock::default().now());
-                        // TODO: Should lock the limiter and wait for the wait_time, or should let other small requests go first?
-
-                        // FIXME: we should sleep here.
-                        // tokio::time::sleep(wait_time).await;
-                    }
-                },
-                // the query was invalid as the rate limit parameters can "never" accommodate the number of cells queried for.
-                Err(_) => return Poll::Ready(Err(Error::new(
-                    ErrorKind::RateLimited,
-                    "InsufficientCapacity due to burst size being smaller than the request size",
-                ))),
+            match self.check(bs.remaining())? {
+                None => self.permitted = true,
+                Some(wait) => self.sleep = Some(Box::pin(tokio::time::sleep(wait))),
             }
         }
+
+        let res = ready!(self.inner.poll_write(cx, bs));
+        self.permitted = false;
+        Poll::Ready(res)
     }
 
     fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
@@ -243,32 +364,65 @@ impl<R: oio::Write> oio::Write for ThrottleWrapper<R> {
     fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
         self.inner.poll_close(cx)
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
 }
 
 impl<R: oio::BlockingWrite> oio::BlockingWrite for ThrottleWrapper<R> {
     fn write(&mut self, bs: &dyn oio::WriteBuf) -> Result<usize> {
-        let buf_length = NonZeroU32::new(bs.remaining() as u32).unwrap();
-
-        loop {
-            match self.limiter.check_n(buf_length) {
-                Ok(res) => match res {
-                    Ok(_) => return self.inner.write(bs),
-                    // the query is valid but the Decider can not accommodate them.
-                    Err(not_until) => {
-                        let wait_time = not_until.wait_time_from(DefaultClock::default().now());
-                        thread::sleep(wait_time);
-                    }
-                },
-                // the query was invalid as the rate limit parameters can "never" accommodate the number of cells queried for.
-                Err(_) => return Err(Error::new(
-                    ErrorKind::RateLimited,
-                    "InsufficientCapacity due to burst size being smaller than the request size",
-                )),
-            }
+        while let Some(wait) = self.check(bs.remaining())? {
+            thread::sleep(wait);
         }
+
+        self.inner.write(bs)
     }
 
     fn close(&mut self) -> Result<()> {
         self.inner.close()
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_throttle_read() -> Result<()> {
+        let bandwidth = 64 * 1024;
+        let burst = 16 * 1024;
+        let op = Operator::new(Memory::default())?
+            .layer(ThrottleLayer::new(bandwidth, burst).with_write_bandwidth(u32::MAX))
+            .finish();
+
+        // Read 2x of the bandwidth, written in chunks that fit in the burst.
+        let size = 2 * bandwidth as usize;
+        let mut w = op.writer("test").await?;
+        for _ in 0..size / burst as usize {
+            w.write(vec![0; burst as usize]).await?;
+        }
+        w.close().await?;
+
+        let start = Instant::now();
+        assert_eq!(op.read("test").await?.len(), size);
+        let elapsed = start.elapsed().as_secs_f64();
+
+        // The first burst passes through immediately.
+        let rate = (size - burst as usize) as f64 / elapsed;
+        let expected = bandwidth as f64;
+        assert!(
+            (rate - expected).abs() / expected < 0.05,
+            "read rate {rate} must be close to {expected}"
+        );
+
+        Ok(())
+    }
 }
@@ -357,6 +357,10 @@ impl<R: oio::Write> oio::Write for TimeoutWrapper<R> {
         self.sleep = None;
         Poll::Ready(v)
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
 }
 
 impl<R: oio::List> oio::List for TimeoutWrapper<R> {
@@ -335,6 +335,10 @@ impl<R: oio::Write> oio::Write for TracingWrapper<R> {
     fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
         self.inner.poll_close(cx)
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
 }
 
 impl<R: oio::BlockingWrite> oio::BlockingWrite for TracingWrapper<R> {
@@ -353,6 +357,10 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for TracingWrapper<R> {
     fn close(&mut self) -> Result<()> {
         self.inner.close()
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
 }
 
 impl<R: oio::List> oio::List for TracingWrapper<R> {
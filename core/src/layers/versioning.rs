@@ -0,0 +1,310 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use crate::raw::oio::BlockingRead;
+use crate::raw::oio::BlockingWrite;
+use crate::raw::oio::Read;
+use crate::raw::oio::WriteExt;
+use crate::raw::*;
+use crate::*;
+
+/// Keep history of files for services that don't support versioning natively.
+///
+/// # Behavior
+///
+/// - Before `write` or `copy` overwrites an existing file, the previous
+///   content will be copied to `<path>.<timestamp_ns>` first.
+/// - Appending to a file doesn't create a version since the previous content
+///   is kept.
+/// - Use [`VersioningLayer::list_versions`] and [`VersioningLayer::restore_version`]
+///   on the operator with this layer to list and restore versions.
+///
+/// # Notes
+///
+/// - Every `write` and `copy` will call `stat` on underlying services.
+/// - Versions are copied via `copy` if underlying services support it,
+///   otherwise the whole previous content will be read into memory and
+///   written again.
+/// - Versions are normal files, so they will be returned by `list` and
+///   will never be removed by this layer.
+///
+/// # Examples
+///
+/// ```
+/// use anyhow::Result;
+/// use opendal::layers::VersioningLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// # async fn test() -> Result<()> {
+/// let op = Operator::new(services::Memory::default())?
+///     .layer(VersioningLayer::new())
+///     .finish();
+///
+/// op.write("test", "v1").await?;
+/// op.write("test", "v2").await?;
+///
+/// let versions = VersioningLayer::list_versions(&op, "test").await?;
+/// let version = versions[0].name().rsplit('.').next().unwrap();
+/// VersioningLayer::restore_version(&op, "test", version).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct VersioningLayer;
+
+impl VersioningLayer {
+    /// Create a new VersioningLayer.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// List all versions of given file, sorted from oldest to newest.
+    ///
+    /// The version of returned entry is the suffix after the last `.` of its name.
+    pub async fn list_versions(op: &Operator, path: &str) -> Result<Vec<Entry>> {
+        let prefix = format!("{}.", get_basename(path));
+
+        let mut versions: Vec<(u128, Entry)> = op
+            .list(get_parent(path))
+            .await?
+            .into_iter()
+            .filter(|e| e.metadata().mode().is_file())
+            .filter_map(|e| {
+                let version = parse_version(e.name().strip_prefix(&prefix)?)?;
+                Some((version, e))
+            })
+            .collect();
+        versions.sort_by_key(|(version, _)| *version);
+
+        Ok(versions.into_iter().map(|(_, e)| e).collect())
+    }
+
+    /// Restore given version of the file by copying it back to `path`.
+    ///
+    /// Current content of `path` will be kept as a new version, so restore
+    /// can be undone too.
+    pub async fn restore_version(op: &Operator, path: &str, version: &str) -> Result<()> {
+        if parse_version(version).is_none() {
+            return Err(Error::new(ErrorKind::InvalidInput, "version is invalid")
+                .with_operation("VersioningLayer::restore_version")
+                .with_context("path", path)
+                .with_context("version", version));
+        }
+
+        let from = format!("{path}.{version}");
+        if op.info().full_capability().copy {
+            return op.copy(&from, path).await;
+        }
+
+        let bs = op.read(&from).await?;
+        op.write(path, bs).await
+    }
+}
+
+impl<A: Accessor> Layer<A> for VersioningLayer {
+    type LayeredAccessor = VersioningAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccessor {
+        VersioningAccessor { inner }
+    }
+}
+
+/// Parse version which must be a timestamp in nanoseconds.
+fn parse_version(version: &str) -> Option<u128> {
+    if version.is_empty() || !version.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    version.parse().ok()
+}
+
+/// Build the path to store the current version of given path.
+fn version_path(path: &str) -> String {
+    let ts = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+
+    format!("{path}.{ts}")
+}
+
+#[derive(Debug)]
+pub struct VersioningAccessor<A: Accessor> {
+    inner: A,
+}
+
+impl<A: Accessor> VersioningAccessor<A> {
+    /// Copy current content of path as a new version if it exists.
+    async fn snapshot(&self, path: &str) -> Result<()> {
+        let meta = match self.inner.stat(path, OpStat::new()).await {
+            Ok(rp) => rp.into_metadata(),
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        if !meta.mode().is_file() {
+            return Ok(());
+        }
+
+        let to = version_path(path);
+        if self.inner.info().full_capability().copy {
+            self.inner.copy(path, &to, OpCopy::new()).await?;
+            return Ok(());
+        }
+
+        let (_, mut r) = self.inner.read(path, OpRead::new()).await?;
+        let bs = r.read_exact(meta.content_length() as usize).await?;
+        let (_, mut w) = self.inner.write(&to, OpWrite::new()).await?;
+        w.write_all(bs).await?;
+        w.close().await
+    }
+
+    fn blocking_snapshot(&self, path: &str) -> Result<()> {
+        let meta = match self.inner.blocking_stat(path, OpStat::new()) {
+            Ok(rp) => rp.into_metadata(),
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        if !meta.mode().is_file() {
+            return Ok(());
+        }
+
+        let to = version_path(path);
+        if self.inner.info().full_capability().copy {
+            self.inner.blocking_copy(path, &to, OpCopy::new())?;
+            return Ok(());
+        }
+
+        let (_, mut r) = self.inner.blocking_read(path, OpRead::new())?;
+        let mut buf = Vec::with_capacity(meta.content_length() as usize);
+        r.read_to_end(&mut buf)?;
+        let (_, mut w) = self.inner.blocking_write(&to, OpWrite::new())?;
+        let mut bs = Bytes::from(buf);
+        while !bs.is_empty() {
+            let n = w.write(&bs)?;
+            bs = bs.slice(n..);
+        }
+        w.close()
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<A: Accessor> LayeredAccessor for VersioningAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type BlockingReader = A::BlockingReader;
+    type Writer = A::Writer;
+    type BlockingWriter = A::BlockingWriter;
+    type Lister = A::Lister;
+    type BlockingLister = A::BlockingLister;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        if !args.append() {
+            self.snapshot(path).await?;
+        }
+
+        self.inner.write(path, args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.snapshot(to).await?;
+
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.inner.list(path, args).await
+    }
+
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        self.inner.blocking_read(path, args)
+    }
+
+    fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        if !args.append() {
+            self.blocking_snapshot(path)?;
+        }
+
+        self.inner.blocking_write(path, args)
+    }
+
+    fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        self.blocking_snapshot(to)?;
+
+        self.inner.blocking_copy(from, to, args)
+    }
+
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        self.inner.blocking_list(path, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::Memory;
+
+    #[tokio::test]
+    async fn test_versioning() -> Result<()> {
+        let op = Operator::new(Memory::default())?
+            .layer(VersioningLayer::new())
+            .finish();
+
+        op.write("dir/test", "v1").await?;
+        assert!(VersioningLayer::list_versions(&op, "dir/test")
+            .await?
+            .is_empty());
+
+        op.write("dir/test", "v2").await?;
+        op.write("dir/test", "v3").await?;
+
+        let versions = VersioningLayer::list_versions(&op, "dir/test").await?;
+        assert_eq!(versions.len(), 2);
+        assert_eq!(op.read(versions[0].path()).await?, b"v1");
+        assert_eq!(op.read(versions[1].path()).await?, b"v2");
+
+        let version = versions[0].name().rsplit('.').next().unwrap();
+        VersioningLayer::restore_version(&op, "dir/test", version).await?;
+        assert_eq!(op.read("dir/test").await?, b"v1");
+
+        // The content before restore is kept as a new version.
+        let versions = VersioningLayer::list_versions(&op, "dir/test").await?;
+        assert_eq!(versions.len(), 3);
+        assert_eq!(op.read(versions[2].path()).await?, b"v3");
+
+        let err = VersioningLayer::restore_version(&op, "dir/test", "latest")
+            .await
+            .expect_err("restore must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+        Ok(())
+    }
+}
@@ -19,10 +19,14 @@ use std::fmt::Debug;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use futures::StreamExt;
 
 use crate::raw::*;
 use crate::*;
 
+/// The max number of concurrent `stat` calls in the default `batch_stat`.
+pub(crate) const BATCH_STAT_CONCURRENT_LIMIT: usize = 8;
+
 /// Underlying trait of all backends for implementers.
 ///
 /// The actual data access of storage service happens in Accessor layer.
@@ -118,6 +122,30 @@ pub trait Accessor: Send + Sync + Debug + Unpin + 'static {
         ))
     }
 
+    /// Invoke `stat` on all given paths, returns results in the same order
+    /// as paths.
+    ///
+    /// The default implementation calls [`Accessor::stat`] concurrently.
+    /// Services that can fetch metadata of multiple paths in one request
+    /// could override it.
+    ///
+    /// # Behavior
+    ///
+    /// - The outer error means the whole batch failed, the error of every
+    ///   path will be returned in its own result.
+    async fn batch_stat(&self, paths: Vec<String>, args: OpStat) -> Result<Vec<Result<RpStat>>> {
+        let results = futures::stream::iter(paths)
+            .map(|path| {
+                let args = args.clone();
+                async move { self.stat(&path, args).await }
+            })
+            .buffered(BATCH_STAT_CONCURRENT_LIMIT)
+            .collect()
+            .await;
+
+        Ok(results)
+    }
+
     /// Invoke the `read` operation on the specified path, returns a
     /// [`Reader`][crate::Reader] if operate successful.
     ///
@@ -205,6 +233,63 @@ pub trait Accessor: Send + Sync + Debug + Unpin + 'static {
         ))
     }
 
+    /// Invoke the `setxattr` operation on the specified path.
+    ///
+    /// Require [Capability::xattr]
+    ///
+    /// # Behavior
+    ///
+    /// - Existing attribute with the same name will be overwritten.
+    async fn setxattr(&self, path: &str, args: OpSetxattr) -> Result<RpSetxattr> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `getxattr` operation on the specified path.
+    ///
+    /// Require [Capability::xattr]
+    ///
+    /// # Behavior
+    ///
+    /// - Get an attribute that doesn't exist MUST fail with [`ErrorKind::NotFound`].
+    async fn getxattr(&self, path: &str, args: OpGetxattr) -> Result<RpGetxattr> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `listxattr` operation on the specified path.
+    ///
+    /// Require [Capability::xattr]
+    async fn listxattr(&self, path: &str, args: OpListxattr) -> Result<RpListxattr> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `list_buckets` operation to fetch the names of all
+    /// buckets accessible under given project.
+    ///
+    /// Require [Capability::list_buckets]
+    async fn list_buckets(&self, args: OpListBuckets) -> Result<RpListBuckets> {
+        let _ = args;
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
     /// Invoke the `rename` operation on the specified `from` path and `to` path.
     ///
     /// Require [Capability::rename]
@@ -217,6 +302,133 @@ pub trait Accessor: Send + Sync + Debug + Unpin + 'static {
         ))
     }
 
+    /// Invoke the `hard_link` operation on the specified `from` path and `to` path.
+    ///
+    /// Require [Capability::hard_link]
+    ///
+    /// # Behavior
+    ///
+    /// - `from` and `to` MUST be file path, DON'T NEED to check mode.
+    /// - Link to an existing `to` SHOULD fail with [`ErrorKind::AlreadyExists`].
+    async fn hard_link(&self, from: &str, to: &str, args: OpHardLink) -> Result<RpHardLink> {
+        let (_, _, _) = (from, to, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `symlink` operation which creates a symlink at `link`
+    /// pointing to `target`.
+    ///
+    /// Require [Capability::symlink]
+    ///
+    /// # Behavior
+    ///
+    /// - `target` is stored as is, services SHOULD NOT resolve or check it.
+    /// - `link` MUST be file path, DON'T NEED to check mode.
+    /// - Create symlink at an existing `link` SHOULD fail with [`ErrorKind::AlreadyExists`].
+    async fn symlink(&self, target: &str, link: &str, args: OpSymlink) -> Result<RpSymlink> {
+        let (_, _, _) = (target, link, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `set_permissions` operation on the specified path.
+    ///
+    /// Require [Capability::set_permissions]
+    ///
+    /// # Behavior
+    ///
+    /// - The mode in args is unix permission bits like `0o644`.
+    async fn set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `statvfs` operation to fetch the stats of underlying
+    /// file system.
+    ///
+    /// Require [Capability::statvfs]
+    async fn statvfs(&self, args: OpStatvfs) -> Result<RpStatvfs> {
+        let _ = args;
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `truncate` operation on the specified path.
+    ///
+    /// Require [Capability::truncate]
+    ///
+    /// # Behavior
+    ///
+    /// - The file will be shrunk or extended with zeros to the given length.
+    /// - Truncating a file that doesn't exist returns `NotFound`.
+    async fn truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `fallocate` operation on the specified path.
+    ///
+    /// Require [Capability::fallocate]
+    ///
+    /// # Behavior
+    ///
+    /// - Disk space for the given size will be allocated without changing
+    ///   the size of file.
+    /// - The file will be created if it doesn't exist.
+    async fn fallocate(&self, path: &str, args: OpFallocate) -> Result<RpFallocate> {
+        let (_, _) = (path, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
+    /// Invoke the `rename_noreplace` operation on the specified `from` path and `to` path.
+    ///
+    /// Require [Capability::rename_noreplace]
+    ///
+    /// # Behavior
+    ///
+    /// - Rename to an existing `to` MUST fail with [`ErrorKind::AlreadyExists`].
+    /// - The check and rename MUST be atomic, so that only one of concurrent
+    ///   renames to the same `to` could succeed.
+    async fn rename_noreplace(
+        &self,
+        from: &str,
+        to: &str,
+        args: OpRenameNoreplace,
+    ) -> Result<RpRenameNoreplace> {
+        let (_, _, _) = (from, to, args);
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        ))
+    }
+
     /// Invoke the `presign` operation on the specified path.
     ///
     /// Require [`Capability::presign`]
@@ -407,6 +619,9 @@ impl<T: Accessor + ?Sized> Accessor for Arc<T> {
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         self.as_ref().stat(path, args).await
     }
+    async fn batch_stat(&self, paths: Vec<String>, args: OpStat) -> Result<Vec<Result<RpStat>>> {
+        self.as_ref().batch_stat(paths, args).await
+    }
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
         self.as_ref().read(path, args).await
     }
@@ -428,6 +643,48 @@ impl<T: Accessor + ?Sized> Accessor for Arc<T> {
     async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
         self.as_ref().rename(from, to, args).await
     }
+    async fn hard_link(&self, from: &str, to: &str, args: OpHardLink) -> Result<RpHardLink> {
+        self.as_ref().hard_link(from, to, args).await
+    }
+    async fn symlink(&self, target: &str, link: &str, args: OpSymlink) -> Result<RpSymlink> {
+        self.as_ref().symlink(target, link, args).await
+    }
+    async fn set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        self.as_ref().set_permissions(path, args).await
+    }
+    async fn statvfs(&self, args: OpStatvfs) -> Result<RpStatvfs> {
+        self.as_ref().statvfs(args).await
+    }
+    async fn truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        self.as_ref().truncate(path, args).await
+    }
+    async fn fallocate(&self, path: &str, args: OpFallocate) -> Result<RpFallocate> {
+        self.as_ref().fallocate(path, args).await
+    }
+    async fn rename_noreplace(
+        &self,
+        from: &str,
+        to: &str,
+        args: OpRenameNoreplace,
+    ) -> Result<RpRenameNoreplace> {
+        self.as_ref().rename_noreplace(from, to, args).await
+    }
+    async fn setxattr(&self, path: &str, args: OpSetxattr) -> Result<RpSetxattr> {
+        self.as_ref().setxattr(path, args).await
+    }
+    async fn getxattr(&self, path: &str, args: OpGetxattr) -> Result<RpGetxattr> {
+        self.as_ref().getxattr(path, args).await
+    }
+    async fn listxattr(&self, path: &str, args: OpListxattr) -> Result<RpListxattr> {
+        self.as_ref().listxattr(path, args).await
+    }
+    async fn list_buckets(&self, args: OpListBuckets) -> Result<RpListBuckets> {
+        self.as_ref().list_buckets(args).await
+    }
 
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
         self.as_ref().presign(path, args).await
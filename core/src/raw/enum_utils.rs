@@ -117,6 +117,13 @@ impl<ONE: oio::Write, TWO: oio::Write> oio::Write for TwoWays<ONE, TWO> {
             Self::Two(v) => v.poll_abort(cx),
         }
     }
+
+    fn written_bytes(&self) -> u64 {
+        match self {
+            Self::One(v) => v.written_bytes(),
+            Self::Two(v) => v.written_bytes(),
+        }
+    }
 }
 
 /// ThreeWays is used to implement traits that based on three ways.
@@ -18,6 +18,7 @@
 use std::fmt::Debug;
 
 use async_trait::async_trait;
+use futures::StreamExt;
 
 use crate::raw::*;
 use crate::*;
@@ -163,10 +164,81 @@ pub trait LayeredAccessor: Send + Sync + Debug + Unpin + 'static {
         self.inner().rename(from, to, args).await
     }
 
+    async fn hard_link(&self, from: &str, to: &str, args: OpHardLink) -> Result<RpHardLink> {
+        self.inner().hard_link(from, to, args).await
+    }
+
+    async fn symlink(&self, target: &str, link: &str, args: OpSymlink) -> Result<RpSymlink> {
+        self.inner().symlink(target, link, args).await
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        self.inner().set_permissions(path, args).await
+    }
+
+    async fn statvfs(&self, args: OpStatvfs) -> Result<RpStatvfs> {
+        self.inner().statvfs(args).await
+    }
+
+    async fn truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        self.inner().truncate(path, args).await
+    }
+
+    async fn fallocate(&self, path: &str, args: OpFallocate) -> Result<RpFallocate> {
+        self.inner().fallocate(path, args).await
+    }
+
+    async fn rename_noreplace(
+        &self,
+        from: &str,
+        to: &str,
+        args: OpRenameNoreplace,
+    ) -> Result<RpRenameNoreplace> {
+        self.inner().rename_noreplace(from, to, args).await
+    }
+
+    async fn setxattr(&self, path: &str, args: OpSetxattr) -> Result<RpSetxattr> {
+        self.inner().setxattr(path, args).await
+    }
+
+    async fn getxattr(&self, path: &str, args: OpGetxattr) -> Result<RpGetxattr> {
+        self.inner().getxattr(path, args).await
+    }
+
+    async fn listxattr(&self, path: &str, args: OpListxattr) -> Result<RpListxattr> {
+        self.inner().listxattr(path, args).await
+    }
+
+    async fn list_buckets(&self, args: OpListBuckets) -> Result<RpListBuckets> {
+        self.inner().list_buckets(args).await
+    }
+
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         self.inner().stat(path, args).await
     }
 
+    /// Call `stat` of this layer concurrently by default, so that every
+    /// path still goes through this layer.
+    ///
+    /// Layers that don't change the behavior of `stat` could forward it to
+    /// inner accessor to make use of the native batch support.
+    async fn batch_stat(&self, paths: Vec<String>, args: OpStat) -> Result<Vec<Result<RpStat>>> {
+        let results = futures::stream::iter(paths)
+            .map(|path| {
+                let args = args.clone();
+                async move { self.stat(&path, args).await }
+            })
+            .buffered(BATCH_STAT_CONCURRENT_LIMIT)
+            .collect()
+            .await;
+
+        Ok(results)
+    }
+
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.inner().delete(path, args).await
     }
@@ -242,10 +314,67 @@ impl<L: LayeredAccessor> Accessor for L {
         (self as &L).rename(from, to, args).await
     }
 
+    async fn hard_link(&self, from: &str, to: &str, args: OpHardLink) -> Result<RpHardLink> {
+        (self as &L).hard_link(from, to, args).await
+    }
+
+    async fn symlink(&self, target: &str, link: &str, args: OpSymlink) -> Result<RpSymlink> {
+        (self as &L).symlink(target, link, args).await
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        (self as &L).set_permissions(path, args).await
+    }
+
+    async fn statvfs(&self, args: OpStatvfs) -> Result<RpStatvfs> {
+        (self as &L).statvfs(args).await
+    }
+
+    async fn truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        (self as &L).truncate(path, args).await
+    }
+
+    async fn fallocate(&self, path: &str, args: OpFallocate) -> Result<RpFallocate> {
+        (self as &L).fallocate(path, args).await
+    }
+
+    async fn rename_noreplace(
+        &self,
+        from: &str,
+        to: &str,
+        args: OpRenameNoreplace,
+    ) -> Result<RpRenameNoreplace> {
+        (self as &L).rename_noreplace(from, to, args).await
+    }
+
+    async fn setxattr(&self, path: &str, args: OpSetxattr) -> Result<RpSetxattr> {
+        (self as &L).setxattr(path, args).await
+    }
+
+    async fn getxattr(&self, path: &str, args: OpGetxattr) -> Result<RpGetxattr> {
+        (self as &L).getxattr(path, args).await
+    }
+
+    async fn listxattr(&self, path: &str, args: OpListxattr) -> Result<RpListxattr> {
+        (self as &L).listxattr(path, args).await
+    }
+
+    async fn list_buckets(&self, args: OpListBuckets) -> Result<RpListBuckets> {
+        (self as &L).list_buckets(args).await
+    }
+
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         (self as &L).stat(path, args).await
     }
 
+    async fn batch_stat(&self, paths: Vec<String>, args: OpStat) -> Result<Vec<Result<RpStat>>> {
+        (self as &L).batch_stat(paths, args).await
+    }
+
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         (self as &L).delete(path, args).await
     }
@@ -365,4 +494,27 @@ mod tests {
 
         assert!(*test.deleted.clone().lock().await);
     }
+
+    #[tokio::test]
+    async fn test_batch_stat() {
+        let op = Operator::new(Memory::default()).unwrap().finish();
+        op.write("a", "Hello").await.unwrap();
+        op.write("b", "Hello, World!").await.unwrap();
+
+        let paths = vec!["a".to_string(), "not_exist".to_string(), "b".to_string()];
+        let results = op
+            .into_inner()
+            .batch_stat(paths, OpStat::new())
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 3);
+        let sizes: Vec<_> = results
+            .into_iter()
+            .map(|res| res.map(|rp| rp.into_metadata().content_length()))
+            .collect();
+        assert_eq!(sizes[0].as_ref().unwrap(), &5);
+        assert_eq!(sizes[1].as_ref().unwrap_err().kind(), ErrorKind::NotFound);
+        assert_eq!(sizes[2].as_ref().unwrap(), &13);
+    }
 }
@@ -19,6 +19,7 @@ use std::fmt::Display;
 use std::fmt::Formatter;
 use std::future::Future;
 use std::pin::Pin;
+use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
 
@@ -101,6 +102,15 @@ pub trait ListExt: List {
     fn next(&mut self) -> NextFuture<Self> {
         NextFuture { lister: self }
     }
+
+    /// Build a future that drives `poll_next` to the end and collects all
+    /// entries into a `Vec`.
+    fn collect(&mut self) -> CollectFuture<Self> {
+        CollectFuture {
+            lister: self,
+            entries: Vec::new(),
+        }
+    }
 }
 
 pub struct NextFuture<'a, L: List + Unpin + ?Sized> {
@@ -118,6 +128,27 @@ where
     }
 }
 
+pub struct CollectFuture<'a, L: List + Unpin + ?Sized> {
+    lister: &'a mut L,
+    entries: Vec<Entry>,
+}
+
+impl<L> Future for CollectFuture<'_, L>
+where
+    L: List + Unpin + ?Sized,
+{
+    type Output = Result<Vec<Entry>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<Vec<Entry>>> {
+        loop {
+            match ready!(self.lister.poll_next(cx))? {
+                Some(entry) => self.entries.push(entry),
+                None => return Poll::Ready(Ok(std::mem::take(&mut self.entries))),
+            }
+        }
+    }
+}
+
 /// BlockingList is the blocking version of [`List`].
 pub trait BlockingList: Send + 'static {
     /// Fetch a new page of [`Entry`]
@@ -125,6 +156,16 @@ pub trait BlockingList: Send + 'static {
     /// `Ok(None)` means all pages have been returned. Any following call
     /// to `next` will always get the same result.
     fn next(&mut self) -> Result<Option<Entry>>;
+
+    /// Call `next` until the end and collect all entries into a `Vec`.
+    fn collect(&mut self) -> Result<Vec<Entry>> {
+        let mut entries = Vec::new();
+        while let Some(entry) = self.next()? {
+            entries.push(entry);
+        }
+
+        Ok(entries)
+    }
 }
 
 /// BlockingLister is a boxed [`BlockingList`]
@@ -134,6 +175,10 @@ impl<P: BlockingList + ?Sized> BlockingList for Box<P> {
     fn next(&mut self) -> Result<Option<Entry>> {
         (**self).next()
     }
+
+    fn collect(&mut self) -> Result<Vec<Entry>> {
+        (**self).collect()
+    }
 }
 
 impl BlockingList for () {
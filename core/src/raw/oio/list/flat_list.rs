@@ -20,6 +20,7 @@ use std::task::Context;
 use std::task::Poll;
 
 use futures::FutureExt;
+use log::warn;
 
 use crate::raw::*;
 use crate::*;
@@ -66,6 +67,8 @@ type ListFuture<A, L> = BoxedStaticFuture<(A, oio::Entry, Result<(RpList, L)>)>;
 pub struct FlatLister<A: Accessor, L> {
     acc: Option<A>,
     root: String,
+    skip_hidden: bool,
+    max_depth: Option<usize>,
 
     next_dir: Option<oio::Entry>,
     active_lister: Vec<(Option<oio::Entry>, L)>,
@@ -90,11 +93,47 @@ where
         FlatLister {
             acc: Some(acc),
             root: path.to_string(),
+            skip_hidden: false,
+            max_depth: None,
             next_dir: Some(oio::Entry::new(path, Metadata::new(EntryMode::DIR))),
             active_lister: vec![],
             list_future: None,
         }
     }
+
+    /// Skip hidden entries while listing nested dirs.
+    pub fn with_skip_hidden(mut self, skip_hidden: bool) -> Self {
+        self.skip_hidden = skip_hidden;
+        self
+    }
+
+    /// Stop listing nested dirs deeper than max_depth, `None` means unlimited.
+    ///
+    /// Root dir is at depth 0, so max depth `1` lists the root dir only.
+    pub fn with_max_depth(mut self, max_depth: Option<usize>) -> Self {
+        if max_depth == Some(0) {
+            self.next_dir = None;
+        }
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Check if the dir found in current lister can be listed.
+    ///
+    /// The dir is at the depth of the count of active listers.
+    fn can_list(&self, dir: &oio::Entry) -> bool {
+        let depth = self.active_lister.len();
+        match self.max_depth {
+            Some(max_depth) if depth >= max_depth => {
+                warn!(
+                    "flat lister reached max depth {max_depth}, dir {} will not be listed",
+                    dir.path()
+                );
+                false
+            }
+            _ => true,
+        }
+    }
 }
 
 impl<A, L> oio::List for FlatLister<A, L>
@@ -115,8 +154,9 @@ where
 
             if let Some(de) = self.next_dir.take() {
                 let acc = self.acc.take().expect("Accessor must be valid");
+                let args = OpList::new().with_skip_hidden(self.skip_hidden);
                 let fut = async move {
-                    let res = acc.list(de.path(), OpList::new()).await;
+                    let res = acc.list(de.path(), args).await;
                     (acc, de, res)
                 };
                 self.list_future = Some(Box::pin(fut));
@@ -130,6 +170,9 @@ where
 
             match ready!(lister.poll_next(cx))? {
                 Some(v) if v.mode().is_dir() => {
+                    if !self.can_list(&v) {
+                        return Poll::Ready(Ok(Some(v)));
+                    }
                     self.next_dir = Some(v);
                     continue;
                 }
@@ -163,7 +206,8 @@ where
         loop {
             if let Some(de) = self.next_dir.take() {
                 let acc = self.acc.take().expect("Accessor must be valid");
-                let (_, l) = acc.blocking_list(de.path(), OpList::new())?;
+                let args = OpList::new().with_skip_hidden(self.skip_hidden);
+                let (_, l) = acc.blocking_list(de.path(), args)?;
 
                 self.acc = Some(acc);
                 self.active_lister.push((Some(de), l))
@@ -176,6 +220,9 @@ where
 
             match lister.next()? {
                 Some(v) if v.mode().is_dir() => {
+                    if !self.can_list(&v) {
+                        return Ok(Some(v));
+                    }
                     self.next_dir = Some(v);
                     continue;
                 }
@@ -294,4 +341,29 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_blocking_list_with_max_depth() -> Result<()> {
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let cases = vec![
+            (0, vec![]),
+            (1, vec!["x/x/"]),
+            (2, vec!["x/x/x/", "x/x/"]),
+            (3, vec!["x/x/x/x", "x/x/x/", "x/x/"]),
+        ];
+
+        for (max_depth, expected) in cases {
+            let mut lister =
+                FlatLister::new(MockService::new(), "x/").with_max_depth(Some(max_depth));
+
+            let mut entries = Vec::default();
+            while let Some(e) = lister.next()? {
+                entries.push(e.path().to_string())
+            }
+            assert_eq!(entries, expected, "max_depth {max_depth}");
+        }
+
+        Ok(())
+    }
 }
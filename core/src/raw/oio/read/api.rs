@@ -18,9 +18,11 @@
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::io;
+use std::ops::Deref;
 use std::ops::DerefMut;
 
 use bytes::Bytes;
+use bytes::BytesMut;
 use futures::Future;
 use tokio::io::ReadBuf;
 
@@ -35,6 +37,8 @@ pub enum ReadOperation {
     Read,
     /// Operation for [`Read::seek`]
     Seek,
+    /// Operation for [`Read::read_at`]
+    ReadAt,
     /// Operation for [`BlockingRead::read`]
     BlockingRead,
     /// Operation for [`BlockingRead::seek`]
@@ -63,6 +67,7 @@ impl From<ReadOperation> for &'static str {
         match v {
             Read => "Reader::read",
             Seek => "Reader::seek",
+            ReadAt => "Reader::read_at",
             BlockingRead => "BlockingReader::read",
             BlockingSeek => "BlockingReader::seek",
             BlockingNext => "BlockingReader::next",
@@ -110,6 +115,74 @@ pub trait Read: Unpin + Send + Sync {
     fn seek(&mut self, pos: io::SeekFrom) -> impl Future<Output = Result<u64>> + Send;
     #[cfg(target_arch = "wasm32")]
     fn seek(&mut self, pos: io::SeekFrom) -> impl Future<Output = Result<u64>>;
+
+    /// Read exactly `n` bytes from underlying reader.
+    ///
+    /// Returns `ContentIncomplete` error if reader reaches its end before
+    /// `n` bytes have been read.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn read_exact(&mut self, n: usize) -> impl Future<Output = Result<Bytes>> + Send {
+        read_exact(self, n)
+    }
+    #[cfg(target_arch = "wasm32")]
+    fn read_exact(&mut self, n: usize) -> impl Future<Output = Result<Bytes>> {
+        read_exact(self, n)
+    }
+
+    /// Read at most `limit` bytes at `offset` without moving the position
+    /// of this reader.
+    ///
+    /// Unlike `seek` + `read`, this only takes `&self` so that multiple
+    /// range reads can be served concurrently.
+    ///
+    /// Returns `Unsupported` error if underlying reader doesn't support
+    /// positional reads.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn read_at(&self, offset: u64, limit: usize) -> impl Future<Output = Result<Bytes>> + Send {
+        let _ = (offset, limit);
+
+        async {
+            Err(Error::new(
+                ErrorKind::Unsupported,
+                "reader doesn't support read_at",
+            ))
+        }
+    }
+    #[cfg(target_arch = "wasm32")]
+    fn read_at(&self, offset: u64, limit: usize) -> impl Future<Output = Result<Bytes>> {
+        let _ = (offset, limit);
+
+        async {
+            Err(Error::new(
+                ErrorKind::Unsupported,
+                "reader doesn't support read_at",
+            ))
+        }
+    }
+}
+
+async fn read_exact<R: Read + ?Sized>(r: &mut R, n: usize) -> Result<Bytes> {
+    let bs = r.read(n).await?;
+    // Fast path that underlying reader returns all data at once.
+    if bs.len() == n {
+        return Ok(bs);
+    }
+
+    let mut buf = BytesMut::with_capacity(n);
+    buf.extend_from_slice(&bs);
+    while buf.len() < n {
+        let bs = r.read(n - buf.len()).await?;
+        if bs.is_empty() {
+            return Err(
+                Error::new(ErrorKind::ContentIncomplete, "reader got too little data")
+                    .with_context("expect", n.to_string())
+                    .with_context("actual", buf.len().to_string()),
+            );
+        }
+        buf.extend_from_slice(&bs);
+    }
+
+    Ok(buf.freeze())
 }
 
 impl Read for () {
@@ -136,6 +209,8 @@ pub trait ReadDyn: Unpin + Send + Sync {
     fn read_dyn(&mut self, limit: usize) -> BoxedFuture<Result<Bytes>>;
 
     fn seek_dyn(&mut self, pos: io::SeekFrom) -> BoxedFuture<Result<u64>>;
+
+    fn read_at_dyn(&self, offset: u64, limit: usize) -> BoxedFuture<Result<Bytes>>;
 }
 
 impl<T: Read + ?Sized> ReadDyn for T {
@@ -146,6 +221,10 @@ impl<T: Read + ?Sized> ReadDyn for T {
     fn seek_dyn(&mut self, pos: io::SeekFrom) -> BoxedFuture<Result<u64>> {
         Box::pin(self.seek(pos))
     }
+
+    fn read_at_dyn(&self, offset: u64, limit: usize) -> BoxedFuture<Result<Bytes>> {
+        Box::pin(self.read_at(offset, limit))
+    }
 }
 
 /// # NOTE
@@ -160,6 +239,10 @@ impl<T: ReadDyn + ?Sized> Read for Box<T> {
     async fn seek(&mut self, pos: io::SeekFrom) -> Result<u64> {
         self.deref_mut().seek_dyn(pos).await
     }
+
+    async fn read_at(&self, offset: u64, limit: usize) -> Result<Bytes> {
+        self.deref().read_at_dyn(offset, limit).await
+    }
 }
 
 /// BlockingReader is a boxed dyn `BlockingRead`.
@@ -272,3 +355,43 @@ impl<T: BlockingRead + ?Sized> BlockingRead for Box<T> {
         (**self).next()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A reader that returns at most `step` bytes for every read.
+    struct StepReader {
+        inner: Bytes,
+        step: usize,
+    }
+
+    impl Read for StepReader {
+        async fn read(&mut self, limit: usize) -> Result<Bytes> {
+            let n = self.inner.len().min(limit).min(self.step);
+            Ok(self.inner.split_to(n))
+        }
+
+        async fn seek(&mut self, _: io::SeekFrom) -> Result<u64> {
+            unimplemented!()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_exact() {
+        let mut r = StepReader {
+            inner: Bytes::from("Hello, World!"),
+            step: 3,
+        };
+
+        let bs = r.read_exact(5).await.unwrap();
+        assert_eq!(bs, "Hello");
+        let bs = r.read_exact(0).await.unwrap();
+        assert_eq!(bs, "");
+        let bs = r.read_exact(8).await.unwrap();
+        assert_eq!(bs, ", World!");
+
+        let err = r.read_exact(1).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ContentIncomplete);
+    }
+}
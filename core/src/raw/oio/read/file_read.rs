@@ -261,7 +261,19 @@ where
             (self.offset, self.size) = Self::offset(r, self.op.range()).await?;
         }
 
-        r.read(limit).await
+        let limit = if let Some(size) = self.size {
+            // Sanity check.
+            if self.cur >= size {
+                return Ok(Bytes::new());
+            }
+            cmp::min(limit, (size - self.cur) as usize)
+        } else {
+            limit
+        };
+
+        let bs = r.read(limit).await?;
+        self.cur += bs.len() as u64;
+        Ok(bs)
     }
 }
 
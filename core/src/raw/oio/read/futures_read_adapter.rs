@@ -0,0 +1,130 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp;
+use std::io;
+use std::pin::Pin;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use bytes::Bytes;
+use futures::AsyncRead;
+use futures::FutureExt;
+
+use crate::raw::*;
+
+/// FuturesReadAdapter implements [`AsyncRead`] via [`oio::Read`].
+///
+/// The future returned by [`oio::Read::read`] is stored inside the adapter
+/// and will be driven to completion across polls, so the waker registered
+/// by inner reader is always respected.
+///
+/// # Notes
+///
+/// Inner reader may return more bytes than the size of given buf if the
+/// buf passed in later polls is smaller, the extra bytes will be
+/// kept and returned in the following reads.
+pub struct FuturesReadAdapter<R: oio::Read> {
+    state: State<R>,
+    /// Bytes that have been read from inner reader but not returned yet.
+    buf: Bytes,
+}
+
+enum State<R> {
+    Idle(Option<R>),
+    Reading(BoxedStaticFuture<(R, crate::Result<Bytes>)>),
+}
+
+/// # Safety
+///
+/// FuturesReadAdapter will only be used with `&mut self`.
+unsafe impl<R: oio::Read> Sync for FuturesReadAdapter<R> {}
+
+impl<R: oio::Read> FuturesReadAdapter<R> {
+    /// Create a new futures read adapter.
+    pub fn new(r: R) -> Self {
+        Self {
+            state: State::Idle(Some(r)),
+            buf: Bytes::new(),
+        }
+    }
+}
+
+impl<R: oio::Read + 'static> AsyncRead for FuturesReadAdapter<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        loop {
+            if !this.buf.is_empty() {
+                let n = cmp::min(buf.len(), this.buf.len());
+                buf[..n].copy_from_slice(&this.buf.split_to(n));
+                return Poll::Ready(Ok(n));
+            }
+
+            match &mut this.state {
+                State::Idle(r) => {
+                    let mut r = r.take().expect("reader must be valid");
+                    let size = buf.len();
+                    let fut = async move {
+                        let res = r.read(size).await;
+                        (r, res)
+                    };
+                    this.state = State::Reading(Box::pin(fut));
+                }
+                State::Reading(fut) => {
+                    let (r, res) = ready!(fut.poll_unpin(cx));
+                    this.state = State::Idle(Some(r));
+                    let bs = res.map_err(format_std_io_error)?;
+                    if bs.is_empty() {
+                        return Poll::Ready(Ok(0));
+                    }
+                    this.buf = bs;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::AsyncReadExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_futures_read_adapter() {
+        let r = oio::Cursor::from(b"Hello, World!".to_vec());
+        let mut r = FuturesReadAdapter::new(r);
+
+        let mut buf = [0; 5];
+        r.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"Hello");
+
+        let mut buf = Vec::new();
+        r.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf, b", World!");
+    }
+}
@@ -42,6 +42,12 @@ pub use futures_read::FuturesReader;
 mod tokio_read;
 pub use tokio_read::TokioReader;
 
+mod tokio_read_adapter;
+pub use tokio_read_adapter::TokioReadAdapter;
+
+mod futures_read_adapter;
+pub use futures_read_adapter::FuturesReadAdapter;
+
 mod std_read;
 pub use std_read::StdReader;
 
@@ -50,3 +56,6 @@ pub use lazy_read::LazyReader;
 
 mod buffer_reader;
 pub use buffer_reader::BufferReader;
+
+mod spanning_read;
+pub use spanning_read::SpanningReader;
@@ -0,0 +1,187 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io::SeekFrom;
+
+use bytes::Bytes;
+
+use crate::raw::*;
+use crate::*;
+
+/// SpanningReader concatenates multiple parts into one reader.
+///
+/// Parts are read in order, the next part will be read after current part
+/// returns empty bytes.
+///
+/// # Notes
+///
+/// The size of every part will be fetched via `seek` on the first seek of
+/// this reader.
+pub struct SpanningReader<R> {
+    parts: Vec<R>,
+    /// The size of every part, loaded while seeking.
+    sizes: Option<Vec<u64>>,
+    /// The index of current part, equals to `parts.len()` if all parts
+    /// have been consumed.
+    idx: usize,
+    /// The offset beyond the end of all parts after seeking past the end.
+    end_offset: u64,
+}
+
+impl<R> SpanningReader<R> {
+    /// Create a new spanning reader from given parts.
+    pub fn new(parts: Vec<R>) -> Self {
+        Self {
+            parts,
+            sizes: None,
+            idx: 0,
+            end_offset: 0,
+        }
+    }
+}
+
+impl<R: oio::Read> SpanningReader<R> {
+    async fn load_sizes(&mut self) -> Result<Vec<u64>> {
+        if let Some(sizes) = &self.sizes {
+            return Ok(sizes.clone());
+        }
+
+        let mut sizes = Vec::with_capacity(self.parts.len());
+        for part in self.parts.iter_mut() {
+            sizes.push(part.seek(SeekFrom::End(0)).await?);
+        }
+        self.sizes = Some(sizes.clone());
+        Ok(sizes)
+    }
+}
+
+impl<R: oio::Read> oio::Read for SpanningReader<R> {
+    async fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        // Offset in current part must be fetched before loading sizes
+        // which will move all parts to the end.
+        let offset = match self.parts.get_mut(self.idx) {
+            Some(part) => part.seek(SeekFrom::Current(0)).await?,
+            None => self.end_offset,
+        };
+        let sizes = self.load_sizes().await?;
+        let total: u64 = sizes.iter().sum();
+
+        let (base, amt) = match pos {
+            SeekFrom::Start(n) => (0, n as i64),
+            SeekFrom::End(n) => (total as i64, n),
+            SeekFrom::Current(n) => {
+                let cur: u64 = sizes[..self.idx].iter().sum::<u64>() + offset;
+                (cur as i64, n)
+            }
+        };
+        let seek_pos = match base.checked_add(amt) {
+            Some(n) if n >= 0 => n as u64,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "invalid seek to a negative or overflowing position",
+                ))
+            }
+        };
+
+        // Find the part that contains seek_pos and rewind all parts after it.
+        self.idx = self.parts.len();
+        self.end_offset = seek_pos.saturating_sub(total);
+        let mut start = 0;
+        for (idx, size) in sizes.iter().enumerate() {
+            if self.idx == self.parts.len() && seek_pos < start + size {
+                self.idx = idx;
+                self.parts[idx]
+                    .seek(SeekFrom::Start(seek_pos - start))
+                    .await?;
+            } else if idx > self.idx {
+                self.parts[idx].seek(SeekFrom::Start(0)).await?;
+            }
+            start += size;
+        }
+
+        Ok(seek_pos)
+    }
+
+    async fn read(&mut self, limit: usize) -> Result<Bytes> {
+        while let Some(part) = self.parts.get_mut(self.idx) {
+            let bs = part.read(limit).await?;
+            // Empty result of zero limit doesn't mean the end of part.
+            if !bs.is_empty() || limit == 0 {
+                return Ok(bs);
+            }
+            self.idx += 1;
+        }
+
+        Ok(Bytes::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raw::oio::Read;
+
+    fn new_reader() -> SpanningReader<oio::Cursor> {
+        SpanningReader::new(vec![
+            oio::Cursor::from(b"Hello".to_vec()),
+            oio::Cursor::from(Vec::new()),
+            oio::Cursor::from(b", World".to_vec()),
+            oio::Cursor::from(b"!".to_vec()),
+        ])
+    }
+
+    async fn read_to_end(r: &mut SpanningReader<oio::Cursor>) -> Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        loop {
+            let bs = r.read(4).await?;
+            if bs.is_empty() {
+                return Ok(buf);
+            }
+            buf.extend_from_slice(&bs);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spanning_read() -> Result<()> {
+        let mut r = new_reader();
+        assert_eq!(read_to_end(&mut r).await?, b"Hello, World!");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_spanning_seek() -> Result<()> {
+        let mut r = new_reader();
+
+        assert_eq!(r.seek(SeekFrom::Start(7)).await?, 7);
+        assert_eq!(read_to_end(&mut r).await?, b"World!");
+
+        assert_eq!(r.seek(SeekFrom::End(-8)).await?, 5);
+        assert_eq!(r.read(2).await?, b", ".as_slice());
+        assert_eq!(r.seek(SeekFrom::Current(-4)).await?, 3);
+        assert_eq!(read_to_end(&mut r).await?, b"lo, World!");
+
+        assert_eq!(r.seek(SeekFrom::End(10)).await?, 23);
+        assert!(r.read(4).await?.is_empty());
+        assert_eq!(r.seek(SeekFrom::Current(-20)).await?, 3);
+        assert_eq!(read_to_end(&mut r).await?, b"lo, World!");
+        assert!(r.seek(SeekFrom::Current(-14)).await.is_err());
+
+        Ok(())
+    }
+}
@@ -41,6 +41,14 @@ impl<R: AsyncRead + AsyncSeek> TokioReader<R> {
             buf: Vec::with_capacity(64 * 1024),
         }
     }
+
+    /// Create a new tokio reader with given capacity of internal buffer.
+    pub fn with_capacity(inner: R, capacity: usize) -> Self {
+        Self {
+            inner,
+            buf: Vec::with_capacity(capacity),
+        }
+    }
 }
 
 impl<R> oio::Read for TokioReader<R>
@@ -19,9 +19,13 @@ use std::fmt::Display;
 use std::fmt::Formatter;
 use std::future::Future;
 use std::pin::Pin;
+use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
 
+use bytes::Buf;
+use bytes::Bytes;
+
 use crate::raw::*;
 use crate::*;
 
@@ -91,6 +95,13 @@ pub trait Write: Unpin + Send + Sync {
 
     /// Abort the pending writer.
     fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>>;
+
+    /// Get the number of bytes that have been accepted by this writer.
+    ///
+    /// Returns `0` if the writer doesn't track it.
+    fn written_bytes(&self) -> u64 {
+        0
+    }
 }
 
 impl Write for () {
@@ -128,6 +139,10 @@ impl<T: Write + ?Sized> Write for Box<T> {
     fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
         (**self).poll_abort(cx)
     }
+
+    fn written_bytes(&self) -> u64 {
+        (**self).written_bytes()
+    }
 }
 
 /// Impl WriteExt for all T: Write
@@ -140,6 +155,14 @@ pub trait WriteExt: Write {
         WriteFuture { writer: self, buf }
     }
 
+    /// Build a future that calls `poll_write` until all bytes are written.
+    ///
+    /// `poll_write` could accept less bytes than given, this future will
+    /// advance the input and retry until it's fully consumed.
+    fn write_all(&mut self, buf: Bytes) -> WriteAllFuture<Self> {
+        WriteAllFuture { writer: self, buf }
+    }
+
     /// Build a future for `poll_close`.
     fn close(&mut self) -> CloseFuture<Self> {
         CloseFuture { writer: self }
@@ -168,6 +191,34 @@ where
     }
 }
 
+pub struct WriteAllFuture<'a, W: Write + Unpin + ?Sized> {
+    writer: &'a mut W,
+    buf: Bytes,
+}
+
+impl<W> Future for WriteAllFuture<'_, W>
+where
+    W: Write + Unpin + ?Sized,
+{
+    type Output = Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        while this.buf.has_remaining() {
+            let n = ready!(this.writer.poll_write(cx, &this.buf))?;
+            if n == 0 {
+                return Poll::Ready(Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "writer accepted zero bytes",
+                )));
+            }
+            this.buf.advance(n);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
 pub struct AbortFuture<'a, W: Write + Unpin + ?Sized> {
     writer: &'a mut W,
 }
@@ -208,6 +259,13 @@ pub trait BlockingWrite: Send + Sync + 'static {
 
     /// Close the writer and make sure all data has been flushed.
     fn close(&mut self) -> Result<()>;
+
+    /// Get the number of bytes that have been accepted by this writer.
+    ///
+    /// Returns `0` if the writer doesn't track it.
+    fn written_bytes(&self) -> u64 {
+        0
+    }
 }
 
 impl BlockingWrite for () {
@@ -236,4 +294,55 @@ impl<T: BlockingWrite + ?Sized> BlockingWrite for Box<T> {
     fn close(&mut self) -> Result<()> {
         (**self).close()
     }
+
+    fn written_bytes(&self) -> u64 {
+        (**self).written_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A writer that accepts at most `step` bytes for every write.
+    struct StepWriter {
+        buf: Vec<u8>,
+        step: usize,
+    }
+
+    impl Write for StepWriter {
+        fn poll_write(
+            &mut self,
+            _: &mut Context<'_>,
+            bs: &dyn oio::WriteBuf,
+        ) -> Poll<Result<usize>> {
+            let n = bs.remaining().min(self.step);
+            self.buf.extend_from_slice(&bs.bytes(n));
+            Poll::Ready(Ok(n))
+        }
+
+        fn poll_close(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_abort(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_all() {
+        let mut w = StepWriter {
+            buf: Vec::new(),
+            step: 3,
+        };
+
+        w.write_all(Bytes::from("Hello, World!")).await.unwrap();
+        w.write_all(Bytes::new()).await.unwrap();
+        assert_eq!(w.buf, b"Hello, World!");
+
+        w.step = 0;
+        let err = w.write_all(Bytes::from("!")).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
 }
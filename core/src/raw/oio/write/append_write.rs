@@ -61,6 +61,7 @@ pub struct AppendWriter<W: AppendWrite> {
     state: State<W>,
 
     offset: Option<u64>,
+    written: u64,
 }
 
 enum State<W> {
@@ -85,6 +86,7 @@ impl<W: AppendWrite> AppendWriter<W> {
         Self {
             state: State::Idle(Some(inner)),
             offset: None,
+            written: 0,
         }
     }
 }
@@ -130,6 +132,7 @@ where
                     let size = size?;
                     // Update offset after succeed.
                     self.offset = self.offset.map(|offset| offset + size as u64);
+                    self.written += size as u64;
                     return Poll::Ready(Ok(size));
                 }
             }
@@ -143,4 +146,8 @@ where
     fn poll_abort(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
         Poll::Ready(Ok(()))
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.written
+    }
 }
@@ -140,6 +140,7 @@ pub struct BlockWriter<W: BlockWrite> {
     block_ids: Vec<Uuid>,
     cache: Option<oio::ChunkedBytes>,
     futures: ConcurrentFutures<WriteBlockFuture>,
+    written: u64,
 }
 
 enum State {
@@ -167,6 +168,7 @@ impl<W: BlockWrite> BlockWriter<W> {
             block_ids: Vec::new(),
             cache: None,
             futures: ConcurrentFutures::new(1.max(concurrent)),
+            written: 0,
         }
     }
 
@@ -175,6 +177,7 @@ impl<W: BlockWrite> BlockWriter<W> {
         let bs = oio::ChunkedBytes::from_vec(bs.vectored_bytes(size));
         assert!(self.cache.is_none());
         self.cache = Some(bs);
+        self.written += size as u64;
         size
     }
 }
@@ -322,6 +325,10 @@ where
             }
         }
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.written
+    }
 }
 
 #[cfg(test)]
@@ -40,6 +40,7 @@ pub struct ExactBufWriter<W: oio::Write> {
     /// The size for buffer, we will flush the underlying storage at the size of this buffer.
     buffer_size: usize,
     buffer: oio::ChunkedBytes,
+    written: u64,
 }
 
 impl<W: oio::Write> ExactBufWriter<W> {
@@ -49,6 +50,7 @@ impl<W: oio::Write> ExactBufWriter<W> {
             inner,
             buffer_size,
             buffer: oio::ChunkedBytes::default(),
+            written: 0,
         }
     }
 }
@@ -62,6 +64,7 @@ impl<W: oio::Write> oio::Write for ExactBufWriter<W> {
 
         let remaining = self.buffer_size - self.buffer.len();
         let written = self.buffer.extend_from_write_buf(remaining, bs);
+        self.written += written as u64;
         Poll::Ready(Ok(written))
     }
 
@@ -78,6 +81,11 @@ impl<W: oio::Write> oio::Write for ExactBufWriter<W> {
         self.buffer.clear();
         self.inner.poll_abort(cx)
     }
+
+    // Bytes are accepted once they have been buffered.
+    fn written_bytes(&self) -> u64 {
+        self.written
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,167 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::io;
+use std::pin::Pin;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use futures::AsyncWrite;
+
+use crate::raw::oio::WriteBuf;
+use crate::raw::*;
+
+/// The default buffer size of [`FuturesWriteAdapter`].
+const DEFAULT_BUFFER_SIZE: usize = 256 * 1024;
+
+/// FuturesWriteAdapter implements [`AsyncWrite`] via [`oio::Write`].
+///
+/// Incoming bytes are buffered and written to inner writer once the buffer
+/// is full, so that small writes from libraries like codecs won't be
+/// sent to underlying storage one by one.
+///
+/// - `poll_flush` writes all buffered bytes to inner writer.
+/// - `poll_close` writes all buffered bytes and closes inner writer.
+pub struct FuturesWriteAdapter<W: oio::Write> {
+    inner: W,
+
+    /// The size for buffer, we will write to inner writer once the buffer is full.
+    buffer_size: usize,
+    buffer: oio::ChunkedBytes,
+    written: u64,
+}
+
+impl<W: oio::Write> FuturesWriteAdapter<W> {
+    /// Create a new futures write adapter.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+            buffer: oio::ChunkedBytes::default(),
+            written: 0,
+        }
+    }
+
+    /// Set the buffer size of this adapter, default to 256 KiB.
+    ///
+    /// Setting it to `0` disables the buffer.
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+
+    /// Get the number of bytes that have been accepted by this adapter,
+    /// including the buffered ones.
+    pub fn written_bytes(&self) -> u64 {
+        self.written
+    }
+
+    /// Write all buffered bytes to inner writer.
+    fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while !self.buffer.is_empty() {
+            let n = ready!(self.inner.poll_write(cx, &self.buffer)).map_err(format_std_io_error)?;
+            self.buffer.advance(n);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: oio::Write> AsyncWrite for FuturesWriteAdapter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        if this.buffer_size == 0 {
+            ready!(this.poll_drain(cx))?;
+            let n = ready!(this.inner.poll_write(cx, &buf)).map_err(format_std_io_error)?;
+            this.written += n as u64;
+            return Poll::Ready(Ok(n));
+        }
+
+        if this.buffer.len() >= this.buffer_size {
+            ready!(this.poll_drain(cx))?;
+        }
+
+        let remaining = this.buffer_size - this.buffer.len();
+        let written = this.buffer.extend_from_write_buf(remaining, &buf);
+        this.written += written as u64;
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().poll_drain(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        ready!(this.poll_drain(cx))?;
+        this.inner.poll_close(cx).map_err(format_std_io_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::AsyncWriteExt;
+
+    use super::*;
+    use crate::*;
+
+    #[derive(Default)]
+    struct MockWriter {
+        buf: Vec<u8>,
+        closed: bool,
+    }
+
+    impl oio::Write for MockWriter {
+        fn poll_write(&mut self, _: &mut Context<'_>, bs: &dyn WriteBuf) -> Poll<Result<usize>> {
+            self.buf.extend_from_slice(bs.chunk());
+            Poll::Ready(Ok(bs.chunk().len()))
+        }
+
+        fn poll_close(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
+            self.closed = true;
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_abort(&mut self, _: &mut Context<'_>) -> Poll<Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_futures_write_adapter() {
+        let mut w = FuturesWriteAdapter::new(MockWriter::default()).with_buffer_size(8);
+
+        for _ in 0..4 {
+            w.write_all(b"Hello").await.unwrap();
+        }
+        // Only full buffers are written before flush.
+        assert_eq!(w.inner.buf, b"HelloHelloHelloH");
+        // Buffered bytes have been accepted too.
+        assert_eq!(w.written_bytes(), 20);
+
+        w.close().await.unwrap();
+        assert_eq!(w.inner.buf, b"HelloHelloHelloHello");
+        assert!(w.inner.closed);
+    }
+}
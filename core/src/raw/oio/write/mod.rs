@@ -46,3 +46,9 @@ pub use range_write::RangeWriter;
 mod block_write;
 pub use block_write::BlockWrite;
 pub use block_write::BlockWriter;
+
+mod tokio_write_adapter;
+pub use tokio_write_adapter::TokioWriteAdapter;
+
+mod futures_write_adapter;
+pub use futures_write_adapter::FuturesWriteAdapter;
@@ -173,6 +173,7 @@ pub struct MultipartWriter<W: MultipartWrite> {
     cache: Option<oio::ChunkedBytes>,
     futures: ConcurrentFutures<WritePartFuture>,
     next_part_number: usize,
+    written: u64,
 }
 
 enum State {
@@ -203,6 +204,7 @@ impl<W: MultipartWrite> MultipartWriter<W> {
             cache: None,
             futures: ConcurrentFutures::new(1.max(concurrent)),
             next_part_number: 0,
+            written: 0,
         }
     }
 
@@ -211,6 +213,7 @@ impl<W: MultipartWrite> MultipartWriter<W> {
         let bs = oio::ChunkedBytes::from_vec(bs.vectored_bytes(size));
         assert!(self.cache.is_none());
         self.cache = Some(bs);
+        self.written += size as u64;
         size
     }
 }
@@ -400,6 +403,10 @@ where
             }
         }
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.written
+    }
 }
 
 #[cfg(test)]
@@ -412,6 +419,7 @@ mod tests {
     use rand::RngCore;
 
     use super::*;
+    use crate::raw::oio::Write;
     use crate::raw::oio::WriteExt;
 
     struct TestWrite {
@@ -520,5 +528,40 @@ mod tests {
 
         let actual_size = w.w.lock().unwrap().length;
         assert_eq!(actual_size, total_size);
+        assert_eq!(w.written_bytes(), total_size);
+    }
+
+    #[tokio::test]
+    async fn test_buffered_multipart_writer_written_bytes() {
+        let mut rng = thread_rng();
+
+        let mut w = oio::ExactBufWriter::new(MultipartWriter::new(TestWrite::new(), 8), 4096);
+        let mut total_size = 0u64;
+
+        for _ in 0..100 {
+            let size = rng.gen_range(1..1024);
+            total_size += size as u64;
+
+            let mut bs = vec![0; size];
+            rng.fill_bytes(&mut bs);
+
+            let mut buf = bs.as_slice();
+            while !buf.is_empty() {
+                match w.write(&buf).await {
+                    Ok(n) => buf = &buf[n..],
+                    Err(_) => continue,
+                }
+            }
+        }
+        // Buffered bytes have been accepted before close.
+        assert_eq!(w.written_bytes(), total_size);
+
+        loop {
+            match w.close().await {
+                Ok(_) => break,
+                Err(_) => continue,
+            }
+        }
+        assert_eq!(w.written_bytes(), total_size);
     }
 }
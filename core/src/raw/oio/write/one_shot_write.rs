@@ -129,4 +129,11 @@ impl<W: OneShotWrite> oio::Write for OneShotWriter<W> {
         self.buffer = None;
         Poll::Ready(Ok(()))
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.buffer
+            .as_ref()
+            .map(|bs| bs.len() as u64)
+            .unwrap_or_default()
+    }
 }
@@ -146,6 +146,7 @@ pub struct RangeWriter<W: RangeWrite> {
     next_offset: u64,
     buffer: Option<oio::ChunkedBytes>,
     futures: ConcurrentFutures<WriteRangeFuture>,
+    written: u64,
 
     w: Arc<W>,
     state: State,
@@ -179,6 +180,7 @@ impl<W: RangeWrite> RangeWriter<W> {
             buffer: None,
             location: None,
             next_offset: 0,
+            written: 0,
         }
     }
 
@@ -187,6 +189,7 @@ impl<W: RangeWrite> RangeWriter<W> {
         let bs = oio::ChunkedBytes::from_vec(bs.vectored_bytes(size));
         assert!(self.buffer.is_none());
         self.buffer = Some(bs);
+        self.written += size as u64;
         size
     }
 }
@@ -350,6 +353,10 @@ impl<W: RangeWrite> oio::Write for RangeWriter<W> {
             }
         }
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.written
+    }
 }
 
 #[cfg(test)]
@@ -363,6 +370,7 @@ mod tests {
     use rand::RngCore;
 
     use super::*;
+    use crate::raw::oio::Write;
     use crate::raw::oio::WriteExt;
 
     struct TestWrite {
@@ -476,5 +484,7 @@ mod tests {
 
         let actual_size = w.w.lock().unwrap().length;
         assert_eq!(actual_size, total_size);
+
+        assert_eq!(w.written_bytes(), total_size);
     }
 }
@@ -35,6 +35,28 @@ pub enum Operation {
     Copy,
     /// Operation for [`crate::raw::Accessor::rename`]
     Rename,
+    /// Operation for [`crate::raw::Accessor::hard_link`]
+    HardLink,
+    /// Operation for [`crate::raw::Accessor::symlink`]
+    Symlink,
+    /// Operation for [`crate::raw::Accessor::set_permissions`]
+    SetPermissions,
+    /// Operation for [`crate::raw::Accessor::statvfs`]
+    Statvfs,
+    /// Operation for [`crate::raw::Accessor::truncate`]
+    Truncate,
+    /// Operation for [`crate::raw::Accessor::fallocate`]
+    Fallocate,
+    /// Operation for [`crate::raw::Accessor::rename_noreplace`]
+    RenameNoreplace,
+    /// Operation for [`crate::raw::Accessor::setxattr`]
+    Setxattr,
+    /// Operation for [`crate::raw::Accessor::getxattr`]
+    Getxattr,
+    /// Operation for [`crate::raw::Accessor::listxattr`]
+    Listxattr,
+    /// Operation for [`crate::raw::Accessor::list_buckets`]
+    ListBuckets,
     /// Operation for [`crate::raw::Accessor::stat`]
     Stat,
     /// Operation for [`crate::raw::Accessor::delete`]
@@ -85,6 +107,17 @@ impl From<Operation> for &'static str {
             Operation::Write => "write",
             Operation::Copy => "copy",
             Operation::Rename => "rename",
+            Operation::HardLink => "hard_link",
+            Operation::Symlink => "symlink",
+            Operation::SetPermissions => "set_permissions",
+            Operation::Statvfs => "statvfs",
+            Operation::Truncate => "truncate",
+            Operation::Fallocate => "fallocate",
+            Operation::RenameNoreplace => "rename_noreplace",
+            Operation::Setxattr => "setxattr",
+            Operation::Getxattr => "getxattr",
+            Operation::Listxattr => "listxattr",
+            Operation::ListBuckets => "list_buckets",
             Operation::Stat => "stat",
             Operation::Delete => "delete",
             Operation::List => "list",
@@ -19,8 +19,10 @@
 //!
 //! By using ops, users can add more context for operation.
 
+use std::collections::HashMap;
 use std::time::Duration;
 
+use bytes::Bytes;
 use flagset::FlagSet;
 
 use crate::raw::*;
@@ -85,6 +87,10 @@ pub struct OpList {
     ///
     /// Default to `false`.
     recursive: bool,
+    /// The max_depth is used to limit how deep a recursive list could go.
+    ///
+    /// Default to `None` which means unlimited.
+    max_depth: Option<usize>,
     /// Metakey is used to control which meta should be returned.
     ///
     /// Lister will make sure the result for specified meta is **known**:
@@ -99,6 +105,10 @@ pub struct OpList {
     /// - If this is set to > 1, the list operation will be concurrent,
     ///   and the maximum number of concurrent operations will be determined by this value.
     concurrent: usize,
+    /// The skip_hidden is used to skip entries whose name starts with `.`.
+    ///
+    /// Default to `false`.
+    skip_hidden: bool,
 }
 
 impl Default for OpList {
@@ -107,9 +117,11 @@ impl Default for OpList {
             limit: None,
             start_after: None,
             recursive: false,
+            max_depth: None,
             // By default, we want to know what's the mode of this entry.
             metakey: Metakey::Mode.into(),
             concurrent: 1,
+            skip_hidden: false,
         }
     }
 }
@@ -158,6 +170,23 @@ impl OpList {
         self.recursive
     }
 
+    /// Change the max_depth of recursive list.
+    ///
+    /// - `0` means no entries will be listed.
+    /// - `1` means only the entries under the given path, like non-recursive list.
+    /// - `n` means entries at most `n` levels under the given path.
+    ///
+    /// Dirs at the max depth will be returned without being listed.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Get the max_depth of recursive list.
+    pub fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
     /// Change the metakey of this list operation.
     ///
     /// The default metakey is `Metakey::Mode`.
@@ -183,6 +212,20 @@ impl OpList {
     pub fn concurrent(&self) -> usize {
         self.concurrent
     }
+
+    /// Change the skip_hidden of this list operation.
+    ///
+    /// If `true`, entries whose name starts with `.` will be skipped,
+    /// and hidden dirs will not be listed while listing recursively.
+    pub fn with_skip_hidden(mut self, skip_hidden: bool) -> Self {
+        self.skip_hidden = skip_hidden;
+        self
+    }
+
+    /// Get the skip_hidden of list operation.
+    pub fn skip_hidden(&self) -> bool {
+        self.skip_hidden
+    }
 }
 
 /// Args for `presign` operation.
@@ -310,6 +353,7 @@ pub struct OpRead {
     /// The maximum buffer capability.
     /// `None` stand for disable buffer.
     buffer: Option<usize>,
+    checksum: bool,
 }
 
 impl OpRead {
@@ -413,6 +457,22 @@ impl OpRead {
         self.version.as_deref()
     }
 
+    /// Set whether to verify the content md5 while reading.
+    ///
+    /// Reader will return `DataIntegrityCheckFailed` error at the end of the
+    /// content if it can't be verified, for example its md5 doesn't match or
+    /// the file has been changed while reading. Only whole file reads without
+    /// seek will be verified.
+    pub fn with_checksum(mut self, checksum: bool) -> Self {
+        self.checksum = checksum;
+        self
+    }
+
+    /// Get checksum from option
+    pub fn checksum(&self) -> bool {
+        self.checksum
+    }
+
     /// Set the buffer capability.
     pub fn with_buffer(mut self, buffer: usize) -> Self {
         self.buffer = Some(buffer);
@@ -435,6 +495,7 @@ pub struct OpStat {
     override_cache_control: Option<String>,
     override_content_disposition: Option<String>,
     version: Option<String>,
+    checksum: bool,
 }
 
 impl OpStat {
@@ -509,18 +570,36 @@ impl OpStat {
     pub fn version(&self) -> Option<&str> {
         self.version.as_deref()
     }
+
+    /// Set whether to calculate the content md5 of the file.
+    ///
+    /// Services that can't get the content md5 for free (like fs) will read
+    /// the whole file to calculate it, so it's disabled by default.
+    pub fn with_checksum(mut self, checksum: bool) -> Self {
+        self.checksum = checksum;
+        self
+    }
+
+    /// Get checksum from option
+    pub fn checksum(&self) -> bool {
+        self.checksum
+    }
 }
 
 /// Args for `write` operation.
 #[derive(Debug, Clone, Default)]
 pub struct OpWrite {
     append: bool,
+    if_not_exists: bool,
     buffer: Option<usize>,
     concurrent: usize,
 
     content_type: Option<String>,
     content_disposition: Option<String>,
     cache_control: Option<String>,
+    content_md5: Option<String>,
+    custom_metadata: HashMap<String, String>,
+    acl: Option<String>,
 }
 
 impl OpWrite {
@@ -550,6 +629,27 @@ impl OpWrite {
         self
     }
 
+    /// Get the if_not_exists from op.
+    ///
+    /// The if_not_exists is the flag to indicate that this write operation should
+    /// only succeed if the path doesn't exist.
+    pub fn if_not_exists(&self) -> bool {
+        self.if_not_exists
+    }
+
+    /// Set the if_not_exists of op.
+    ///
+    /// If the if_not_exists is set, the write will fail with `AlreadyExists`
+    /// if the path already exists.
+    ///
+    /// # Notes
+    ///
+    /// Service could return `Unsupported` if the underlying storage does not support it.
+    pub fn with_if_not_exists(mut self, if_not_exists: bool) -> Self {
+        self.if_not_exists = if_not_exists;
+        self
+    }
+
     /// Get the buffer from op.
     ///
     /// The buffer is used by service to decide the buffer size of the underlying writer.
@@ -603,6 +703,45 @@ impl OpWrite {
         self
     }
 
+    /// Get the content md5 from option
+    pub fn content_md5(&self) -> Option<&str> {
+        self.content_md5.as_deref()
+    }
+
+    /// Set the content md5 (base64 encoded) of option.
+    ///
+    /// Services will verify the written content against it.
+    pub fn with_content_md5(mut self, content_md5: &str) -> Self {
+        self.content_md5 = Some(content_md5.to_string());
+        self
+    }
+
+    /// Get the user defined metadata from option
+    pub fn custom_metadata(&self) -> &HashMap<String, String> {
+        &self.custom_metadata
+    }
+
+    /// Set the user defined metadata of option.
+    ///
+    /// Services will store them alongside the object.
+    pub fn with_custom_metadata(mut self, custom_metadata: HashMap<String, String>) -> Self {
+        self.custom_metadata = custom_metadata;
+        self
+    }
+
+    /// Get the acl from option
+    pub fn acl(&self) -> Option<&str> {
+        self.acl.as_deref()
+    }
+
+    /// Set the acl of option.
+    ///
+    /// The acl is a predefined ACL of services like `private` or `publicRead`.
+    pub fn with_acl(mut self, acl: &str) -> Self {
+        self.acl = Some(acl.to_string());
+        self
+    }
+
     /// Get the concurrent.
     pub fn concurrent(&self) -> usize {
         self.concurrent
@@ -636,3 +775,179 @@ impl OpRename {
         Self::default()
     }
 }
+
+/// Args for `hard_link` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpHardLink {}
+
+impl OpHardLink {
+    /// Create a new `OpHardLink`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Args for `symlink` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpSymlink {}
+
+impl OpSymlink {
+    /// Create a new `OpSymlink`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Args for `set_permissions` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpSetPermissions {
+    mode: u32,
+}
+
+impl OpSetPermissions {
+    /// Create a new `OpSetPermissions` with unix permission mode like `0o644`.
+    pub fn new(mode: u32) -> Self {
+        Self { mode }
+    }
+
+    /// Get the unix permission mode of this operation.
+    pub fn mode(&self) -> u32 {
+        self.mode
+    }
+}
+
+/// Args for `statvfs` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpStatvfs {}
+
+impl OpStatvfs {
+    /// Create a new `OpStatvfs`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Args for `truncate` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpTruncate {
+    size: u64,
+}
+
+impl OpTruncate {
+    /// Create a new `OpTruncate` with the new size of file.
+    pub fn new(size: u64) -> Self {
+        Self { size }
+    }
+
+    /// Get the new size of file.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+/// Args for `fallocate` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpFallocate {
+    size: u64,
+}
+
+impl OpFallocate {
+    /// Create a new `OpFallocate` with the size to pre-allocate.
+    pub fn new(size: u64) -> Self {
+        Self { size }
+    }
+
+    /// Get the size to pre-allocate.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+/// Args for `rename_noreplace` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpRenameNoreplace {}
+
+impl OpRenameNoreplace {
+    /// Create a new `OpRenameNoreplace`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Args for `setxattr` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpSetxattr {
+    name: String,
+    value: Bytes,
+}
+
+impl OpSetxattr {
+    /// Create a new `OpSetxattr` with the name and value of attribute.
+    pub fn new(name: &str, value: Bytes) -> Self {
+        Self {
+            name: name.to_string(),
+            value,
+        }
+    }
+
+    /// Get the name of attribute.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the value of attribute.
+    pub fn value(&self) -> &Bytes {
+        &self.value
+    }
+}
+
+/// Args for `getxattr` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpGetxattr {
+    name: String,
+}
+
+impl OpGetxattr {
+    /// Create a new `OpGetxattr` with the name of attribute.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+        }
+    }
+
+    /// Get the name of attribute.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Args for `listxattr` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpListxattr {}
+
+impl OpListxattr {
+    /// Create a new `OpListxattr`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Args for `list_buckets` operation.
+#[derive(Debug, Clone, Default)]
+pub struct OpListBuckets {
+    project: String,
+}
+
+impl OpListBuckets {
+    /// Create a new `OpListBuckets` with the project that buckets belong to.
+    pub fn new(project: &str) -> Self {
+        Self {
+            project: project.to_string(),
+        }
+    }
+
+    /// Get the project that buckets belong to.
+    pub fn project(&self) -> &str {
+        &self.project
+    }
+}
@@ -215,7 +215,7 @@ pub fn validate_path(path: &str, mode: EntryMode) -> bool {
     debug_assert!(!path.is_empty(), "input path should not be empty");
 
     match mode {
-        EntryMode::FILE => !path.ends_with('/'),
+        EntryMode::FILE | EntryMode::Symlink => !path.ends_with('/'),
         EntryMode::DIR => path.ends_with('/'),
         EntryMode::Unknown => false,
     }
@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use bytes::Bytes;
 use http::Request;
 
 use crate::raw::*;
@@ -244,6 +245,155 @@ impl RpRename {
     }
 }
 
+/// Reply for `hard_link` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpHardLink {}
+
+impl RpHardLink {
+    /// Create a new reply for `hard_link`.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// Reply for `symlink` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpSymlink {}
+
+impl RpSymlink {
+    /// Create a new reply for `symlink`.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// Reply for `set_permissions` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpSetPermissions {}
+
+impl RpSetPermissions {
+    /// Create a new reply for `set_permissions`.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// Reply for `statvfs` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpStatvfs {
+    stats: FsStats,
+}
+
+impl RpStatvfs {
+    /// Create a new reply for `statvfs`.
+    pub fn new(stats: FsStats) -> Self {
+        Self { stats }
+    }
+
+    /// Consume RpStatvfs to get the inner stats.
+    pub fn into_stats(self) -> FsStats {
+        self.stats
+    }
+}
+
+/// Reply for `truncate` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpTruncate {}
+
+impl RpTruncate {
+    /// Create a new reply for `truncate`.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// Reply for `fallocate` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpFallocate {}
+
+impl RpFallocate {
+    /// Create a new reply for `fallocate`.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// Reply for `rename_noreplace` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpRenameNoreplace {}
+
+impl RpRenameNoreplace {
+    /// Create a new reply for `rename_noreplace`.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// Reply for `setxattr` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpSetxattr {}
+
+impl RpSetxattr {
+    /// Create a new reply for `setxattr`.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// Reply for `getxattr` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpGetxattr {
+    value: Bytes,
+}
+
+impl RpGetxattr {
+    /// Create a new reply for `getxattr`.
+    pub fn new(value: Bytes) -> Self {
+        Self { value }
+    }
+
+    /// Consume reply to get the value of attribute.
+    pub fn into_value(self) -> Bytes {
+        self.value
+    }
+}
+
+/// Reply for `listxattr` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpListxattr {
+    names: Vec<String>,
+}
+
+impl RpListxattr {
+    /// Create a new reply for `listxattr`.
+    pub fn new(names: Vec<String>) -> Self {
+        Self { names }
+    }
+
+    /// Consume reply to get the names of attributes.
+    pub fn into_names(self) -> Vec<String> {
+        self.names
+    }
+}
+
+/// Reply for `list_buckets` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpListBuckets {
+    buckets: Vec<String>,
+}
+
+impl RpListBuckets {
+    /// Create a new reply for `list_buckets`.
+    pub fn new(buckets: Vec<String>) -> Self {
+        Self { buckets }
+    }
+
+    /// Consume reply to get the names of buckets.
+    pub fn into_buckets(self) -> Vec<String> {
+        self.buckets
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
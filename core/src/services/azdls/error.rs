@@ -73,6 +73,7 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
         | StatusCode::BAD_GATEWAY
         | StatusCode::SERVICE_UNAVAILABLE
         | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
+        StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
         _ => (ErrorKind::Unexpected, false),
     };
 
@@ -55,6 +55,7 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
         // COS could return `520 Origin Error` errors which should be retried.
         v if v.as_u16() == 520 => (ErrorKind::Unexpected, true),
 
+        StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
         _ => (ErrorKind::Unexpected, false),
     };
 
@@ -18,14 +18,31 @@
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use async_trait::async_trait;
+use base64::engine::general_purpose;
+use base64::Engine;
+use bytes::Bytes;
 use chrono::DateTime;
+use futures::StreamExt;
 use log::debug;
+use md5::Digest;
+use tokio::io::AsyncReadExt;
+use tokio::sync::OwnedSemaphorePermit;
+use tokio::sync::Semaphore;
 use uuid::Uuid;
 
+use super::framed::FramedReader;
+use super::framed::FramedWriter;
 use super::lister::FsLister;
+use super::reader;
+use super::reader::FsReader;
+use super::reader::MmapReader;
+use super::watcher;
+use super::watcher::FsWatcher;
 use super::writer::FsWriter;
+use super::xattr;
 use crate::raw::*;
 use crate::*;
 
@@ -35,6 +52,15 @@ use crate::*;
 pub struct FsBuilder {
     root: Option<PathBuf>,
     atomic_write_dir: Option<PathBuf>,
+    follow_symlinks: Option<bool>,
+    max_open_files: Option<usize>,
+    create_root: Option<bool>,
+    sort_list: Option<bool>,
+    direct_io: Option<bool>,
+    umask: Option<u32>,
+    mmap_threshold: Option<u64>,
+    chunk_size: Option<usize>,
+    verify_on_close: Option<bool>,
 }
 
 impl FsBuilder {
@@ -54,7 +80,9 @@ impl FsBuilder {
     /// # Notes
     ///
     /// - When append is enabled, we will not use atomic write
-    /// to avoid data loss and performance issue.
+    ///   to avoid data loss and performance issue.
+    /// - The dir must be on the same file system as root, otherwise `build`
+    ///   will return `ConfigInvalid` error.
     pub fn atomic_write_dir(&mut self, dir: &str) -> &mut Self {
         self.atomic_write_dir = if dir.is_empty() {
             None
@@ -65,6 +93,151 @@ impl FsBuilder {
         self
     }
 
+    /// Set whether fs should follow symlinks or not.
+    ///
+    /// Default to `true`.
+    ///
+    /// # Notes
+    ///
+    /// When disabled, `stat` will return the metadata of the symlink itself
+    /// with [`EntryMode::Symlink`] and `read` will refuse to open symlinks.
+    /// `list` always returns symlinks as [`EntryMode::Symlink`].
+    pub fn follow_symlinks(&mut self, follow_symlinks: bool) -> &mut Self {
+        self.follow_symlinks = Some(follow_symlinks);
+
+        self
+    }
+
+    /// Set the max number of files that can be opened by `read` and `write`
+    /// at the same time.
+    ///
+    /// Default to unlimited.
+    ///
+    /// # Notes
+    ///
+    /// The permit is held until the returned reader or writer has been dropped.
+    /// Async operations will wait for a permit, while blocking operations will
+    /// return a temporary `RateLimited` error if no permit is available.
+    pub fn max_open_files(&mut self, max_open_files: usize) -> &mut Self {
+        self.max_open_files = Some(max_open_files);
+
+        self
+    }
+
+    /// Set whether fs should create the root dir if it doesn't exist.
+    ///
+    /// Default to `true`.
+    ///
+    /// When disabled, `build` will return `NotFound` error if root doesn't
+    /// exist, which is useful to detect misconfigured root.
+    pub fn create_root(&mut self, create_root: bool) -> &mut Self {
+        self.create_root = Some(create_root);
+
+        self
+    }
+
+    /// Set whether fs should return listed entries in lexicographic order.
+    ///
+    /// Default to `false`.
+    ///
+    /// # Notes
+    ///
+    /// The order of entries returned by the file system is not defined and
+    /// may differ across runs and platforms. When enabled, all entries of
+    /// the dir will be collected and sorted by path before yielding, which
+    /// costs extra memory for large dirs.
+    pub fn sort_list(&mut self, sort_list: bool) -> &mut Self {
+        self.sort_list = Some(sort_list);
+
+        self
+    }
+
+    /// Set whether fs should open files for write with `O_DIRECT`.
+    ///
+    /// Default to `false`. Only supported on linux, `build` will return
+    /// `ConfigInvalid` error on other platforms.
+    ///
+    /// # Notes
+    ///
+    /// Direct IO bypasses the page cache, so the kernel requires both the
+    /// address and the length of every write buffer to be aligned to the
+    /// logical sector size of the device (typically 512 or 4096 bytes).
+    /// fs checks that buffers are aligned to 512 bytes and returns
+    /// `InvalidInput` error otherwise; devices with 4096 bytes sectors
+    /// still require users to align their buffers to 4096.
+    pub fn direct_io(&mut self, direct_io: bool) -> &mut Self {
+        self.direct_io = Some(direct_io);
+
+        self
+    }
+
+    /// Set the umask of files created by `write`, for example `0o077`.
+    ///
+    /// Default to use the umask of current process only. Only supported on
+    /// unix, `build` will return `ConfigInvalid` error on other platforms.
+    ///
+    /// # Notes
+    ///
+    /// The umask applies to both the temp file of atomic write and the target
+    /// file. It is applied per file on top of the umask of current process, so
+    /// it can only make created files more restrictive.
+    pub fn umask(&mut self, umask: u32) -> &mut Self {
+        self.umask = Some(umask);
+
+        self
+    }
+
+    /// Set the size threshold in bytes above which `read` will serve the
+    /// file from a memory map instead of file reads.
+    ///
+    /// Default to disabled.
+    ///
+    /// # Notes
+    ///
+    /// Memory map avoids a syscall for every read, which is faster for
+    /// large files that are read sequentially or repeatedly and are likely
+    /// to be in page cache. File reads are preferable for small files and
+    /// for files on network file systems, where page faults are expensive.
+    ///
+    /// Files MUST NOT be truncated by others while they are mapped, otherwise
+    /// the process could be killed by `SIGBUS`.
+    pub fn mmap_threshold(&mut self, threshold: u64) -> &mut Self {
+        self.mmap_threshold = Some(threshold);
+
+        self
+    }
+
+    /// Set the chunk size in bytes that `read` will fetch from files at once.
+    ///
+    /// Default to 64 KiB.
+    ///
+    /// # Notes
+    ///
+    /// Larger chunk size like 1 MiB reduces the number of syscalls for
+    /// workloads reading large contiguous files, at the cost of memory
+    /// for every opened reader. Reads served from memory map are not affected.
+    pub fn chunk_size(&mut self, size: usize) -> &mut Self {
+        self.chunk_size = Some(size);
+
+        self
+    }
+
+    /// Set whether to verify written files after `close`.
+    ///
+    /// Default to `false`.
+    ///
+    /// # Notes
+    ///
+    /// Writer will calculate the md5 of written content, then re-read the
+    /// whole file after it has been synced and return `DataIntegrityCheckFailed`
+    /// error if they don't match. This doubles the IO of writes. Appending
+    /// to an existing file is not verified since it contains previous content.
+    pub fn verify_on_close(&mut self, verify_on_close: bool) -> &mut Self {
+        self.verify_on_close = Some(verify_on_close);
+
+        self
+    }
+
     /// OpenDAL requires all input path are normalized to make sure the
     /// behavior is consistent. By enable path check, we can make sure
     /// fs will behave the same as other services.
@@ -87,6 +260,24 @@ impl Builder for FsBuilder {
         map.get("root").map(|v| builder.root(v));
         map.get("atomic_write_dir")
             .map(|v| builder.atomic_write_dir(v));
+        map.get("follow_symlinks")
+            .map(|v| builder.follow_symlinks(v == "on" || v == "true"));
+        map.get("max_open_files")
+            .map(|v| v.parse::<usize>().map(|v| builder.max_open_files(v)));
+        map.get("create_root")
+            .map(|v| builder.create_root(v == "on" || v == "true"));
+        map.get("sort_list")
+            .map(|v| builder.sort_list(v == "on" || v == "true"));
+        map.get("direct_io")
+            .map(|v| builder.direct_io(v == "on" || v == "true"));
+        map.get("umask")
+            .map(|v| u32::from_str_radix(v, 8).map(|v| builder.umask(v)));
+        map.get("mmap_threshold")
+            .map(|v| v.parse::<u64>().map(|v| builder.mmap_threshold(v)));
+        map.get("chunk_size")
+            .map(|v| v.parse::<usize>().map(|v| builder.chunk_size(v)));
+        map.get("verify_on_close")
+            .map(|v| builder.verify_on_close(v == "on" || v == "true"));
 
         builder
     }
@@ -103,9 +294,56 @@ impl Builder for FsBuilder {
         }?;
         debug!("backend use root {}", root.to_string_lossy());
 
-        // If root dir is not exist, we must create it.
+        let direct_io = self.direct_io.unwrap_or_default();
+        if direct_io && !cfg!(target_os = "linux") {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "direct_io is only supported on linux",
+            )
+            .with_operation("Builder::build"));
+        }
+
+        if self.max_open_files == Some(0) {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "max_open_files must be greater than 0",
+            )
+            .with_operation("Builder::build"));
+        }
+
+        if let Some(umask) = self.umask {
+            if !cfg!(unix) {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "umask is only supported on unix",
+                )
+                .with_operation("Builder::build"));
+            }
+            if umask > 0o777 {
+                return Err(Error::new(ErrorKind::ConfigInvalid, "umask is invalid")
+                    .with_operation("Builder::build")
+                    .with_context("umask", format!("{umask:o}")));
+            }
+        }
+
+        if self.chunk_size == Some(0) {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "chunk_size must be greater than 0",
+            )
+            .with_operation("Builder::build"));
+        }
+
+        // If root dir is not exist, we must create it unless create_root is disabled.
         if let Err(e) = std::fs::metadata(&root) {
             if e.kind() == std::io::ErrorKind::NotFound {
+                if !self.create_root.unwrap_or(true) {
+                    return Err(Error::new(ErrorKind::NotFound, "root dir is not exist")
+                        .with_operation("Builder::build")
+                        .with_context("root", root.to_string_lossy())
+                        .set_source(e));
+                }
+
                 std::fs::create_dir_all(&root).map_err(|e| {
                     Error::new(ErrorKind::Unexpected, "create root dir failed")
                         .with_operation("Builder::build")
@@ -159,10 +397,32 @@ impl Builder for FsBuilder {
             })
             .unwrap_or(Ok(None))?;
 
+        // Atomic write renames the temp file into root, which fails with
+        // `EXDEV` if they are on different file systems.
+        if let Some(d) = &atomic_write_dir {
+            if !is_same_file_system(&root, d)? {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "atomic_write_dir must be on the same file system as root",
+                )
+                .with_operation("Builder::build")
+                .with_context("root", root.to_string_lossy())
+                .with_context("atomic_write_dir", d.to_string_lossy()));
+            }
+        }
+
         debug!("backend build finished: {:?}", &self);
         Ok(FsBackend {
             root,
             atomic_write_dir,
+            follow_symlinks: self.follow_symlinks.unwrap_or(true),
+            open_files: self.max_open_files.map(|v| Arc::new(Semaphore::new(v))),
+            sort_list: self.sort_list.unwrap_or_default(),
+            direct_io,
+            umask: self.umask,
+            mmap_threshold: self.mmap_threshold,
+            chunk_size: self.chunk_size,
+            verify_on_close: self.verify_on_close.unwrap_or_default(),
         })
     }
 }
@@ -172,8 +432,281 @@ impl Builder for FsBuilder {
 pub struct FsBackend {
     root: PathBuf,
     atomic_write_dir: Option<PathBuf>,
+    follow_symlinks: bool,
+    open_files: Option<Arc<Semaphore>>,
+    sort_list: bool,
+    direct_io: bool,
+    umask: Option<u32>,
+    mmap_threshold: Option<u64>,
+    chunk_size: Option<usize>,
+    verify_on_close: bool,
+}
+
+/// Check if given dirs are on the same file system.
+#[cfg(unix)]
+fn is_same_file_system(a: &Path, b: &Path) -> Result<bool> {
+    use std::os::unix::fs::MetadataExt;
+
+    let a = std::fs::metadata(a).map_err(new_std_io_error)?;
+    let b = std::fs::metadata(b).map_err(new_std_io_error)?;
+    Ok(a.dev() == b.dev())
+}
+
+/// Check if given dirs are on the same file system.
+///
+/// We only compare the drive (prefix) of the canonicalized paths on windows.
+#[cfg(windows)]
+fn is_same_file_system(a: &Path, b: &Path) -> Result<bool> {
+    use std::path::Component;
+
+    let prefix = |p: &Path| match p.components().next() {
+        Some(Component::Prefix(v)) => Some(v.as_os_str().to_ascii_lowercase()),
+        _ => None,
+    };
+    Ok(prefix(a) == prefix(b))
+}
+
+/// Check if given dirs are on the same file system.
+#[cfg(not(any(unix, windows)))]
+fn is_same_file_system(_: &Path, _: &Path) -> Result<bool> {
+    Ok(true)
+}
+
+/// Create a symlink at `link` which points to `target`.
+#[cfg(unix)]
+async fn create_symlink(target: &str, link: &Path) -> Result<()> {
+    tokio::fs::symlink(target, link)
+        .await
+        .map_err(new_std_io_error)
+}
+
+/// Create a symlink at `link` which points to `target`.
+///
+/// Only file symlinks are supported on windows.
+#[cfg(windows)]
+async fn create_symlink(target: &str, link: &Path) -> Result<()> {
+    tokio::fs::symlink_file(target, link)
+        .await
+        .map_err(new_std_io_error)
+}
+
+/// Create a symlink at `link` which points to `target`.
+#[cfg(not(any(unix, windows)))]
+async fn create_symlink(_: &str, _: &Path) -> Result<()> {
+    Err(Error::new(
+        ErrorKind::Unsupported,
+        "symlink is not supported on this platform",
+    ))
+}
+
+/// Set the unix permission mode of given path.
+#[cfg(unix)]
+async fn set_permissions(p: &Path, mode: u32) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    tokio::fs::set_permissions(p, std::fs::Permissions::from_mode(mode))
+        .await
+        .map_err(new_std_io_error)
+}
+
+/// Set the unix permission mode of given path.
+#[cfg(not(unix))]
+async fn set_permissions(_: &Path, _: u32) -> Result<()> {
+    Err(Error::new(
+        ErrorKind::Unsupported,
+        "set permissions is not supported on this platform",
+    ))
+}
+
+/// Fetch the stats of the file system that contains given path.
+#[cfg(unix)]
+#[allow(clippy::unnecessary_cast)]
+fn statvfs(p: &Path) -> Result<FsStats> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let cpath = CString::new(p.as_os_str().as_bytes()).map_err(|err| {
+        Error::new(ErrorKind::InvalidInput, "path contains nul byte").set_source(err)
+    })?;
+
+    // Safety: cpath is a valid nul terminated string and buf is only read
+    // after statvfs returns successfully.
+    let buf = unsafe {
+        let mut buf: libc::statvfs = std::mem::zeroed();
+        if libc::statvfs(cpath.as_ptr(), &mut buf) != 0 {
+            return Err(new_std_io_error(std::io::Error::last_os_error()));
+        }
+        buf
+    };
+
+    Ok(FsStats {
+        block_size: buf.f_frsize as u64,
+        blocks_total: buf.f_blocks as u64,
+        blocks_free: buf.f_bfree as u64,
+        blocks_available: buf.f_bavail as u64,
+        inode_total: buf.f_files as u64,
+        inode_free: buf.f_ffree as u64,
+    })
+}
+
+/// Fetch the stats of the file system that contains given path.
+#[cfg(not(unix))]
+fn statvfs(_: &Path) -> Result<FsStats> {
+    Err(Error::new(
+        ErrorKind::Unsupported,
+        "statvfs is not supported on this platform",
+    ))
+}
+
+/// Allocate disk space of `len` bytes for given file without changing its size.
+#[cfg(target_os = "linux")]
+fn fallocate(f: &tokio::fs::File, len: u64) -> Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    if len == 0 {
+        return Ok(());
+    }
+
+    // Safety: the fd is owned by `f` which outlives this call.
+    let ret = unsafe {
+        libc::fallocate(
+            f.as_raw_fd(),
+            libc::FALLOC_FL_KEEP_SIZE,
+            0,
+            len as libc::off_t,
+        )
+    };
+    if ret != 0 {
+        return Err(new_std_io_error(std::io::Error::last_os_error()));
+    }
+
+    Ok(())
 }
 
+/// Allocate disk space of `len` bytes for given file without changing its size.
+///
+/// Contiguous space is preferred, and falls back to any space if not available.
+#[cfg(target_os = "macos")]
+fn fallocate(f: &tokio::fs::File, len: u64) -> Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    if len == 0 {
+        return Ok(());
+    }
+
+    let mut store = libc::fstore_t {
+        fst_flags: libc::F_ALLOCATECONTIG | libc::F_ALLOCATEALL,
+        fst_posmode: libc::F_PEOFPOSMODE,
+        fst_offset: 0,
+        fst_length: len as libc::off_t,
+        fst_bytesalloc: 0,
+    };
+    // Safety: the fd is owned by `f` which outlives this call, and store
+    // is a valid fstore_t.
+    unsafe {
+        if libc::fcntl(f.as_raw_fd(), libc::F_PREALLOCATE, &store) == -1 {
+            store.fst_flags = libc::F_ALLOCATEALL;
+            if libc::fcntl(f.as_raw_fd(), libc::F_PREALLOCATE, &store) == -1 {
+                return Err(new_std_io_error(std::io::Error::last_os_error()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Allocate disk space of `len` bytes for given file without changing its size.
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn fallocate(_: &tokio::fs::File, _: u64) -> Result<()> {
+    Err(Error::new(
+        ErrorKind::Unsupported,
+        "fallocate is not supported on this platform",
+    ))
+}
+
+/// Rename `from` to `to` atomically, fails with `AlreadyExists` if `to` exists.
+pub(super) async fn rename_noreplace(from: &Path, to: &Path) -> Result<()> {
+    // renameat2 could block on slow file systems like other fs calls, run
+    // it in the blocking thread pool.
+    let (from, to) = (from.to_path_buf(), to.to_path_buf());
+    tokio::task::spawn_blocking(move || blocking_rename_noreplace(&from, &to))
+        .await
+        .map_err(new_task_join_error)?
+}
+
+/// Rename `from` to `to` atomically, fails with `AlreadyExists` if `to` exists.
+///
+/// `renameat2` with `RENAME_NOREPLACE` is used if the file system supports it,
+/// otherwise falls back to `link` + `unlink`.
+#[cfg(target_os = "linux")]
+pub(super) fn blocking_rename_noreplace(from: &Path, to: &Path) -> Result<()> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let (cfrom, cto) = match (
+        CString::new(from.as_os_str().as_bytes()),
+        CString::new(to.as_os_str().as_bytes()),
+    ) {
+        (Ok(cfrom), Ok(cto)) => (cfrom, cto),
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "path contains nul byte",
+            ))
+        }
+    };
+
+    // Safety: both paths are valid nul terminated strings.
+    let ret = unsafe {
+        libc::syscall(
+            libc::SYS_renameat2,
+            libc::AT_FDCWD,
+            cfrom.as_ptr(),
+            libc::AT_FDCWD,
+            cto.as_ptr(),
+            libc::RENAME_NOREPLACE,
+        )
+    };
+    if ret == 0 {
+        return Ok(());
+    }
+
+    let err = std::io::Error::last_os_error();
+    match err.raw_os_error() {
+        // The kernel or file system doesn't support RENAME_NOREPLACE.
+        Some(libc::EINVAL) | Some(libc::ENOSYS) => link_and_unlink(from, to),
+        _ => Err(new_std_io_error(err)),
+    }
+}
+
+/// Rename `from` to `to` atomically, fails with `AlreadyExists` if `to` exists.
+#[cfg(not(target_os = "linux"))]
+pub(super) fn blocking_rename_noreplace(from: &Path, to: &Path) -> Result<()> {
+    link_and_unlink(from, to)
+}
+
+/// Emulate rename without replacing via `link` + `unlink`.
+///
+/// `link` fails if `to` exists, so only one of concurrent renames could
+/// succeed. `from` will be left in place if `unlink` fails. Dirs can't be
+/// hard linked, so they return `Unsupported` error.
+fn link_and_unlink(from: &Path, to: &Path) -> Result<()> {
+    let meta = std::fs::symlink_metadata(from).map_err(new_std_io_error)?;
+    if meta.is_dir() {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "rename_noreplace of dir is not supported on this file system",
+        )
+        .with_context("from", from.to_string_lossy()));
+    }
+
+    std::fs::hard_link(from, to).map_err(new_std_io_error)?;
+    std::fs::remove_file(from).map_err(new_std_io_error)
+}
+
+/// The max number of concurrent operations in a batch.
+const BATCH_CONCURRENT_LIMIT: usize = 8;
+
 #[inline]
 fn tmp_file_of(path: &str) -> String {
     let name = get_basename(path);
@@ -183,6 +716,230 @@ fn tmp_file_of(path: &str) -> String {
 }
 
 impl FsBackend {
+    /// Watch the file system events of given path.
+    ///
+    /// Watching a dir reports the events of its direct children while
+    /// watching a file reports the events of itself. Events are read via
+    /// inotify on linux and kqueue on macos, other platforms will return
+    /// `Unsupported` error.
+    ///
+    /// This function must be called within a tokio runtime.
+    pub async fn watch(&self, path: &str) -> Result<FsWatcher> {
+        Self::ensure_within_root(path)?;
+        let p = self.root.join(path.trim_end_matches('/'));
+        let meta = tokio::fs::metadata(&p).await.map_err(new_std_io_error)?;
+
+        watcher::watch(&p, path, meta.is_dir()).await
+    }
+
+    /// Read at most `len` bytes at `offset` of given path via `pread`.
+    ///
+    /// No reader is created and no position is shared, so multiple range
+    /// reads can be served concurrently.
+    pub async fn read_at(&self, path: &str, offset: u64, len: usize) -> Result<Bytes> {
+        Self::ensure_within_root(path)?;
+        let p = self.root.join(path.trim_end_matches('/'));
+
+        if !self.follow_symlinks {
+            let meta = tokio::fs::symlink_metadata(&p)
+                .await
+                .map_err(new_std_io_error)?;
+            Self::ensure_not_symlink(&meta, &p)?;
+        }
+
+        let _permit = self.acquire_open_file().await;
+        let f = tokio::fs::File::open(&p).await.map_err(new_std_io_error)?;
+        reader::pread(Arc::new(f.into_std().await), offset, len).await
+    }
+
+    /// Create a writer that writes every payload as a length prefixed and
+    /// CRC-32C checksummed frame into given path.
+    ///
+    /// Existing file will be overwritten. Frames will be visible atomically
+    /// on close if `atomic_write_dir` is set. Refer to [`FramedWriter`] for
+    /// the frame format.
+    pub async fn framed_write(
+        &self,
+        path: &str,
+    ) -> Result<FramedWriter<FsWriter<tokio::fs::File>>> {
+        let (_, w) = self.write(path, OpWrite::new()).await?;
+
+        Ok(FramedWriter::new(w))
+    }
+
+    /// Create a reader that reads frames written by `framed_write` from
+    /// given path and verifies their checksum.
+    pub async fn framed_read(
+        &self,
+        path: &str,
+    ) -> Result<FramedReader<<Self as Accessor>::Reader>> {
+        let (_, r) = self.read(path, OpRead::new()).await?;
+
+        Ok(FramedReader::new(r))
+    }
+
+    /// Get the root dir of this backend.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Get the temp dir for atomic write of this backend, if configured.
+    pub fn atomic_write_dir(&self) -> Option<&Path> {
+        self.atomic_write_dir.as_deref()
+    }
+
+    fn build_metadata(meta: &std::fs::Metadata) -> Result<Metadata> {
+        let mode = if meta.is_dir() {
+            EntryMode::DIR
+        } else if meta.is_file() {
+            EntryMode::FILE
+        } else if meta.is_symlink() {
+            EntryMode::Symlink
+        } else {
+            EntryMode::Unknown
+        };
+        let mut m = Metadata::new(mode)
+            .with_content_length(meta.len())
+            .with_last_modified(
+                meta.modified()
+                    .map(DateTime::from)
+                    .map_err(new_std_io_error)?,
+            );
+        // Access time could be unavailable on some platforms.
+        if let Ok(atime) = meta.accessed() {
+            m.set_atime(atime.into());
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            use std::os::unix::fs::PermissionsExt;
+
+            // Only keep the permission bits, file type is exposed by mode.
+            m.set_unix_permissions(meta.permissions().mode() & 0o7777);
+            m.set_inode(meta.ino());
+            m.set_ctime(
+                parse_datetime_from_from_timestamp(meta.ctime())?
+                    + chrono::Duration::nanoseconds(meta.ctime_nsec()),
+            );
+        }
+        m.set_etag(&Self::etag_of(meta));
+
+        Ok(m)
+    }
+
+    /// Build the etag of file from its metadata instead of its content.
+    ///
+    /// The etag is the hex encoded CRC32C of `(dev, ino, mtime_ns, size)`,
+    /// so it changes when the file is modified or replaced but is stable
+    /// across reads of the same file. `dev` and `ino` are only available
+    /// on unix.
+    pub(super) fn etag_of(meta: &std::fs::Metadata) -> String {
+        #[cfg(unix)]
+        let (dev, ino) = {
+            use std::os::unix::fs::MetadataExt;
+
+            (meta.dev(), meta.ino())
+        };
+        #[cfg(not(unix))]
+        let (dev, ino) = (0u64, 0u64);
+
+        let mtime_ns = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+
+        let mut buf = Vec::with_capacity(40);
+        buf.extend_from_slice(&dev.to_le_bytes());
+        buf.extend_from_slice(&ino.to_le_bytes());
+        buf.extend_from_slice(&mtime_ns.to_le_bytes());
+        buf.extend_from_slice(&meta.len().to_le_bytes());
+
+        format!("{:08x}", crc32c::crc32c(&buf))
+    }
+
+    // Acquire a permit to open a file if max_open_files is set.
+    async fn acquire_open_file(&self) -> Option<OwnedSemaphorePermit> {
+        match &self.open_files {
+            Some(s) => Some(
+                s.clone()
+                    .acquire_owned()
+                    .await
+                    .expect("semaphore must be valid"),
+            ),
+            None => None,
+        }
+    }
+
+    // Try to acquire a permit to open a file without waiting if max_open_files is set.
+    fn blocking_acquire_open_file(&self) -> Result<Option<OwnedSemaphorePermit>> {
+        match &self.open_files {
+            Some(s) => s.clone().try_acquire_owned().map(Some).map_err(|e| {
+                Error::new(ErrorKind::RateLimited, "too many open files")
+                    .set_temporary()
+                    .set_source(e)
+            }),
+            None => Ok(None),
+        }
+    }
+
+    // Calculate the content md5 (base64 encoded) of given file.
+    pub(super) async fn content_md5_of(p: &Path) -> Result<String> {
+        let mut f = tokio::fs::File::open(p).await.map_err(new_std_io_error)?;
+
+        let mut hasher = md5::Md5::new();
+        let mut buf = vec![0; 64 * 1024];
+        loop {
+            let n = f.read(&mut buf).await.map_err(new_std_io_error)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+        }
+
+        Ok(general_purpose::STANDARD.encode(hasher.finalize()))
+    }
+
+    // Synchronously calculate the content md5 (base64 encoded) of given file.
+    pub(super) fn blocking_content_md5_of(p: &Path) -> Result<String> {
+        let mut f = std::fs::File::open(p).map_err(new_std_io_error)?;
+
+        let mut hasher = md5::Md5::new();
+        std::io::copy(&mut f, &mut hasher).map_err(new_std_io_error)?;
+
+        Ok(general_purpose::STANDARD.encode(hasher.finalize()))
+    }
+
+    // Refuse to operate on symlinks while follow_symlinks is disabled.
+    fn ensure_not_symlink(meta: &std::fs::Metadata, p: &Path) -> Result<()> {
+        if meta.is_symlink() {
+            return Err(Error::new(
+                ErrorKind::IsADirectory,
+                "path is a symlink while follow_symlinks is disabled",
+            )
+            .with_context("path", p.to_string_lossy()));
+        }
+
+        Ok(())
+    }
+
+    // Refuse paths that could escape the root via `..`.
+    fn ensure_within_root(path: &str) -> Result<()> {
+        let escaped = Path::new(path)
+            .components()
+            .any(|c| matches!(c, std::path::Component::ParentDir));
+        if escaped {
+            return Err(
+                Error::new(ErrorKind::PermissionDenied, "path must not escape the root")
+                    .with_context("path", path),
+            );
+        }
+
+        Ok(())
+    }
+
     // Synchronously build write path and ensure the parent dirs created
     fn blocking_ensure_write_abs_path(parent: &Path, path: &str) -> Result<PathBuf> {
         let p = parent.join(path);
@@ -240,10 +997,16 @@ impl FsBackend {
 
 #[async_trait]
 impl Accessor for FsBackend {
-    type Reader = oio::TokioReader<tokio::fs::File>;
+    type Reader = FsReader<
+        ThreeWays<
+            oio::TokioReader<tokio::fs::File>,
+            oio::BufferReader<oio::TokioReader<tokio::fs::File>>,
+            MmapReader,
+        >,
+    >;
     type Writer = FsWriter<tokio::fs::File>;
     type Lister = Option<FsLister<tokio::fs::ReadDir>>;
-    type BlockingReader = oio::StdReader<std::fs::File>;
+    type BlockingReader = FsReader<oio::StdReader<std::fs::File>>;
     type BlockingWriter = FsWriter<std::fs::File>;
     type BlockingLister = Option<FsLister<std::fs::ReadDir>>;
 
@@ -253,23 +1016,38 @@ impl Accessor for FsBackend {
             .set_root(&self.root.to_string_lossy())
             .set_native_capability(Capability {
                 stat: true,
+                stat_with_checksum: true,
 
                 read: true,
                 read_can_seek: true,
+                read_with_checksum: true,
 
                 write: true,
                 write_can_empty: true,
                 write_can_append: true,
                 write_can_multi: true,
+                write_with_if_not_exists: true,
                 create_dir: true,
                 delete: true,
 
                 list: true,
+                list_with_skip_hidden: true,
 
                 copy: true,
                 rename: true,
+                hard_link: true,
+                symlink: cfg!(any(unix, windows)),
+                set_permissions: cfg!(unix),
+                statvfs: cfg!(unix),
+                truncate: true,
+                fallocate: cfg!(any(target_os = "linux", target_os = "macos")),
+                rename_noreplace: true,
+                xattr: cfg!(any(target_os = "linux", target_os = "macos")),
                 blocking: true,
 
+                batch: true,
+                batch_delete: true,
+
                 ..Default::default()
             });
 
@@ -286,25 +1064,24 @@ impl Accessor for FsBackend {
         Ok(RpCreateDir::default())
     }
 
-    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         let p = self.root.join(path.trim_end_matches('/'));
 
-        let meta = tokio::fs::metadata(&p).await.map_err(new_std_io_error)?;
-
-        let mode = if meta.is_dir() {
-            EntryMode::DIR
-        } else if meta.is_file() {
-            EntryMode::FILE
+        let meta = if self.follow_symlinks {
+            tokio::fs::metadata(&p).await
         } else {
-            EntryMode::Unknown
-        };
-        let m = Metadata::new(mode)
-            .with_content_length(meta.len())
-            .with_last_modified(
-                meta.modified()
-                    .map(DateTime::from)
-                    .map_err(new_std_io_error)?,
-            );
+            tokio::fs::symlink_metadata(&p).await
+        }
+        .map_err(new_std_io_error)?;
+
+        let mut m = Self::build_metadata(&meta)?;
+        if m.is_symlink() {
+            let target = tokio::fs::read_link(&p).await.map_err(new_std_io_error)?;
+            m.set_symlink_target(&target.to_string_lossy());
+        }
+        if args.checksum() && m.is_file() {
+            m.set_content_md5(&Self::content_md5_of(&p).await?);
+        }
 
         Ok(RpStat::new(m))
     }
@@ -318,17 +1095,47 @@ impl Accessor for FsBackend {
     /// - open file first, and than use `seek`. (100ns)
     ///
     /// Benchmark could be found [here](https://gist.github.com/Xuanwo/48f9cfbc3022ea5f865388bb62e1a70f)
-    async fn read(&self, path: &str, _: OpRead) -> Result<(RpRead, Self::Reader)> {
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
         let p = self.root.join(path.trim_end_matches('/'));
 
+        if !self.follow_symlinks {
+            let meta = tokio::fs::symlink_metadata(&p)
+                .await
+                .map_err(new_std_io_error)?;
+            Self::ensure_not_symlink(&meta, &p)?;
+        }
+
+        let permit = self.acquire_open_file().await;
         let f = tokio::fs::OpenOptions::new()
             .read(true)
             .open(&p)
             .await
             .map_err(new_std_io_error)?;
+        let meta = f.metadata().await.map_err(new_std_io_error)?;
+        let size = meta.len();
+
+        let inner = match self.mmap_threshold {
+            Some(threshold) if size > threshold => {
+                let f = f.into_std().await;
+                // Safety: the map is read only, and users have been warned
+                // not to truncate files while they are mapped.
+                let mmap = unsafe { memmap2::Mmap::map(&f) }.map_err(new_std_io_error)?;
+                ThreeWays::Three(MmapReader::new(mmap).with_range(args.range()))
+            }
+            _ => match self.chunk_size {
+                Some(chunk_size) => ThreeWays::Two(oio::BufferReader::new(
+                    oio::TokioReader::with_capacity(f, chunk_size),
+                    chunk_size,
+                )),
+                None => ThreeWays::One(oio::TokioReader::new(f)),
+            },
+        };
 
-        let r = oio::TokioReader::new(f);
-        Ok((RpRead::new(), r))
+        let mut r = FsReader::new(inner, size, permit);
+        if args.checksum() && args.range().is_full() {
+            r = r.with_checksum(p.clone(), Self::etag_of(&meta));
+        }
+        Ok((RpRead::new(), r.with_path(p)))
     }
 
     async fn write(&self, path: &str, op: OpWrite) -> Result<(RpWrite, Self::Writer)> {
@@ -355,18 +1162,36 @@ impl Accessor for FsBackend {
 
         let mut open_options = tokio::fs::OpenOptions::new();
         open_options.create(true).write(true);
+        if op.if_not_exists() {
+            open_options.create_new(true);
+        }
         if op.append() {
             open_options.append(true);
         } else {
             open_options.truncate(true);
         }
+        #[cfg(target_os = "linux")]
+        if self.direct_io {
+            open_options.custom_flags(libc::O_DIRECT);
+        }
+        #[cfg(unix)]
+        if let Some(umask) = self.umask {
+            open_options.mode(0o666 & !umask);
+        }
 
+        let permit = self.acquire_open_file().await;
         let f = open_options
             .open(tmp_path.as_ref().unwrap_or(&target_path))
             .await
             .map_err(new_std_io_error)?;
 
-        Ok((RpWrite::new(), FsWriter::new(target_path, tmp_path, f)))
+        Ok((
+            RpWrite::new(),
+            FsWriter::new(target_path, tmp_path, f, permit)
+                .with_direct_io(self.direct_io)
+                .with_noreplace(op.if_not_exists())
+                .with_verify_on_close(self.verify_on_close && !op.append()),
+        ))
     }
 
     async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
@@ -389,7 +1214,7 @@ impl Accessor for FsBackend {
         }
     }
 
-    async fn list(&self, path: &str, _: OpList) -> Result<(RpList, Self::Lister)> {
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
         let p = self.root.join(path.trim_end_matches('/'));
 
         let f = match tokio::fs::read_dir(&p).await {
@@ -403,7 +1228,7 @@ impl Accessor for FsBackend {
             }
         };
 
-        let rd = FsLister::new(&self.root, f);
+        let rd = FsLister::new(&self.root, f, self.sort_list).with_skip_hidden(args.skip_hidden());
 
         Ok((RpList::default(), Some(rd)))
     }
@@ -436,6 +1261,158 @@ impl Accessor for FsBackend {
         Ok(RpRename::default())
     }
 
+    async fn hard_link(&self, from: &str, to: &str, _args: OpHardLink) -> Result<RpHardLink> {
+        Self::ensure_within_root(from)?;
+        Self::ensure_within_root(to)?;
+        let from = self.root.join(from.trim_end_matches('/'));
+        let to = Self::ensure_write_abs_path(&self.root, to.trim_end_matches('/')).await?;
+
+        tokio::fs::hard_link(from, to)
+            .await
+            .map_err(new_std_io_error)?;
+
+        Ok(RpHardLink::default())
+    }
+
+    async fn symlink(&self, target: &str, link: &str, _args: OpSymlink) -> Result<RpSymlink> {
+        Self::ensure_within_root(link)?;
+        let link = Self::ensure_write_abs_path(&self.root, link.trim_end_matches('/')).await?;
+
+        create_symlink(target, &link).await?;
+
+        Ok(RpSymlink::default())
+    }
+
+    async fn set_permissions(
+        &self,
+        path: &str,
+        args: OpSetPermissions,
+    ) -> Result<RpSetPermissions> {
+        Self::ensure_within_root(path)?;
+        let p = self.root.join(path.trim_end_matches('/'));
+
+        set_permissions(&p, args.mode()).await?;
+
+        Ok(RpSetPermissions::default())
+    }
+
+    async fn statvfs(&self, _: OpStatvfs) -> Result<RpStatvfs> {
+        // statvfs is a single cheap syscall, there is no need to move it
+        // to the blocking thread pool.
+        let stats = statvfs(&self.root)?;
+
+        Ok(RpStatvfs::new(stats))
+    }
+
+    async fn truncate(&self, path: &str, args: OpTruncate) -> Result<RpTruncate> {
+        Self::ensure_within_root(path)?;
+        let p = self.root.join(path.trim_end_matches('/'));
+
+        // Open without create and truncate so that missing files return
+        // NotFound and existing content is kept.
+        let f = tokio::fs::OpenOptions::new()
+            .write(true)
+            .open(&p)
+            .await
+            .map_err(new_std_io_error)?;
+        f.set_len(args.size()).await.map_err(new_std_io_error)?;
+        f.sync_all().await.map_err(new_std_io_error)?;
+
+        Ok(RpTruncate::default())
+    }
+
+    async fn fallocate(&self, path: &str, args: OpFallocate) -> Result<RpFallocate> {
+        Self::ensure_within_root(path)?;
+        let p = Self::ensure_write_abs_path(&self.root, path.trim_end_matches('/')).await?;
+
+        // Keep the existing content, fallocate only extends the space.
+        let f = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(false)
+            .open(&p)
+            .await
+            .map_err(new_std_io_error)?;
+        fallocate(&f, args.size())?;
+
+        Ok(RpFallocate::default())
+    }
+
+    async fn rename_noreplace(
+        &self,
+        from: &str,
+        to: &str,
+        _args: OpRenameNoreplace,
+    ) -> Result<RpRenameNoreplace> {
+        Self::ensure_within_root(from)?;
+        Self::ensure_within_root(to)?;
+        let from = self.root.join(from.trim_end_matches('/'));
+
+        // try to get the metadata of the source file to ensure it exists
+        tokio::fs::metadata(&from).await.map_err(new_std_io_error)?;
+
+        let to = Self::ensure_write_abs_path(&self.root, to.trim_end_matches('/')).await?;
+
+        rename_noreplace(&from, &to).await?;
+
+        Ok(RpRenameNoreplace::default())
+    }
+
+    async fn setxattr(&self, path: &str, args: OpSetxattr) -> Result<RpSetxattr> {
+        Self::ensure_within_root(path)?;
+        let p = self.root.join(path.trim_end_matches('/'));
+
+        // xattr syscalls are blocking, run them in the blocking thread pool.
+        let (name, value) = (args.name().to_string(), args.value().clone());
+        tokio::task::spawn_blocking(move || xattr::set(&p, &name, &value))
+            .await
+            .map_err(new_task_join_error)??;
+
+        Ok(RpSetxattr::default())
+    }
+
+    async fn getxattr(&self, path: &str, args: OpGetxattr) -> Result<RpGetxattr> {
+        Self::ensure_within_root(path)?;
+        let p = self.root.join(path.trim_end_matches('/'));
+
+        let name = args.name().to_string();
+        let value = tokio::task::spawn_blocking(move || xattr::get(&p, &name))
+            .await
+            .map_err(new_task_join_error)??;
+
+        Ok(RpGetxattr::new(value))
+    }
+
+    async fn listxattr(&self, path: &str, _: OpListxattr) -> Result<RpListxattr> {
+        Self::ensure_within_root(path)?;
+        let p = self.root.join(path.trim_end_matches('/'));
+
+        let names = tokio::task::spawn_blocking(move || xattr::list(&p))
+            .await
+            .map_err(new_task_join_error)??;
+
+        Ok(RpListxattr::new(names))
+    }
+
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let ops = args.into_operation();
+
+        let results = futures::stream::iter(ops)
+            .map(|(path, op)| async move {
+                let res = match op {
+                    BatchOperation::Delete(op) => {
+                        self.delete(&path, op).await.map(BatchedReply::Delete)
+                    }
+                };
+                (path, res)
+            })
+            .buffer_unordered(BATCH_CONCURRENT_LIMIT)
+            .collect::<Vec<_>>()
+            .await;
+
+        Ok(RpBatch::new(results))
+    }
+
     fn blocking_create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
         let p = self.root.join(path.trim_end_matches('/'));
 
@@ -444,38 +1421,48 @@ impl Accessor for FsBackend {
         Ok(RpCreateDir::default())
     }
 
-    fn blocking_stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+    fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         let p = self.root.join(path.trim_end_matches('/'));
 
-        let meta = std::fs::metadata(p).map_err(new_std_io_error)?;
-
-        let mode = if meta.is_dir() {
-            EntryMode::DIR
-        } else if meta.is_file() {
-            EntryMode::FILE
+        let meta = if self.follow_symlinks {
+            std::fs::metadata(&p)
         } else {
-            EntryMode::Unknown
-        };
-        let m = Metadata::new(mode)
-            .with_content_length(meta.len())
-            .with_last_modified(
-                meta.modified()
-                    .map(DateTime::from)
-                    .map_err(new_std_io_error)?,
-            );
+            std::fs::symlink_metadata(&p)
+        }
+        .map_err(new_std_io_error)?;
+
+        let mut m = Self::build_metadata(&meta)?;
+        if m.is_symlink() {
+            let target = std::fs::read_link(&p).map_err(new_std_io_error)?;
+            m.set_symlink_target(&target.to_string_lossy());
+        }
+        if args.checksum() && m.is_file() {
+            m.set_content_md5(&Self::blocking_content_md5_of(&p)?);
+        }
 
         Ok(RpStat::new(m))
     }
 
-    fn blocking_read(&self, path: &str, _: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+    fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
         let p = self.root.join(path.trim_end_matches('/'));
 
+        if !self.follow_symlinks {
+            let meta = std::fs::symlink_metadata(&p).map_err(new_std_io_error)?;
+            Self::ensure_not_symlink(&meta, &p)?;
+        }
+
+        let permit = self.blocking_acquire_open_file()?;
         let f = std::fs::OpenOptions::new()
             .read(true)
-            .open(p)
+            .open(&p)
             .map_err(new_std_io_error)?;
+        let meta = f.metadata().map_err(new_std_io_error)?;
+        let size = meta.len();
 
-        let r = oio::StdReader::new(f);
+        let mut r = FsReader::new(oio::StdReader::new(f), size, permit);
+        if args.checksum() && args.range().is_full() {
+            r = r.with_checksum(p, Self::etag_of(&meta));
+        }
 
         Ok((RpRead::new(), r))
     }
@@ -504,18 +1491,40 @@ impl Accessor for FsBackend {
 
         let mut f = std::fs::OpenOptions::new();
         f.create(true).write(true);
+        if op.if_not_exists() {
+            f.create_new(true);
+        }
 
         if op.append() {
             f.append(true);
         } else {
             f.truncate(true);
         }
+        #[cfg(target_os = "linux")]
+        if self.direct_io {
+            use std::os::unix::fs::OpenOptionsExt;
+
+            f.custom_flags(libc::O_DIRECT);
+        }
+        #[cfg(unix)]
+        if let Some(umask) = self.umask {
+            use std::os::unix::fs::OpenOptionsExt;
 
+            f.mode(0o666 & !umask);
+        }
+
+        let permit = self.blocking_acquire_open_file()?;
         let f = f
             .open(tmp_path.as_ref().unwrap_or(&target_path))
             .map_err(new_std_io_error)?;
 
-        Ok((RpWrite::new(), FsWriter::new(target_path, tmp_path, f)))
+        Ok((
+            RpWrite::new(),
+            FsWriter::new(target_path, tmp_path, f, permit)
+                .with_direct_io(self.direct_io)
+                .with_noreplace(op.if_not_exists())
+                .with_verify_on_close(self.verify_on_close && !op.append()),
+        ))
     }
 
     fn blocking_delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
@@ -538,7 +1547,7 @@ impl Accessor for FsBackend {
         }
     }
 
-    fn blocking_list(&self, path: &str, _: OpList) -> Result<(RpList, Self::BlockingLister)> {
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
         let p = self.root.join(path.trim_end_matches('/'));
 
         let f = match std::fs::read_dir(p) {
@@ -552,7 +1561,7 @@ impl Accessor for FsBackend {
             }
         };
 
-        let rd = FsLister::new(&self.root, f);
+        let rd = FsLister::new(&self.root, f, self.sort_list).with_skip_hidden(args.skip_hidden());
 
         Ok((RpList::default(), Some(rd)))
     }
@@ -586,8 +1595,60 @@ impl Accessor for FsBackend {
 
 #[cfg(test)]
 mod tests {
+    use std::ops::Deref;
+
     use super::*;
 
+    /// A temporary dir that is removed on drop, even if the test panics.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            Self(std::env::temp_dir().join(Uuid::new_v4().to_string()))
+        }
+
+        /// Create a builder rooted at this dir.
+        fn builder(&self) -> FsBuilder {
+            let mut builder = FsBuilder::default();
+            builder.root(&self.0.to_string_lossy());
+            builder
+        }
+    }
+
+    impl Deref for TempDir {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_watch() {
+        use futures::StreamExt;
+
+        use super::super::FsEventKind;
+
+        let root = TempDir::new();
+
+        let backend = root.builder().build().unwrap();
+        std::fs::create_dir(root.join("dir")).unwrap();
+
+        let mut w = backend.watch("dir").await.unwrap();
+        std::fs::write(root.join("dir/file"), "Hello").unwrap();
+
+        let event = w.next().await.unwrap().unwrap();
+        assert_eq!(event.path(), "dir/file");
+        assert_eq!(event.kind(), FsEventKind::Created);
+    }
+
     #[test]
     fn test_tmp_file_of() {
         let cases = vec![
@@ -602,4 +1663,410 @@ mod tests {
             assert!(tmp_file.starts_with(expected_prefix));
         }
     }
+
+    #[test]
+    fn test_build_without_create_root() {
+        let root = TempDir::new();
+
+        let mut builder = root.builder();
+        builder.create_root(false);
+
+        let err = builder.build().expect_err("build must fail");
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert!(!root.exists());
+    }
+
+    #[test]
+    fn test_root_and_atomic_write_dir() {
+        let root = TempDir::new();
+        let tmp = TempDir::new();
+
+        let mut builder = root.builder();
+        builder.atomic_write_dir(&tmp.to_string_lossy());
+        let backend = builder.build().unwrap();
+
+        // Both dirs are canonicalized while building.
+        assert_eq!(backend.root(), root.canonicalize().unwrap());
+        assert_eq!(
+            backend.atomic_write_dir(),
+            Some(tmp.canonicalize().unwrap().as_path())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_with_if_not_exists_and_atomic_write_dir() {
+        let root = TempDir::new();
+        let tmp = TempDir::new();
+
+        let mut builder = root.builder();
+        builder.atomic_write_dir(&tmp.to_string_lossy());
+        let op = Operator::new(builder).unwrap().finish();
+
+        // Content is not visible before close.
+        let mut w = op.writer_with("file").if_not_exists(true).await.unwrap();
+        w.write("Hello").await.unwrap();
+        assert!(!op.is_exist("file").await.unwrap());
+        w.close().await.unwrap();
+        assert_eq!(op.read("file").await.unwrap(), b"Hello");
+
+        let err = op
+            .write_with("file", "World")
+            .if_not_exists(true)
+            .await
+            .expect_err("write to existing file must fail");
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+        assert_eq!(op.read("file").await.unwrap(), b"Hello");
+        // The tmp file is removed after failing to publish.
+        assert_eq!(std::fs::read_dir(&*tmp).unwrap().count(), 0);
+
+        let err = op
+            .blocking()
+            .write_with("file", "World")
+            .if_not_exists(true)
+            .call()
+            .expect_err("write to existing file must fail");
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+        assert_eq!(op.read("file").await.unwrap(), b"Hello");
+        assert_eq!(std::fs::read_dir(&*tmp).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_max_open_files() {
+        let root = TempDir::new();
+
+        let mut builder = root.builder();
+        builder.max_open_files(0);
+        let err = builder.build().expect_err("max_open_files 0 must fail");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        let mut builder = FsBuilder::from_map(HashMap::from([
+            ("root".to_string(), root.to_string_lossy().to_string()),
+            ("max_open_files".to_string(), "two".to_string()),
+        ]));
+        assert_eq!(builder.max_open_files, None);
+
+        builder.max_open_files(2);
+        let op = Operator::new(builder).unwrap().finish();
+        op.write("file", "Hello, World!").await.unwrap();
+
+        let r1 = op.reader("file").await.unwrap();
+        let _r2 = op.reader("file").await.unwrap();
+        let err = op
+            .blocking()
+            .reader("file")
+            .expect_err("open more than max_open_files must fail");
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+
+        // The permit is released after reader has been dropped.
+        drop(r1);
+        op.blocking().reader("file").unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_list_with_sort_list() {
+        let root = TempDir::new();
+
+        let mut builder = root.builder();
+        builder.sort_list(true);
+        let op = Operator::new(builder).unwrap().finish();
+
+        for name in ["c", "a", "d", "b"] {
+            op.write(&format!("dir/{name}"), "Hello, World!")
+                .await
+                .unwrap();
+        }
+
+        let paths = op
+            .list("dir/")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.path().to_string())
+            .filter(|p| p != "dir/")
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec!["dir/a", "dir/b", "dir/c", "dir/d"]);
+    }
+
+    #[tokio::test]
+    async fn test_paths_escaping_root() {
+        let root = TempDir::new();
+
+        let backend = root.builder().build().unwrap();
+        let op = OperatorBuilder::new(backend.clone()).finish();
+        op.write("file", "Hello, World!").await.unwrap();
+
+        let err = op.hard_link("../file", "link").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = op.hard_link("file", "../link").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = op.symlink("file", "../link").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = op.set_permissions("../file", 0o600).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = op.truncate("../file", 0).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = op.fallocate("../file", 1024).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = op.rename_noreplace("../file", "file2").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = op.rename_noreplace("file", "../file2").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        #[cfg(any(target_os = "linux", target_os = "macos"))]
+        {
+            let err = op.setxattr("../file", "user.tag", "hot").await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+            let err = op.getxattr("../file", "user.tag").await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+            let err = op.listxattr("../file").await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        }
+        let err = backend.read_at("../file", 0, 1).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = backend.watch("../").await.err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_follow_symlinks_disabled() {
+        let root = TempDir::new();
+
+        let mut builder = root.builder();
+        builder.follow_symlinks(false);
+        let op = Operator::new(builder).unwrap().finish();
+
+        op.write("dir/file", "Hello, World!").await.unwrap();
+        std::os::unix::fs::symlink("file", root.join("dir/link")).unwrap();
+        // A link to its parent dir loops forever if it's followed.
+        std::os::unix::fs::symlink(".", root.join("dir/loop")).unwrap();
+
+        let meta = op.stat("dir/link").await.unwrap();
+        assert_eq!(meta.mode(), EntryMode::Symlink);
+        assert_eq!(meta.symlink_target(), Some("file"));
+        let meta = op.stat("dir/loop").await.unwrap();
+        assert_eq!(meta.mode(), EntryMode::Symlink);
+
+        let err = op.read("dir/link").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::IsADirectory);
+
+        let mut entries = op
+            .list_with("dir/")
+            .recursive(true)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.path().to_string(), e.metadata().mode()))
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            entries,
+            vec![
+                ("dir/file".to_string(), EntryMode::FILE),
+                ("dir/link".to_string(), EntryMode::Symlink),
+                ("dir/loop".to_string(), EntryMode::Symlink),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_symlink_target() {
+        let root = TempDir::new();
+
+        let op = Operator::new(root.builder()).unwrap().finish();
+
+        // Targets are stored as is, only the link itself must be under root.
+        op.symlink("/etc/passwd", "abs").await.unwrap();
+        op.symlink("../../file", "dir/outside").await.unwrap();
+        assert_eq!(
+            std::fs::read_link(root.join("abs")).unwrap(),
+            Path::new("/etc/passwd")
+        );
+        assert_eq!(
+            std::fs::read_link(root.join("dir/outside")).unwrap(),
+            Path::new("../../file")
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stat_unix_metadata() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = TempDir::new();
+
+        let op = Operator::new(root.builder()).unwrap().finish();
+
+        op.write("file", "Hello, World!").await.unwrap();
+        std::fs::set_permissions(root.join("file"), std::fs::Permissions::from_mode(0o600))
+            .unwrap();
+
+        let meta = op.stat("file").await.unwrap();
+        assert_eq!(meta.unix_permissions(), Some(0o600));
+        assert!(meta.atime().is_some());
+        assert!(meta.ctime() >= meta.last_modified());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_write_with_umask() {
+        let root = TempDir::new();
+
+        let mut builder = root.builder();
+        builder.umask(0o077);
+        let op = Operator::new(builder).unwrap().finish();
+
+        op.write("file", "Hello, World!").await.unwrap();
+
+        let meta = op.stat("file").await.unwrap();
+        assert_eq!(meta.unix_permissions(), Some(0o600));
+    }
+
+    #[tokio::test]
+    async fn test_read_with_mmap() {
+        let root = TempDir::new();
+
+        let mut builder = root.builder();
+        builder.mmap_threshold(4);
+        let op = Operator::new(builder).unwrap().finish();
+
+        op.write("small", "Hi").await.unwrap();
+        op.write("large", "Hello, World!").await.unwrap();
+        assert_eq!(op.read("small").await.unwrap(), b"Hi");
+        assert_eq!(op.read("large").await.unwrap(), b"Hello, World!");
+        assert_eq!(op.read_with("large").range(7..12).await.unwrap(), b"World");
+    }
+
+    #[tokio::test]
+    async fn test_read_with_chunk_size() {
+        let root = TempDir::new();
+
+        let mut builder = root.builder();
+        builder.chunk_size(4);
+        let op = Operator::new(builder).unwrap().finish();
+
+        op.write("file", "Hello, World!").await.unwrap();
+        assert_eq!(op.read("file").await.unwrap(), b"Hello, World!");
+        assert_eq!(op.read_with("file").range(7..12).await.unwrap(), b"World");
+
+        let mut builder = root.builder();
+        builder.chunk_size(0);
+        let err = Operator::new(builder)
+            .err()
+            .expect("zero chunk size must be rejected");
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[tokio::test]
+    async fn test_read_at() {
+        use crate::raw::oio::Read;
+
+        let root = TempDir::new();
+
+        let backend = root.builder().build().unwrap();
+        std::fs::write(root.join("file"), "Hello, World!").unwrap();
+
+        let (hello, world, eof) = futures::future::join3(
+            backend.read_at("file", 0, 5),
+            backend.read_at("file", 7, 100),
+            backend.read_at("file", 20, 5),
+        )
+        .await;
+        assert_eq!(hello.unwrap(), "Hello");
+        assert_eq!(world.unwrap(), "World!");
+        assert!(eof.unwrap().is_empty());
+
+        // Positional reads don't move the position of reader.
+        let (_, mut r) = backend.read("file", OpRead::new()).await.unwrap();
+        assert_eq!(r.read(5).await.unwrap(), "Hello");
+        assert_eq!(r.read_at(7, 5).await.unwrap(), "World");
+        assert_eq!(r.read(2).await.unwrap(), ", ");
+    }
+
+    #[tokio::test]
+    async fn test_read_with_checksum() {
+        use crate::raw::oio::Read;
+
+        let root = TempDir::new();
+
+        let backend = root.builder().build().unwrap();
+        std::fs::write(root.join("file"), "Hello, World!").unwrap();
+
+        let args = OpRead::new().with_checksum(true);
+        let (_, mut r) = backend.read("file", args.clone()).await.unwrap();
+        assert_eq!(r.read(100).await.unwrap(), "Hello, World!");
+        assert!(r.read(100).await.unwrap().is_empty());
+        assert_eq!(r.content_md5(), Some("ZajifYh5KDgxtmS9i38K1A=="));
+
+        // Replace the file while reading.
+        let (_, mut r) = backend.read("file", args).await.unwrap();
+        assert_eq!(r.read(5).await.unwrap(), "Hello");
+        std::fs::write(root.join("new"), "Hello, World?").unwrap();
+        std::fs::rename(root.join("new"), root.join("file")).unwrap();
+        assert_eq!(r.read(100).await.unwrap(), ", World!");
+        let err = r.read(100).await.expect_err("changed file must fail");
+        assert_eq!(err.kind(), ErrorKind::DataIntegrityCheckFailed);
+        assert_eq!(r.content_md5(), None);
+    }
+
+    #[tokio::test]
+    async fn test_framed_write_and_read() {
+        let root = TempDir::new();
+
+        let backend = root.builder().build().unwrap();
+
+        let mut w = backend.framed_write("wal").await.unwrap();
+        w.write(Bytes::from("Hello")).await.unwrap();
+        w.write(Bytes::from("World!")).await.unwrap();
+        w.close().await.unwrap();
+
+        let mut r = backend.framed_read("wal").await.unwrap();
+        assert_eq!(r.next().await.unwrap(), Some(Bytes::from("Hello")));
+        assert_eq!(r.next().await.unwrap(), Some(Bytes::from("World!")));
+        assert_eq!(r.next().await.unwrap(), None);
+
+        // Flip a byte in the payload of the last frame.
+        let mut content = std::fs::read(root.join("wal")).unwrap();
+        let last = content.len() - 1;
+        content[last] ^= 0xff;
+        std::fs::write(root.join("wal"), content).unwrap();
+
+        let mut r = backend.framed_read("wal").await.unwrap();
+        assert!(r.next().await.is_ok());
+        let err = r.next().await.expect_err("corrupted frame must fail");
+        assert_eq!(err.kind(), ErrorKind::DataIntegrityCheckFailed);
+    }
+
+    #[tokio::test]
+    async fn test_write_with_verify_on_close() {
+        let root = TempDir::new();
+
+        let mut builder = root.builder();
+        builder.verify_on_close(true);
+        let op = Operator::new(builder).unwrap().finish();
+
+        op.write("file", "Hello, World!").await.unwrap();
+        assert_eq!(op.read("file").await.unwrap(), b"Hello, World!");
+
+        let mut w = op.writer("large").await.unwrap();
+        for _ in 0..16 {
+            w.write(vec![1; 64 * 1024]).await.unwrap();
+        }
+        w.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stat_etag() {
+        let root = TempDir::new();
+
+        let op = Operator::new(root.builder()).unwrap().finish();
+
+        op.write("file", "Hello").await.unwrap();
+        let etag = op.stat("file").await.unwrap().etag().unwrap().to_string();
+        assert_eq!(etag.len(), 8);
+        assert_eq!(op.stat("file").await.unwrap().etag(), Some(etag.as_str()));
+
+        op.write("file", "Hello, World!").await.unwrap();
+        assert_ne!(op.stat("file").await.unwrap().etag(), Some(etag.as_str()));
+    }
 }
@@ -0,0 +1,165 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Length prefixed and checksummed frames for WAL or log like files.
+
+use bytes::BufMut;
+use bytes::Bytes;
+use bytes::BytesMut;
+
+use crate::raw::*;
+use crate::*;
+
+/// The size of frame header: 4 bytes length and 4 bytes checksum.
+pub const FRAME_HEADER_SIZE: usize = 8;
+
+/// FramedWriter writes every payload as a frame into inner writer.
+///
+/// # Format
+///
+/// A framed file is a sequence of frames without any file header:
+///
+/// ```text
+/// +----------------+----------------+-------------------+
+/// | len: u32 (LE)  | crc: u32 (LE)  | payload: len bytes|
+/// +----------------+----------------+-------------------+
+/// ```
+///
+/// - `len` is the size of payload in bytes, encoded as little endian.
+/// - `crc` is the CRC-32C (Castagnoli) of payload, encoded as little endian.
+///
+/// Every `write` call produces exactly one frame. External tools can
+/// recover the data by reading frames until the end of file, a trailing
+/// frame that is shorter than its header claims is the result of an
+/// interrupted write.
+pub struct FramedWriter<W> {
+    inner: W,
+}
+
+impl<W: oio::Write> FramedWriter<W> {
+    /// Create a new FramedWriter.
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Write `payload` as a single frame.
+    pub async fn write(&mut self, payload: Bytes) -> Result<()> {
+        let len = u32::try_from(payload.len()).map_err(|_| {
+            Error::new(ErrorKind::InvalidInput, "frame payload is too large")
+                .with_context("len", payload.len().to_string())
+        })?;
+
+        let mut frame = BytesMut::with_capacity(FRAME_HEADER_SIZE + payload.len());
+        frame.put_u32_le(len);
+        frame.put_u32_le(crc32c::crc32c(&payload));
+        frame.put_slice(&payload);
+
+        oio::WriteExt::write_all(&mut self.inner, frame.freeze()).await
+    }
+
+    /// Close the inner writer so that all frames are persisted.
+    pub async fn close(&mut self) -> Result<()> {
+        oio::WriteExt::close(&mut self.inner).await
+    }
+}
+
+/// FramedReader reads frames from inner reader and verifies their checksum.
+pub struct FramedReader<R> {
+    inner: R,
+}
+
+impl<R: oio::Read> FramedReader<R> {
+    /// Create a new FramedReader.
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+
+    /// Read the payload of next frame.
+    ///
+    /// Returns `None` if the reader reaches the end of file at the boundary
+    /// of frames. A truncated frame returns `ContentIncomplete` error and a
+    /// checksum mismatch returns `DataIntegrityCheckFailed` error.
+    pub async fn next(&mut self) -> Result<Option<Bytes>> {
+        let bs = self.inner.read(FRAME_HEADER_SIZE).await?;
+        if bs.is_empty() {
+            return Ok(None);
+        }
+        let header = if bs.len() == FRAME_HEADER_SIZE {
+            bs
+        } else {
+            let mut header = BytesMut::with_capacity(FRAME_HEADER_SIZE);
+            header.extend_from_slice(&bs);
+            header.extend_from_slice(&self.inner.read_exact(FRAME_HEADER_SIZE - bs.len()).await?);
+            header.freeze()
+        };
+
+        let len = u32::from_le_bytes(header[..4].try_into().expect("must be 4 bytes"));
+        let expected = u32::from_le_bytes(header[4..].try_into().expect("must be 4 bytes"));
+
+        let payload = self.inner.read_exact(len as usize).await?;
+        let actual = crc32c::crc32c(&payload);
+        if actual != expected {
+            return Err(Error::new(
+                ErrorKind::DataIntegrityCheckFailed,
+                "frame crc doesn't match",
+            )
+            .with_context("expected", format!("{expected:08x}"))
+            .with_context("actual", format!("{actual:08x}")));
+        }
+
+        Ok(Some(payload))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_framed_read() -> Result<()> {
+        let mut buf = BytesMut::new();
+        for payload in [&b"Hello"[..], b"", b"World!"] {
+            buf.put_u32_le(payload.len() as u32);
+            buf.put_u32_le(crc32c::crc32c(payload));
+            buf.put_slice(payload);
+        }
+        let content = buf.to_vec();
+
+        let mut r = FramedReader::new(oio::Cursor::from(content.clone()));
+        assert_eq!(r.next().await?, Some(Bytes::from("Hello")));
+        assert_eq!(r.next().await?, Some(Bytes::new()));
+        assert_eq!(r.next().await?, Some(Bytes::from("World!")));
+        assert_eq!(r.next().await?, None);
+
+        // Corrupt the payload of the first frame.
+        let mut corrupted = content.clone();
+        corrupted[FRAME_HEADER_SIZE] = b'h';
+        let mut r = FramedReader::new(oio::Cursor::from(corrupted));
+        let err = r.next().await.expect_err("corrupted frame must fail");
+        assert_eq!(err.kind(), ErrorKind::DataIntegrityCheckFailed);
+
+        // Truncate the last frame.
+        let truncated = content[..content.len() - 1].to_vec();
+        let mut r = FramedReader::new(oio::Cursor::from(truncated));
+        r.next().await?;
+        r.next().await?;
+        let err = r.next().await.expect_err("truncated frame must fail");
+        assert_eq!(err.kind(), ErrorKind::ContentIncomplete);
+
+        Ok(())
+    }
+}
@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::ffi::OsStr;
 use std::fs::FileType;
 use std::path::Path;
 use std::path::PathBuf;
@@ -30,23 +31,57 @@ use crate::EntryMode;
 use crate::Metadata;
 use crate::Result;
 
+/// The file type and symlink target (if any) of a dir entry.
+type EntryTypeFuture =
+    BoxFuture<'static, (tokio::fs::DirEntry, Result<(FileType, Option<PathBuf>)>)>;
+
 pub struct FsLister<P> {
     root: PathBuf,
 
     rd: P,
 
-    fut: Option<BoxFuture<'static, (tokio::fs::DirEntry, Result<FileType>)>>,
+    fut: Option<EntryTypeFuture>,
+
+    sort: bool,
+    skip_hidden: bool,
+    /// Sorted entries in reverse lexicographic order so that we can pop
+    /// them from the end. Only used while sort is enabled.
+    sorted: Option<Vec<oio::Entry>>,
+    entries: Vec<oio::Entry>,
 }
 
 impl<P> FsLister<P> {
-    pub fn new(root: &Path, rd: P) -> Self {
+    pub fn new(root: &Path, rd: P, sort: bool) -> Self {
         Self {
             root: root.to_owned(),
             rd,
 
             fut: None,
+
+            sort,
+            skip_hidden: false,
+            sorted: None,
+            entries: Vec::new(),
         }
     }
+
+    /// Skip entries whose name starts with `.`.
+    pub fn with_skip_hidden(mut self, skip_hidden: bool) -> Self {
+        self.skip_hidden = skip_hidden;
+        self
+    }
+
+    /// Check if the entry of given file name should be skipped.
+    fn should_skip(&self, file_name: &OsStr) -> bool {
+        self.skip_hidden && file_name.to_string_lossy().starts_with('.')
+    }
+
+    /// Sort all collected entries by path and make them ready for yielding.
+    fn sort_entries(&mut self) {
+        let mut entries = std::mem::take(&mut self.entries);
+        entries.sort_by(|a, b| b.path().cmp(a.path()));
+        self.sorted = Some(entries);
+    }
 }
 
 /// # Safety
@@ -54,18 +89,71 @@ impl<P> FsLister<P> {
 /// We will only take `&mut Self` reference for FsLister.
 unsafe impl<P> Sync for FsLister<P> {}
 
+/// Fetch the file type of given entry, and the link target if it's a symlink.
+async fn file_type_of(de: &tokio::fs::DirEntry) -> Result<(FileType, Option<PathBuf>)> {
+    let ft = de.file_type().await.map_err(new_std_io_error)?;
+    let target = if ft.is_symlink() {
+        Some(
+            tokio::fs::read_link(de.path())
+                .await
+                .map_err(new_std_io_error)?,
+        )
+    } else {
+        None
+    };
+
+    Ok((ft, target))
+}
+
+/// Build entry based on the file type and the link target if it's a symlink.
+fn build_entry(rel_path: &str, ft: FileType, target: Option<PathBuf>) -> oio::Entry {
+    if ft.is_file() {
+        oio::Entry::new(rel_path, Metadata::new(EntryMode::FILE))
+    } else if ft.is_dir() {
+        // Make sure we are returning the correct path.
+        oio::Entry::new(&format!("{rel_path}/"), Metadata::new(EntryMode::DIR))
+    } else if ft.is_symlink() {
+        let mut meta = Metadata::new(EntryMode::Symlink);
+        if let Some(target) = target {
+            meta.set_symlink_target(&target.to_string_lossy());
+        }
+        oio::Entry::new(rel_path, meta)
+    } else {
+        oio::Entry::new(rel_path, Metadata::new(EntryMode::Unknown))
+    }
+}
+
 impl oio::List for FsLister<tokio::fs::ReadDir> {
     fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<oio::Entry>>> {
+        if !self.sort {
+            return self.poll_next_entry(cx);
+        }
+
+        loop {
+            if let Some(sorted) = self.sorted.as_mut() {
+                return Poll::Ready(Ok(sorted.pop()));
+            }
+
+            match ready!(self.poll_next_entry(cx))? {
+                Some(de) => self.entries.push(de),
+                None => self.sort_entries(),
+            }
+        }
+    }
+}
+
+impl FsLister<tokio::fs::ReadDir> {
+    fn poll_next_entry(&mut self, cx: &mut Context<'_>) -> Poll<Result<Option<oio::Entry>>> {
         if let Some(fut) = self.fut.as_mut() {
             let (de, ft) = futures::ready!(fut.poll_unpin(cx));
-            let ft = match ft {
-                Ok(ft) => {
+            let (ft, target) = match ft {
+                Ok(v) => {
                     self.fut = None;
-                    ft
+                    v
                 }
                 Err(e) => {
                     let fut = async move {
-                        let ft = de.file_type().await.map_err(new_std_io_error);
+                        let ft = file_type_of(&de).await;
                         (de, ft)
                     };
                     self.fut = Some(Box::pin(fut));
@@ -82,27 +170,21 @@ impl oio::List for FsLister<tokio::fs::ReadDir> {
                     .replace('\\', "/"),
             );
 
-            let d = if ft.is_file() {
-                oio::Entry::new(&rel_path, Metadata::new(EntryMode::FILE))
-            } else if ft.is_dir() {
-                // Make sure we are returning the correct path.
-                oio::Entry::new(&format!("{rel_path}/"), Metadata::new(EntryMode::DIR))
-            } else {
-                oio::Entry::new(&rel_path, Metadata::new(EntryMode::Unknown))
-            };
+            let d = build_entry(&rel_path, ft, target);
 
             return Poll::Ready(Ok(Some(d)));
         }
 
         let de = ready!(self.rd.poll_next_entry(cx)).map_err(new_std_io_error)?;
         match de {
+            Some(de) if self.should_skip(&de.file_name()) => self.poll_next_entry(cx),
             Some(de) => {
                 let fut = async move {
-                    let ft = de.file_type().await.map_err(new_std_io_error);
+                    let ft = file_type_of(&de).await;
                     (de, ft)
                 };
                 self.fut = Some(Box::pin(fut));
-                self.poll_next(cx)
+                self.poll_next_entry(cx)
             }
             None => Poll::Ready(Ok(None)),
         }
@@ -111,9 +193,35 @@ impl oio::List for FsLister<tokio::fs::ReadDir> {
 
 impl oio::BlockingList for FsLister<std::fs::ReadDir> {
     fn next(&mut self) -> Result<Option<oio::Entry>> {
-        let de = match self.rd.next() {
-            Some(de) => de.map_err(new_std_io_error)?,
-            None => return Ok(None),
+        if !self.sort {
+            return self.next_entry();
+        }
+
+        loop {
+            if let Some(sorted) = self.sorted.as_mut() {
+                return Ok(sorted.pop());
+            }
+
+            match self.next_entry()? {
+                Some(de) => self.entries.push(de),
+                None => self.sort_entries(),
+            }
+        }
+    }
+}
+
+impl FsLister<std::fs::ReadDir> {
+    fn next_entry(&mut self) -> Result<Option<oio::Entry>> {
+        let de = loop {
+            match self.rd.next() {
+                Some(de) => {
+                    let de = de.map_err(new_std_io_error)?;
+                    if !self.should_skip(&de.file_name()) {
+                        break de;
+                    }
+                }
+                None => return Ok(None),
+            }
         };
 
         let entry_path = de.path();
@@ -130,16 +238,12 @@ impl oio::BlockingList for FsLister<std::fs::ReadDir> {
         // require the equivalent call to symlink_metadata to learn about
         // the target file type.
         let file_type = de.file_type().map_err(new_std_io_error)?;
-
-        let entry = if file_type.is_file() {
-            oio::Entry::new(&rel_path, Metadata::new(EntryMode::FILE))
-        } else if file_type.is_dir() {
-            // Make sure we are returning the correct path.
-            oio::Entry::new(&format!("{rel_path}/"), Metadata::new(EntryMode::DIR))
+        let target = if file_type.is_symlink() {
+            Some(std::fs::read_link(de.path()).map_err(new_std_io_error)?)
         } else {
-            oio::Entry::new(&rel_path, Metadata::new(EntryMode::Unknown))
+            None
         };
 
-        Ok(Some(entry))
+        Ok(Some(build_entry(&rel_path, file_type, target)))
     }
 }
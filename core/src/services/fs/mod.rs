@@ -16,7 +16,20 @@
 // under the License.
 
 mod backend;
+pub use backend::FsBackend;
 pub use backend::FsBuilder as Fs;
 
+mod framed;
+pub use framed::FramedReader;
+pub use framed::FramedWriter;
 mod lister;
+mod reader;
+pub use reader::FsReader;
+pub use reader::MmapReader;
+mod watcher;
+pub use watcher::FsEvent;
+pub use watcher::FsEventKind;
+pub use watcher::FsWatcher;
 mod writer;
+pub use writer::FsWriter;
+mod xattr;
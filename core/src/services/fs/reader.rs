@@ -0,0 +1,369 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp;
+use std::io::SeekFrom;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use base64::engine::general_purpose;
+use base64::Engine;
+use bytes::Bytes;
+use md5::Digest;
+use tokio::sync::OnceCell;
+use tokio::sync::OwnedSemaphorePermit;
+
+use super::backend::FsBackend;
+use crate::raw::*;
+use crate::*;
+
+/// FsReader wraps the underlying reader and holds the open file permit
+/// until the reader has been dropped.
+///
+/// If checksum is set, FsReader will also calculate the content md5 while
+/// reading and check that the file hasn't been changed at the end of the
+/// content.
+///
+/// FsReader also tracks the position of the reader which can be used to
+/// report the progress of reading.
+///
+/// If path is set, FsReader supports `read_at` via `pread` which doesn't
+/// touch the position of the inner reader. The file for `pread` is opened
+/// on the first `read_at` and shares the open file permit of the reader.
+pub struct FsReader<R> {
+    inner: R,
+    checksum: Option<Checksum>,
+    content_md5: Option<String>,
+    size: u64,
+    read_bytes: u64,
+    path: Option<PathBuf>,
+    file: OnceCell<Arc<std::fs::File>>,
+
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+struct Checksum {
+    hasher: md5::Md5,
+    path: PathBuf,
+    etag: String,
+}
+
+impl<R> FsReader<R> {
+    /// Create a new FsReader with the size of the file while opening.
+    pub fn new(inner: R, size: u64, permit: Option<OwnedSemaphorePermit>) -> Self {
+        Self {
+            inner,
+            checksum: None,
+            content_md5: None,
+            size,
+            read_bytes: 0,
+            path: None,
+            file: OnceCell::new(),
+            _permit: permit,
+        }
+    }
+
+    /// Get the current position of the reader, which is the bytes read
+    /// from the start of file.
+    pub fn position(&self) -> u64 {
+        self.read_bytes
+    }
+
+    /// Check if the reader has reached the end of file.
+    pub fn is_at_eof(&self) -> bool {
+        self.read_bytes >= self.size
+    }
+
+    /// Calculate the content md5 while reading, and check that the file at
+    /// `path` still has the same `etag` at the end of the content.
+    pub fn with_checksum(mut self, path: PathBuf, etag: String) -> Self {
+        self.checksum = Some(Checksum {
+            hasher: md5::Md5::new(),
+            path,
+            etag,
+        });
+        self
+    }
+
+    /// Get the content md5 (base64 encoded) calculated while reading.
+    ///
+    /// It's only available after reading to the end of the content with
+    /// checksum enabled.
+    pub fn content_md5(&self) -> Option<&str> {
+        self.content_md5.as_deref()
+    }
+
+    /// Serve `read_at` with the file at given path.
+    pub fn with_path(mut self, path: PathBuf) -> Self {
+        self.path = Some(path);
+        self
+    }
+
+    fn update_checksum(&mut self, bs: &[u8]) -> Result<()> {
+        let Some(checksum) = self.checksum.as_mut() else {
+            return Ok(());
+        };
+
+        if !bs.is_empty() {
+            checksum.hasher.update(bs);
+            return Ok(());
+        }
+
+        // Reach the end of content, the md5 is only valid if the file hasn't
+        // been modified or replaced while reading. stat is a single cheap
+        // syscall, there is no need to move it to the blocking thread pool.
+        let checksum = self.checksum.take().expect("checksum must be valid");
+        let meta = std::fs::metadata(&checksum.path).map_err(new_std_io_error)?;
+        let etag = FsBackend::etag_of(&meta);
+        if etag != checksum.etag {
+            return Err(Error::new(
+                ErrorKind::DataIntegrityCheckFailed,
+                "file has been changed while reading",
+            )
+            .with_context("path", checksum.path.to_string_lossy())
+            .with_context("expected_etag", checksum.etag)
+            .with_context("actual_etag", etag));
+        }
+
+        self.content_md5 = Some(general_purpose::STANDARD.encode(checksum.hasher.finalize()));
+        Ok(())
+    }
+}
+
+impl<R: oio::Read> oio::Read for FsReader<R> {
+    async fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        // Content is not sequential anymore, we can't verify it.
+        self.checksum = None;
+        let pos = self.inner.seek(pos).await?;
+        self.read_bytes = pos;
+        Ok(pos)
+    }
+
+    async fn read(&mut self, limit: usize) -> Result<Bytes> {
+        let bs = self.inner.read(limit).await?;
+        self.read_bytes += bs.len() as u64;
+        // Empty result of zero limit doesn't mean the end of content.
+        if limit > 0 {
+            self.update_checksum(&bs)?;
+        }
+        Ok(bs)
+    }
+
+    async fn read_at(&self, offset: u64, limit: usize) -> Result<Bytes> {
+        let Some(path) = &self.path else {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "fs reader doesn't support read_at",
+            ));
+        };
+
+        let f = self
+            .file
+            .get_or_try_init(|| async {
+                let f = tokio::fs::File::open(path)
+                    .await
+                    .map_err(new_std_io_error)?;
+                Ok::<_, Error>(Arc::new(f.into_std().await))
+            })
+            .await?;
+        pread(f.clone(), offset, limit).await
+    }
+}
+
+/// Read at most `limit` bytes at `offset` of given file in the blocking
+/// thread pool.
+///
+/// The position of file is not changed on unix, so it's safe to read the
+/// same file concurrently.
+pub(super) async fn pread(f: Arc<std::fs::File>, offset: u64, limit: usize) -> Result<Bytes> {
+    tokio::task::spawn_blocking(move || blocking_pread(&f, offset, limit))
+        .await
+        .map_err(new_task_join_error)?
+}
+
+fn blocking_pread(f: &std::fs::File, offset: u64, limit: usize) -> Result<Bytes> {
+    let mut buf = vec![0; limit];
+    let mut filled = 0;
+    while filled < limit {
+        #[cfg(unix)]
+        let res =
+            std::os::unix::fs::FileExt::read_at(f, &mut buf[filled..], offset + filled as u64);
+        // `seek_read` moves the position of file on windows.
+        #[cfg(windows)]
+        let res =
+            std::os::windows::fs::FileExt::seek_read(f, &mut buf[filled..], offset + filled as u64);
+
+        match res {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(new_std_io_error(err)),
+        }
+    }
+    buf.truncate(filled);
+
+    Ok(Bytes::from(buf))
+}
+
+impl<R: oio::BlockingRead> oio::BlockingRead for FsReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read_bytes += n as u64;
+        // Empty result of empty buf doesn't mean the end of content.
+        if !buf.is_empty() {
+            self.update_checksum(&buf[..n])?;
+        }
+        Ok(n)
+    }
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        // Content is not sequential anymore, we can't verify it.
+        self.checksum = None;
+        let pos = self.inner.seek(pos)?;
+        self.read_bytes = pos;
+        Ok(pos)
+    }
+
+    fn next(&mut self) -> Option<Result<Bytes>> {
+        match self.inner.next() {
+            Some(Ok(bs)) => {
+                self.read_bytes += bs.len() as u64;
+                Some(self.update_checksum(&bs).map(|_| bs))
+            }
+            Some(Err(err)) => Some(Err(err)),
+            None => self.update_checksum(&[]).err().map(Err),
+        }
+    }
+}
+
+/// MmapReader serves reads from a memory mapped file.
+///
+/// Only bytes in `start..end` of the map are visible, positions are
+/// relative to `start`.
+pub struct MmapReader {
+    mmap: memmap2::Mmap,
+    start: usize,
+    end: usize,
+    pos: u64,
+}
+
+impl MmapReader {
+    /// Create a new MmapReader.
+    pub fn new(mmap: memmap2::Mmap) -> Self {
+        let end = mmap.len();
+        Self {
+            mmap,
+            start: 0,
+            end,
+            pos: 0,
+        }
+    }
+
+    /// Only serve bytes in given range of the file.
+    pub fn with_range(mut self, range: BytesRange) -> Self {
+        let len = self.mmap.len() as u64;
+        let (offset, size) = match (range.offset(), range.size()) {
+            (Some(offset), size) => (offset, size),
+            (None, Some(size)) => (len.saturating_sub(size), Some(size)),
+            (None, None) => (0, None),
+        };
+
+        self.start = cmp::min(offset, len) as usize;
+        self.end = match size {
+            Some(size) => cmp::min(offset.saturating_add(size), len) as usize,
+            None => len as usize,
+        };
+        self
+    }
+}
+
+impl oio::Read for MmapReader {
+    async fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let (base, amt) = match pos {
+            SeekFrom::Start(n) => (0, n as i64),
+            SeekFrom::End(n) => ((self.end - self.start) as i64, n),
+            SeekFrom::Current(n) => (self.pos as i64, n),
+        };
+
+        match base.checked_add(amt) {
+            Some(n) if n >= 0 => {
+                self.pos = n as u64;
+                Ok(self.pos)
+            }
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+
+    async fn read(&mut self, limit: usize) -> Result<Bytes> {
+        let len = self.end - self.start;
+        let start = cmp::min(self.pos, len as u64) as usize;
+        let end = cmp::min(start.saturating_add(limit), len);
+
+        self.pos = end as u64;
+        Ok(Bytes::copy_from_slice(
+            &self.mmap[self.start + start..self.start + end],
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::raw::oio::BlockingRead;
+
+    #[test]
+    fn test_position() -> Result<()> {
+        let mut r = FsReader::new(oio::StdReader::new(Cursor::new(vec![0; 10])), 10, None);
+        assert_eq!(r.position(), 0);
+
+        let mut buf = [0; 4];
+        r.read(&mut buf)?;
+        assert_eq!(r.position(), 4);
+        assert!(!r.is_at_eof());
+
+        r.seek(SeekFrom::End(-2))?;
+        assert_eq!(r.position(), 8);
+
+        r.read(&mut buf)?;
+        assert_eq!(r.position(), 10);
+        assert!(r.is_at_eof());
+
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_mmap_with_range() -> Result<()> {
+        let path = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        std::fs::write(&path, "Hello, World!").unwrap();
+        let f = std::fs::File::open(&path).unwrap();
+        // Safety: the file is not modified while it's mapped.
+        let mmap = unsafe { memmap2::Mmap::map(&f) }.unwrap();
+
+        let mut r = MmapReader::new(mmap).with_range(BytesRange::new(Some(7), Some(5)));
+        assert_eq!(oio::Read::read(&mut r, 100).await?, "World");
+        assert!(oio::Read::read(&mut r, 100).await?.is_empty());
+        assert_eq!(oio::Read::seek(&mut r, SeekFrom::End(-2)).await?, 3);
+        assert_eq!(oio::Read::read(&mut r, 100).await?, "ld");
+
+        std::fs::remove_file(&path).unwrap();
+        Ok(())
+    }
+}
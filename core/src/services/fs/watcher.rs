@@ -0,0 +1,499 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Watch file system events of a path.
+//!
+//! - linux: inotify.
+//! - macos: kqueue. kqueue doesn't report the names of changed children,
+//!   so the dir will be listed again to find out created and deleted
+//!   entries. Renaming inside the dir is reported as deleted and created.
+//!
+//! Other platforms will return `Unsupported` error.
+//!
+//! The events are read from a non-blocking fd registered in the tokio
+//! reactor, so no thread is held by the watcher.
+
+use std::path::Path;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use futures::stream::BoxStream;
+use futures::Stream;
+use futures::StreamExt;
+
+use crate::raw::*;
+use crate::*;
+
+/// The kind of a file system event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsEventKind {
+    /// The entry has been created.
+    Created,
+    /// The content of entry has been modified.
+    Modified,
+    /// The entry has been deleted.
+    Deleted,
+    /// The entry has been renamed from or to the path.
+    Renamed,
+}
+
+/// A file system event of a watched path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FsEvent {
+    path: String,
+    kind: FsEventKind,
+}
+
+impl FsEvent {
+    /// The path of the entry relative to root.
+    ///
+    /// Dirs always end with `/`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The kind of this event.
+    pub fn kind(&self) -> FsEventKind {
+        self.kind
+    }
+}
+
+/// FsWatcher is a stream of file system events.
+///
+/// The stream ends after the watched path has been deleted. Lost events
+/// (for example, the event queue of kernel overflowed) are reported as
+/// temporary errors, the stream can still be polled after that.
+pub struct FsWatcher {
+    inner: BoxStream<'static, Result<FsEvent>>,
+}
+
+impl Stream for FsWatcher {
+    type Item = Result<FsEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+/// Watch the events of `p` which is `path` under root.
+///
+/// Must be called within a tokio runtime since the fd is registered in
+/// its reactor.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub async fn watch(p: &Path, path: &str, is_dir: bool) -> Result<FsWatcher> {
+    // Children of a dir are reported as `base` + name, so dirs must end
+    // with `/` and root is an empty base.
+    let mut base = normalize_path(path);
+    if base == "/" {
+        base.clear();
+    } else if is_dir && !base.ends_with('/') {
+        base.push('/');
+    }
+
+    let w = sys::Watcher::new(p, base, is_dir).await?;
+    let inner = futures::stream::unfold(w, |mut w| async move {
+        let event = w.next().await?;
+        Some((event, w))
+    });
+
+    Ok(FsWatcher {
+        inner: inner.boxed(),
+    })
+}
+
+/// Watch the events of `p` which is `path` under root.
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub async fn watch(_: &Path, _: &str, _: bool) -> Result<FsWatcher> {
+    Err(Error::new(
+        ErrorKind::Unsupported,
+        "watch is not supported on this platform",
+    ))
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use std::collections::VecDeque;
+    use std::ffi::CString;
+    use std::io;
+    use std::mem::size_of;
+    use std::os::fd::AsRawFd;
+    use std::os::fd::FromRawFd;
+    use std::os::fd::OwnedFd;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+
+    use tokio::io::unix::AsyncFd;
+
+    use super::FsEvent;
+    use super::FsEventKind;
+    use crate::raw::*;
+    use crate::*;
+
+    const WATCH_MASK: u32 = libc::IN_CREATE
+        | libc::IN_MODIFY
+        | libc::IN_DELETE
+        | libc::IN_DELETE_SELF
+        | libc::IN_MOVED_FROM
+        | libc::IN_MOVED_TO
+        | libc::IN_MOVE_SELF;
+
+    pub struct Watcher {
+        fd: AsyncFd<OwnedFd>,
+        base: String,
+        buf: Vec<u8>,
+        pending: VecDeque<Result<FsEvent>>,
+        done: bool,
+    }
+
+    impl Watcher {
+        pub async fn new(p: &Path, base: String, _: bool) -> Result<Self> {
+            let cpath = CString::new(p.as_os_str().as_bytes()).map_err(|err| {
+                Error::new(ErrorKind::InvalidInput, "path contains nul byte").set_source(err)
+            })?;
+
+            // Safety: inotify_init1 has no pointer arguments.
+            let fd = unsafe { libc::inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC) };
+            if fd < 0 {
+                return Err(new_std_io_error(io::Error::last_os_error()));
+            }
+            // Safety: fd is a valid inotify instance owned by nobody else.
+            let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+            // Safety: cpath is a valid nul terminated string.
+            let wd = unsafe { libc::inotify_add_watch(fd.as_raw_fd(), cpath.as_ptr(), WATCH_MASK) };
+            if wd < 0 {
+                return Err(new_std_io_error(io::Error::last_os_error()));
+            }
+
+            Ok(Self {
+                fd: AsyncFd::new(fd).map_err(new_std_io_error)?,
+                base,
+                buf: vec![0; 4096],
+                pending: VecDeque::new(),
+                done: false,
+            })
+        }
+
+        pub async fn next(&mut self) -> Option<Result<FsEvent>> {
+            loop {
+                if let Some(event) = self.pending.pop_front() {
+                    return Some(event);
+                }
+                if self.done {
+                    return None;
+                }
+
+                match self.read().await {
+                    Ok(n) => self.parse(n),
+                    Err(err) => {
+                        self.done = true;
+                        return Some(Err(new_std_io_error(err)));
+                    }
+                }
+            }
+        }
+
+        async fn read(&mut self) -> io::Result<usize> {
+            let Self { fd, buf, .. } = self;
+            loop {
+                let mut guard = fd.readable().await?;
+                let res = guard.try_io(|fd| {
+                    // Safety: buf is valid for writes of its len.
+                    let ret = unsafe {
+                        libc::read(
+                            fd.as_raw_fd(),
+                            buf.as_mut_ptr() as *mut libc::c_void,
+                            buf.len(),
+                        )
+                    };
+                    if ret < 0 {
+                        return Err(io::Error::last_os_error());
+                    }
+                    Ok(ret as usize)
+                });
+                match res {
+                    Ok(Err(err)) if err.kind() == io::ErrorKind::Interrupted => continue,
+                    Ok(res) => return res,
+                    // The fd is not readable anymore, wait for next readiness.
+                    Err(_) => continue,
+                }
+            }
+        }
+
+        fn parse(&mut self, n: usize) {
+            let mut offset = 0;
+            while offset + size_of::<libc::inotify_event>() <= n {
+                // Safety: the kernel always writes complete events, the buf
+                // is not aligned so read it unaligned.
+                let event: libc::inotify_event = unsafe {
+                    std::ptr::read_unaligned(
+                        self.buf[offset..].as_ptr() as *const libc::inotify_event
+                    )
+                };
+                let start = offset + size_of::<libc::inotify_event>();
+                offset = start + event.len as usize;
+
+                if event.mask & libc::IN_Q_OVERFLOW != 0 {
+                    self.pending.push_back(Err(new_events_lost_error()));
+                    continue;
+                }
+                // The watch has been removed since the path is gone.
+                if event.mask & libc::IN_IGNORED != 0 {
+                    self.done = true;
+                    return;
+                }
+                let Some(kind) = event_kind(event.mask) else {
+                    continue;
+                };
+
+                // The name is padded with nul bytes.
+                let name = self.buf[start..offset]
+                    .split(|b| *b == 0)
+                    .next()
+                    .unwrap_or_default();
+                let path = format!("{}{}", self.base, String::from_utf8_lossy(name));
+                self.pending.push_back(Ok(FsEvent { path, kind }));
+            }
+        }
+    }
+
+    fn event_kind(mask: u32) -> Option<FsEventKind> {
+        if mask & libc::IN_CREATE != 0 {
+            Some(FsEventKind::Created)
+        } else if mask & libc::IN_MODIFY != 0 {
+            Some(FsEventKind::Modified)
+        } else if mask & (libc::IN_DELETE | libc::IN_DELETE_SELF) != 0 {
+            Some(FsEventKind::Deleted)
+        } else if mask & (libc::IN_MOVED_FROM | libc::IN_MOVED_TO | libc::IN_MOVE_SELF) != 0 {
+            Some(FsEventKind::Renamed)
+        } else {
+            None
+        }
+    }
+
+    fn new_events_lost_error() -> Error {
+        Error::new(
+            ErrorKind::Unexpected,
+            "inotify event queue overflowed, some events are lost",
+        )
+        .set_temporary()
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod sys {
+    use std::collections::HashSet;
+    use std::collections::VecDeque;
+    use std::ffi::CString;
+    use std::ffi::OsString;
+    use std::io;
+    use std::os::fd::AsRawFd;
+    use std::os::fd::FromRawFd;
+    use std::os::fd::OwnedFd;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use tokio::io::unix::AsyncFd;
+
+    use super::FsEvent;
+    use super::FsEventKind;
+    use crate::raw::*;
+    use crate::*;
+
+    const WATCH_FFLAGS: u32 =
+        libc::NOTE_WRITE | libc::NOTE_EXTEND | libc::NOTE_DELETE | libc::NOTE_RENAME;
+
+    pub struct Watcher {
+        kq: AsyncFd<OwnedFd>,
+        /// The watched file, kqueue watches the fd instead of path.
+        _file: OwnedFd,
+        /// The path and children of the watched dir.
+        dir: Option<(PathBuf, HashSet<OsString>)>,
+        base: String,
+        pending: VecDeque<Result<FsEvent>>,
+        done: bool,
+    }
+
+    impl Watcher {
+        pub async fn new(p: &Path, base: String, is_dir: bool) -> Result<Self> {
+            let cpath = CString::new(p.as_os_str().as_bytes()).map_err(|err| {
+                Error::new(ErrorKind::InvalidInput, "path contains nul byte").set_source(err)
+            })?;
+
+            // Safety: cpath is a valid nul terminated string.
+            let file = unsafe { libc::open(cpath.as_ptr(), libc::O_EVTONLY | libc::O_CLOEXEC) };
+            if file < 0 {
+                return Err(new_std_io_error(io::Error::last_os_error()));
+            }
+            // Safety: file is a valid fd owned by nobody else.
+            let file = unsafe { OwnedFd::from_raw_fd(file) };
+
+            // Safety: kqueue has no arguments.
+            let kq = unsafe { libc::kqueue() };
+            if kq < 0 {
+                return Err(new_std_io_error(io::Error::last_os_error()));
+            }
+            // Safety: kq is a valid kqueue owned by nobody else.
+            let kq = unsafe { OwnedFd::from_raw_fd(kq) };
+
+            let change = libc::kevent {
+                ident: file.as_raw_fd() as libc::uintptr_t,
+                filter: libc::EVFILT_VNODE,
+                flags: libc::EV_ADD | libc::EV_CLEAR,
+                fflags: WATCH_FFLAGS,
+                data: 0,
+                udata: std::ptr::null_mut(),
+            };
+            // Safety: change is valid during the call and no events are
+            // received.
+            let ret = unsafe {
+                libc::kevent(
+                    kq.as_raw_fd(),
+                    &change,
+                    1,
+                    std::ptr::null_mut(),
+                    0,
+                    std::ptr::null(),
+                )
+            };
+            if ret < 0 {
+                return Err(new_std_io_error(io::Error::last_os_error()));
+            }
+
+            let dir = if is_dir {
+                let children = list(p).await.map_err(new_std_io_error)?;
+                Some((p.to_path_buf(), children))
+            } else {
+                None
+            };
+
+            Ok(Self {
+                kq: AsyncFd::new(kq).map_err(new_std_io_error)?,
+                _file: file,
+                dir,
+                base,
+                pending: VecDeque::new(),
+                done: false,
+            })
+        }
+
+        pub async fn next(&mut self) -> Option<Result<FsEvent>> {
+            loop {
+                if let Some(event) = self.pending.pop_front() {
+                    return Some(event);
+                }
+                if self.done {
+                    return None;
+                }
+
+                let res = match self.read().await {
+                    Ok(fflags) => self.handle(fflags).await,
+                    Err(err) => Err(err),
+                };
+                if let Err(err) = res {
+                    self.done = true;
+                    return Some(Err(new_std_io_error(err)));
+                }
+            }
+        }
+
+        /// Wait for the next vnode event and return its fflags.
+        async fn read(&self) -> io::Result<u32> {
+            loop {
+                let mut guard = self.kq.readable().await?;
+                let res = guard.try_io(|kq| {
+                    // Safety: kevent is a plain C struct.
+                    let mut event: libc::kevent = unsafe { std::mem::zeroed() };
+                    let timeout = libc::timespec {
+                        tv_sec: 0,
+                        tv_nsec: 0,
+                    };
+                    // Safety: event and timeout are valid during the call.
+                    let ret = unsafe {
+                        libc::kevent(kq.as_raw_fd(), std::ptr::null(), 0, &mut event, 1, &timeout)
+                    };
+                    match ret {
+                        n if n < 0 => Err(io::Error::last_os_error()),
+                        0 => Err(io::ErrorKind::WouldBlock.into()),
+                        _ => Ok(event.fflags),
+                    }
+                });
+                match res {
+                    Ok(Err(err)) if err.kind() == io::ErrorKind::Interrupted => continue,
+                    Ok(res) => return res,
+                    // The kqueue is not readable anymore, wait for next readiness.
+                    Err(_) => continue,
+                }
+            }
+        }
+
+        async fn handle(&mut self, fflags: u32) -> io::Result<()> {
+            if fflags & libc::NOTE_DELETE != 0 {
+                self.push(String::new(), FsEventKind::Deleted);
+                self.done = true;
+                return Ok(());
+            }
+            if fflags & libc::NOTE_RENAME != 0 {
+                self.push(String::new(), FsEventKind::Renamed);
+            }
+            if fflags & (libc::NOTE_WRITE | libc::NOTE_EXTEND) == 0 {
+                return Ok(());
+            }
+
+            let Some((p, children)) = self.dir.as_mut() else {
+                self.push(String::new(), FsEventKind::Modified);
+                return Ok(());
+            };
+
+            // kqueue only tells us that the dir has been changed, compare
+            // the children to find out what happened.
+            let latest = match list(p).await {
+                Ok(v) => v,
+                // The dir has been deleted, NOTE_DELETE will follow.
+                Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+                Err(err) => return Err(err),
+            };
+            let created: Vec<_> = latest.difference(children).cloned().collect();
+            let deleted: Vec<_> = children.difference(&latest).cloned().collect();
+            *children = latest;
+
+            for name in created {
+                self.push(name.to_string_lossy().into_owned(), FsEventKind::Created);
+            }
+            for name in deleted {
+                self.push(name.to_string_lossy().into_owned(), FsEventKind::Deleted);
+            }
+            Ok(())
+        }
+
+        fn push(&mut self, name: String, kind: FsEventKind) {
+            let path = format!("{}{}", self.base, name);
+            self.pending.push_back(Ok(FsEvent { path, kind }));
+        }
+    }
+
+    async fn list(p: &Path) -> io::Result<HashSet<OsString>> {
+        let mut children = HashSet::new();
+        let mut rd = tokio::fs::read_dir(p).await?;
+        while let Some(de) = rd.next_entry().await? {
+            children.insert(de.file_name());
+        }
+        Ok(children)
+    }
+}
@@ -15,39 +15,282 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::io::SeekFrom;
 use std::io::Write;
+use std::path::Path;
 use std::path::PathBuf;
 use std::pin::Pin;
 use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
 
+use base64::engine::general_purpose;
+use base64::Engine;
+use bytes::Bytes;
 use futures::future::BoxFuture;
 use futures::FutureExt;
+use md5::Digest;
+use tokio::io::AsyncSeekExt;
 use tokio::io::AsyncWrite;
 use tokio::io::AsyncWriteExt;
+use tokio::sync::OwnedSemaphorePermit;
 
+use super::backend::blocking_rename_noreplace;
+use super::backend::rename_noreplace;
+use super::backend::FsBackend;
 use crate::raw::*;
 use crate::*;
 
+/// The alignment required by direct io for both buffer address and length.
+const DIRECT_IO_ALIGNMENT: usize = 512;
+
+/// FsWriter writes into a file, and renames it from the atomic write dir
+/// to the target path on close if a tmp path is set.
 pub struct FsWriter<F> {
     target_path: PathBuf,
     tmp_path: Option<PathBuf>,
 
     f: Option<F>,
     fut: Option<BoxFuture<'static, Result<()>>>,
+    written: u64,
+    direct_io: bool,
+    fsync_dir: bool,
+    noreplace: bool,
+    /// The md5 of written content, only set if verify on close is enabled.
+    hasher: Option<md5::Md5>,
+
+    // Hold on this permit until this writer has been dropped.
+    _permit: Option<OwnedSemaphorePermit>,
 }
 
 impl<F> FsWriter<F> {
-    pub fn new(target_path: PathBuf, tmp_path: Option<PathBuf>, f: F) -> Self {
+    /// Create a new FsWriter which writes into `f`.
+    ///
+    /// If `tmp_path` is set, `f` must be opened at `tmp_path`.
+    pub fn new(
+        target_path: PathBuf,
+        tmp_path: Option<PathBuf>,
+        f: F,
+        permit: Option<OwnedSemaphorePermit>,
+    ) -> Self {
         Self {
             target_path,
             tmp_path,
 
             f: Some(f),
             fut: None,
+            written: 0,
+            direct_io: false,
+            fsync_dir: true,
+            noreplace: false,
+            hasher: None,
+
+            _permit: permit,
         }
     }
+
+    /// Check the alignment of write buffers since the file is opened with `O_DIRECT`.
+    pub fn with_direct_io(mut self, direct_io: bool) -> Self {
+        self.direct_io = direct_io;
+        self
+    }
+
+    /// Fsync the parent dir after the file has been synced on close.
+    ///
+    /// Default to `true`. Syncing the file only doesn't make the dir entry
+    /// of a newly created or renamed file durable.
+    pub fn with_fsync_dir(mut self, fsync_dir: bool) -> Self {
+        self.fsync_dir = fsync_dir;
+        self
+    }
+
+    /// Publish the tmp file on close without replacing an existing target,
+    /// fails with `AlreadyExists` if the target exists.
+    ///
+    /// Default to `false`. It takes no effect if tmp path is not set.
+    pub fn with_noreplace(mut self, noreplace: bool) -> Self {
+        self.noreplace = noreplace;
+        self
+    }
+
+    /// Re-read the file after close and check its md5 against written content.
+    ///
+    /// Default to `false`. Files written with sparse holes are verified
+    /// as if the holes were filled with zeros.
+    pub fn with_verify_on_close(mut self, verify_on_close: bool) -> Self {
+        self.hasher = verify_on_close.then(md5::Md5::new);
+        self
+    }
+
+    /// Feed the first `n` bytes of `bs` which have been written into hasher.
+    fn update_hasher(&mut self, bs: &dyn oio::WriteBuf, mut n: usize) {
+        let Some(hasher) = self.hasher.as_mut() else {
+            return;
+        };
+
+        for chunk in bs.vectored_chunk() {
+            if n == 0 {
+                break;
+            }
+            let size = n.min(chunk.len());
+            hasher.update(&chunk[..size]);
+            n -= size;
+        }
+    }
+
+    /// Take the base64 encoded md5 of written content if verify on close is enabled.
+    fn take_content_md5(&mut self) -> Option<String> {
+        self.hasher
+            .take()
+            .map(|hasher| general_purpose::STANDARD.encode(hasher.finalize()))
+    }
+
+    fn check_alignment(&self, bs: &dyn oio::WriteBuf) -> Result<()> {
+        if !self.direct_io {
+            return Ok(());
+        }
+
+        for chunk in bs.vectored_chunk() {
+            if chunk.as_ptr() as usize % DIRECT_IO_ALIGNMENT != 0
+                || chunk.len() % DIRECT_IO_ALIGNMENT != 0
+            {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "write buffer is not aligned for direct io",
+                )
+                .with_context("alignment", DIRECT_IO_ALIGNMENT.to_string())
+                .with_context("len", chunk.len().to_string()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FsWriter<tokio::fs::File> {
+    /// Write `bs` at `offset` and leave the gap between the end of previous
+    /// writes and `offset` as a sparse hole instead of filling it with zeros.
+    ///
+    /// # Notes
+    ///
+    /// - `offset` must not be less than the end of previous writes.
+    /// - The file must not be opened in append mode, otherwise all writes
+    ///   go to the end of file.
+    /// - After this call, `written_bytes` returns the end of the last write
+    ///   instead of the number of bytes written.
+    pub async fn write_sparse(&mut self, offset: u64, bs: Bytes) -> Result<()> {
+        if offset < self.written {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "sparse write offset is before the end of previous writes",
+            )
+            .with_context("offset", offset.to_string())
+            .with_context("written", self.written.to_string()));
+        }
+        self.check_alignment(&bs)?;
+
+        let f = self.f.as_mut().expect("FsWriter must be initialized");
+        f.flush().await.map_err(new_std_io_error)?;
+
+        #[cfg(target_os = "linux")]
+        punch_hole(f, self.written, offset - self.written)?;
+
+        f.seek(SeekFrom::Start(offset))
+            .await
+            .map_err(new_std_io_error)?;
+        f.write_all(&bs).await.map_err(new_std_io_error)?;
+
+        if let Some(hasher) = self.hasher.as_mut() {
+            let mut gap = offset - self.written;
+            let zeros = [0; 4096];
+            while gap > 0 {
+                let size = gap.min(zeros.len() as u64) as usize;
+                hasher.update(&zeros[..size]);
+                gap -= size as u64;
+            }
+            hasher.update(&bs);
+        }
+        self.written = offset + bs.len() as u64;
+        Ok(())
+    }
+}
+
+/// Deallocate given range of file so that it stays a hole even if it has
+/// been allocated before.
+///
+/// File systems that don't support punching holes are ignored, since ranges
+/// beyond the end of file will still be holes after seeking.
+#[cfg(target_os = "linux")]
+fn punch_hole(f: &tokio::fs::File, offset: u64, len: u64) -> Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    if len == 0 {
+        return Ok(());
+    }
+
+    // Safety: the fd is owned by `f` which outlives this call.
+    let ret = unsafe {
+        libc::fallocate(
+            f.as_raw_fd(),
+            libc::FALLOC_FL_PUNCH_HOLE | libc::FALLOC_FL_KEEP_SIZE,
+            offset as libc::off_t,
+            len as libc::off_t,
+        )
+    };
+    if ret != 0 {
+        let err = std::io::Error::last_os_error();
+        if err.raw_os_error() != Some(libc::EOPNOTSUPP) {
+            return Err(new_std_io_error(err));
+        }
+    }
+
+    Ok(())
+}
+
+/// Fsync the parent dir of given path so that its dir entry is durable.
+///
+/// Dirs can't be opened as files on windows, so this is a no-op there.
+async fn fsync_parent(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    if let Some(parent) = path.parent() {
+        let dir = tokio::fs::File::open(parent)
+            .await
+            .map_err(new_std_io_error)?;
+        dir.sync_all().await.map_err(new_std_io_error)?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+
+    Ok(())
+}
+
+/// Fsync the parent dir of given path so that its dir entry is durable.
+///
+/// Dirs can't be opened as files on windows, so this is a no-op there.
+fn blocking_fsync_parent(path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    if let Some(parent) = path.parent() {
+        let dir = std::fs::File::open(parent).map_err(new_std_io_error)?;
+        dir.sync_all().map_err(new_std_io_error)?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+
+    Ok(())
+}
+
+fn check_content_md5(path: &Path, expected: &str, actual: &str) -> Result<()> {
+    if expected != actual {
+        return Err(Error::new(
+            ErrorKind::DataIntegrityCheckFailed,
+            "written content doesn't match the file on close",
+        )
+        .with_context("path", path.to_string_lossy())
+        .with_context("expected", expected)
+        .with_context("actual", actual));
+    }
+
+    Ok(())
 }
 
 /// # Safety
@@ -57,11 +300,15 @@ unsafe impl<F> Sync for FsWriter<F> {}
 
 impl oio::Write for FsWriter<tokio::fs::File> {
     fn poll_write(&mut self, cx: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>> {
+        self.check_alignment(bs)?;
+
         let f = self.f.as_mut().expect("FsWriter must be initialized");
 
-        Pin::new(f)
-            .poll_write_vectored(cx, &bs.vectored_chunk())
-            .map_err(new_std_io_error)
+        let n = ready!(Pin::new(f).poll_write_vectored(cx, &bs.vectored_chunk()))
+            .map_err(new_std_io_error)?;
+        self.update_hasher(bs, n);
+        self.written += n as u64;
+        Poll::Ready(Ok(n))
     }
 
     fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
@@ -75,14 +322,31 @@ impl oio::Write for FsWriter<tokio::fs::File> {
             let mut f = self.f.take().expect("FsWriter must be initialized");
             let tmp_path = self.tmp_path.clone();
             let target_path = self.target_path.clone();
+            let fsync_dir = self.fsync_dir;
+            let noreplace = self.noreplace;
+            let content_md5 = self.take_content_md5();
             self.fut = Some(Box::pin(async move {
                 f.flush().await.map_err(new_std_io_error)?;
                 f.sync_all().await.map_err(new_std_io_error)?;
 
-                if let Some(tmp_path) = &tmp_path {
-                    tokio::fs::rename(tmp_path, &target_path)
+                match &tmp_path {
+                    Some(tmp_path) if noreplace => {
+                        if let Err(err) = rename_noreplace(tmp_path, &target_path).await {
+                            let _ = tokio::fs::remove_file(tmp_path).await;
+                            return Err(err);
+                        }
+                    }
+                    Some(tmp_path) => tokio::fs::rename(tmp_path, &target_path)
                         .await
-                        .map_err(new_std_io_error)?;
+                        .map_err(new_std_io_error)?,
+                    None => {}
+                }
+                if fsync_dir {
+                    fsync_parent(&target_path).await?;
+                }
+                if let Some(expected) = content_md5 {
+                    let actual = FsBackend::content_md5_of(&target_path).await?;
+                    check_content_md5(&target_path, &expected, &actual)?;
                 }
 
                 Ok(())
@@ -114,25 +378,122 @@ impl oio::Write for FsWriter<tokio::fs::File> {
             }));
         }
     }
+
+    fn written_bytes(&self) -> u64 {
+        self.written
+    }
 }
 
 impl oio::BlockingWrite for FsWriter<std::fs::File> {
     fn write(&mut self, bs: &dyn oio::WriteBuf) -> Result<usize> {
+        self.check_alignment(bs)?;
+
         let f = self.f.as_mut().expect("FsWriter must be initialized");
 
-        f.write_vectored(&bs.vectored_chunk())
-            .map_err(new_std_io_error)
+        let n = f
+            .write_vectored(&bs.vectored_chunk())
+            .map_err(new_std_io_error)?;
+        self.update_hasher(bs, n);
+        self.written += n as u64;
+        Ok(n)
     }
 
     fn close(&mut self) -> Result<()> {
         if let Some(f) = self.f.take() {
             f.sync_all().map_err(new_std_io_error)?;
 
-            if let Some(tmp_path) = &self.tmp_path {
-                std::fs::rename(tmp_path, &self.target_path).map_err(new_std_io_error)?;
+            match &self.tmp_path {
+                Some(tmp_path) if self.noreplace => {
+                    if let Err(err) = blocking_rename_noreplace(tmp_path, &self.target_path) {
+                        let _ = std::fs::remove_file(tmp_path);
+                        return Err(err);
+                    }
+                }
+                Some(tmp_path) => {
+                    std::fs::rename(tmp_path, &self.target_path).map_err(new_std_io_error)?
+                }
+                None => {}
+            }
+            if self.fsync_dir {
+                blocking_fsync_parent(&self.target_path)?;
+            }
+            if let Some(expected) = self.take_content_md5() {
+                let actual = FsBackend::blocking_content_md5_of(&self.target_path)?;
+                check_content_md5(&self.target_path, &expected, &actual)?;
             }
         }
 
         Ok(())
     }
+    fn written_bytes(&self) -> u64 {
+        self.written
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::raw::oio::WriteExt;
+
+    #[test]
+    fn test_check_alignment() {
+        let w = FsWriter::new(PathBuf::new(), None, (), None);
+        assert!(w.check_alignment(&Bytes::from(vec![0; 100])).is_ok());
+
+        let w = w.with_direct_io(true);
+        let err = w
+            .check_alignment(&Bytes::from(vec![0; 100]))
+            .expect_err("unaligned buffer must be rejected");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[tokio::test]
+    async fn test_write_sparse() {
+        let path = std::env::temp_dir().join(Uuid::new_v4().to_string());
+        let f = tokio::fs::File::create(&path).await.unwrap();
+
+        let mut w = FsWriter::new(path.clone(), None, f, None);
+        w.write_sparse(0, Bytes::from("Hello")).await.unwrap();
+        w.write_sparse(8192, Bytes::from("World")).await.unwrap();
+        let err = w
+            .write_sparse(4096, Bytes::from("!"))
+            .await
+            .expect_err("write before previous writes must fail");
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        w.close().await.unwrap();
+
+        let bs = std::fs::read(&path).unwrap();
+        assert_eq!(bs.len(), 8197);
+        assert_eq!(&bs[..5], b"Hello");
+        assert!(bs[5..8192].iter().all(|b| *b == 0));
+        assert_eq!(&bs[8192..], b"World");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_verify_on_close() {
+        let path = std::env::temp_dir().join(Uuid::new_v4().to_string());
+        let f = tokio::fs::File::create(&path).await.unwrap();
+
+        let mut w = FsWriter::new(path.clone(), None, f, None).with_verify_on_close(true);
+        w.write_sparse(0, Bytes::from("Hello")).await.unwrap();
+        w.write_sparse(8192, Bytes::from("World")).await.unwrap();
+        w.close().await.unwrap();
+
+        // Modify the file behind the writer's back.
+        let f = tokio::fs::OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .await
+            .unwrap();
+        let mut w = FsWriter::new(path.clone(), None, f, None).with_verify_on_close(true);
+        w.write(&Bytes::from("Hi")).await.unwrap();
+        let err = w.close().await.expect_err("modified file must fail verify");
+        assert_eq!(err.kind(), ErrorKind::DataIntegrityCheckFailed);
+
+        std::fs::remove_file(&path).unwrap();
+    }
 }
@@ -0,0 +1,241 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Extended attributes of files.
+//!
+//! All functions here are blocking, callers should run them in the blocking
+//! thread pool.
+
+use std::path::Path;
+
+use bytes::Bytes;
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use crate::raw::*;
+use crate::*;
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+mod sys {
+    use std::ffi::CString;
+    use std::io;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+
+    use libc::c_char;
+    use libc::c_void;
+
+    use crate::*;
+
+    /// The errno returned for an attribute that doesn't exist.
+    #[cfg(target_os = "linux")]
+    pub const ENOATTR: i32 = libc::ENODATA;
+    #[cfg(target_os = "macos")]
+    pub const ENOATTR: i32 = libc::ENOATTR;
+
+    pub fn to_cstring(v: &[u8]) -> Result<CString> {
+        CString::new(v)
+            .map_err(|err| Error::new(ErrorKind::InvalidInput, "contains nul byte").set_source(err))
+    }
+
+    pub fn path_to_cstring(p: &Path) -> Result<CString> {
+        to_cstring(p.as_os_str().as_bytes())
+    }
+
+    fn check_size(ret: isize) -> io::Result<usize> {
+        if ret < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(ret as usize)
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn setxattr(path: &CString, name: &CString, value: &[u8]) -> io::Result<()> {
+        // Safety: all pointers are valid during this call.
+        let ret = unsafe {
+            libc::setxattr(
+                path.as_ptr(),
+                name.as_ptr(),
+                value.as_ptr() as *const c_void,
+                value.len(),
+                0,
+            )
+        };
+        check_size(ret as isize).map(|_| ())
+    }
+
+    #[cfg(target_os = "macos")]
+    pub fn setxattr(path: &CString, name: &CString, value: &[u8]) -> io::Result<()> {
+        // Safety: all pointers are valid during this call.
+        let ret = unsafe {
+            libc::setxattr(
+                path.as_ptr(),
+                name.as_ptr(),
+                value.as_ptr() as *const c_void,
+                value.len(),
+                0,
+                0,
+            )
+        };
+        check_size(ret as isize).map(|_| ())
+    }
+
+    /// Get the value of attribute into buf, returns the size of value.
+    ///
+    /// Only returns the size of value if buf is empty.
+    #[cfg(target_os = "linux")]
+    pub fn getxattr(path: &CString, name: &CString, buf: &mut [u8]) -> io::Result<usize> {
+        // Safety: all pointers are valid during this call.
+        let ret = unsafe {
+            libc::getxattr(
+                path.as_ptr(),
+                name.as_ptr(),
+                buf.as_mut_ptr() as *mut c_void,
+                buf.len(),
+            )
+        };
+        check_size(ret)
+    }
+
+    /// Get the value of attribute into buf, returns the size of value.
+    ///
+    /// Only returns the size of value if buf is empty.
+    #[cfg(target_os = "macos")]
+    pub fn getxattr(path: &CString, name: &CString, buf: &mut [u8]) -> io::Result<usize> {
+        // Safety: all pointers are valid during this call.
+        let ret = unsafe {
+            libc::getxattr(
+                path.as_ptr(),
+                name.as_ptr(),
+                buf.as_mut_ptr() as *mut c_void,
+                buf.len(),
+                0,
+                0,
+            )
+        };
+        check_size(ret)
+    }
+
+    /// List nul separated names of attributes into buf, returns the size of names.
+    ///
+    /// Only returns the size of names if buf is empty.
+    #[cfg(target_os = "linux")]
+    pub fn listxattr(path: &CString, buf: &mut [u8]) -> io::Result<usize> {
+        // Safety: all pointers are valid during this call.
+        let ret =
+            unsafe { libc::listxattr(path.as_ptr(), buf.as_mut_ptr() as *mut c_char, buf.len()) };
+        check_size(ret)
+    }
+
+    /// List nul separated names of attributes into buf, returns the size of names.
+    ///
+    /// Only returns the size of names if buf is empty.
+    #[cfg(target_os = "macos")]
+    pub fn listxattr(path: &CString, buf: &mut [u8]) -> io::Result<usize> {
+        // Safety: all pointers are valid during this call.
+        let ret = unsafe {
+            libc::listxattr(path.as_ptr(), buf.as_mut_ptr() as *mut c_char, buf.len(), 0)
+        };
+        check_size(ret)
+    }
+}
+
+/// Read a value whose size could change between calls, retry if the
+/// buffer is too small.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn read_growing(
+    mut f: impl FnMut(&mut [u8]) -> std::io::Result<usize>,
+) -> std::io::Result<Vec<u8>> {
+    loop {
+        let size = f(&mut [])?;
+        let mut buf = vec![0; size];
+        match f(&mut buf) {
+            Ok(n) => {
+                buf.truncate(n);
+                return Ok(buf);
+            }
+            Err(err) if err.raw_os_error() == Some(libc::ERANGE) => continue,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Set the attribute `name` of given path to `value`.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub fn set(p: &Path, name: &str, value: &[u8]) -> Result<()> {
+    let path = sys::path_to_cstring(p)?;
+    let name = sys::to_cstring(name.as_bytes())?;
+
+    sys::setxattr(&path, &name, value).map_err(new_std_io_error)
+}
+
+/// Get the value of attribute `name` of given path.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub fn get(p: &Path, name: &str) -> Result<Bytes> {
+    let path = sys::path_to_cstring(p)?;
+    let cname = sys::to_cstring(name.as_bytes())?;
+
+    match read_growing(|buf| sys::getxattr(&path, &cname, buf)) {
+        Ok(value) => Ok(Bytes::from(value)),
+        Err(err) if err.raw_os_error() == Some(sys::ENOATTR) => {
+            Err(Error::new(ErrorKind::NotFound, "xattr is not found")
+                .with_context("name", name)
+                .set_source(err))
+        }
+        Err(err) => Err(new_std_io_error(err)),
+    }
+}
+
+/// List the names of all attributes of given path.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub fn list(p: &Path) -> Result<Vec<String>> {
+    let path = sys::path_to_cstring(p)?;
+
+    let buf = read_growing(|buf| sys::listxattr(&path, buf)).map_err(new_std_io_error)?;
+
+    Ok(buf
+        .split(|b| *b == 0)
+        .filter(|name| !name.is_empty())
+        .map(|name| String::from_utf8_lossy(name).into_owned())
+        .collect())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn new_unsupported_error() -> Error {
+    Error::new(
+        ErrorKind::Unsupported,
+        "xattr is not supported on this platform",
+    )
+}
+
+/// Set the attribute `name` of given path to `value`.
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn set(_: &Path, _: &str, _: &[u8]) -> Result<()> {
+    Err(new_unsupported_error())
+}
+
+/// Get the value of attribute `name` of given path.
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn get(_: &Path, _: &str) -> Result<Bytes> {
+    Err(new_unsupported_error())
+}
+
+/// List the names of all attributes of given path.
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn list(_: &Path) -> Result<Vec<String>> {
+    Err(new_unsupported_error())
+}
@@ -19,6 +19,7 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use http::StatusCode;
@@ -29,10 +30,13 @@ use reqsign::GoogleTokenLoad;
 use reqsign::GoogleTokenLoader;
 use serde::Deserialize;
 use serde_json;
+use tokio::sync::OnceCell;
+use tokio::sync::Semaphore;
 
 use super::core::*;
 use super::error::parse_error;
 use super::lister::GcsLister;
+use super::writer::GcsAppendWriter;
 use super::writer::GcsWriter;
 use super::writer::GcsWriters;
 use crate::raw::*;
@@ -65,6 +69,14 @@ pub struct GcsConfig {
     predefined_acl: Option<String>,
     /// The default storage class used by gcs.
     default_storage_class: Option<String>,
+    /// The Cloud KMS key used to encrypt new objects (CMEK).
+    kms_key_name: Option<String>,
+    /// Use the ambient GKE Workload Identity instead of credential files.
+    workload_identity: bool,
+    /// Check that the bucket exists before the first operation.
+    validate_bucket: bool,
+    /// The max number of connections to gcs.
+    max_connections: Option<usize>,
 }
 
 impl Debug for GcsConfig {
@@ -74,6 +86,9 @@ impl Debug for GcsConfig {
             .field("bucket", &self.bucket)
             .field("endpoint", &self.endpoint)
             .field("scope", &self.scope)
+            .field("workload_identity", &self.workload_identity)
+            .field("validate_bucket", &self.validate_bucket)
+            .field("max_connections", &self.max_connections)
             .finish_non_exhaustive()
     }
 }
@@ -86,6 +101,7 @@ pub struct GcsBuilder {
 
     http_client: Option<HttpClient>,
     customed_token_loader: Option<Box<dyn GoogleTokenLoad>>,
+    request_timeout: Option<Duration>,
 }
 
 impl Debug for GcsBuilder {
@@ -166,6 +182,61 @@ impl GcsBuilder {
         self
     }
 
+    /// Enable GKE Workload Identity authentication.
+    ///
+    /// If enabled, we will not load credentials from env or well-known
+    /// location. Instead, short-lived access tokens will be fetched from
+    /// the GKE metadata server for the configured `service_account`
+    /// (`default` if not set) and cached until they expire.
+    ///
+    /// This option conflicts with `credential` and `credential_path`.
+    pub fn workload_identity(&mut self, enabled: bool) -> &mut Self {
+        self.config.workload_identity = enabled;
+        self
+    }
+
+    /// Check that the bucket exists before sending the first operation.
+    ///
+    /// Default to `false`.
+    ///
+    /// If enabled, operations will return `NotFound` error with a clear
+    /// message instead of confusing 404 errors on objects when the bucket
+    /// doesn't exist. The check is sent lazily since `build` can't send
+    /// requests, and only once per backend.
+    pub fn validate_bucket(&mut self, enabled: bool) -> &mut Self {
+        self.config.validate_bucket = enabled;
+        self
+    }
+
+    /// Set the max number of connections to gcs.
+    ///
+    /// Default to unlimited.
+    ///
+    /// # Notes
+    ///
+    /// Requests will wait for a free connection once the limit is reached,
+    /// a connection is counted until the response of its request has been
+    /// received. The idle connections kept in the pool are bounded too
+    /// unless a custom `http_client` is specified.
+    pub fn max_connections(&mut self, max_connections: usize) -> &mut Self {
+        self.config.max_connections = Some(max_connections);
+        self
+    }
+
+    /// Set the timeout of every request sent to gcs.
+    ///
+    /// Default to 120 seconds.
+    ///
+    /// # Notes
+    ///
+    /// The timeout covers sending the request body and receiving the
+    /// response headers, so it should be large enough to upload a whole
+    /// chunk of writes.
+    pub fn request_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -212,6 +283,23 @@ impl GcsBuilder {
         };
         self
     }
+
+    /// Set the Cloud KMS key used to encrypt objects written by this backend.
+    ///
+    /// The value is the full resource name of the key, for example:
+    /// `projects/my-project/locations/us/keyRings/my-ring/cryptoKeys/my-key`.
+    ///
+    /// Objects encrypted with customer-managed encryption keys (CMEK) are
+    /// decrypted transparently by GCS, so reading them doesn't require this
+    /// setting.
+    ///
+    /// Reference: [Use customer-managed encryption keys](https://cloud.google.com/storage/docs/encryption/using-customer-managed-keys)
+    pub fn kms_key_name(&mut self, kms_key_name: &str) -> &mut Self {
+        if !kms_key_name.is_empty() {
+            self.config.kms_key_name = Some(kms_key_name.to_string())
+        };
+        self
+    }
 }
 
 impl Builder for GcsBuilder {
@@ -244,12 +332,25 @@ impl Builder for GcsBuilder {
             ),
         }?;
 
-        // TODO: server side encryption
+        if self.config.max_connections == Some(0) {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "max_connections must be positive")
+                    .with_operation("Builder::build")
+                    .with_context("service", Scheme::Gcs),
+            );
+        }
 
         let client = if let Some(client) = self.http_client.take() {
             client
         } else {
-            HttpClient::new().map_err(|err| {
+            #[allow(unused_mut)]
+            let mut builder = reqwest::ClientBuilder::new();
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Some(max_connections) = self.config.max_connections {
+                builder = builder.pool_max_idle_per_host(max_connections);
+            }
+
+            HttpClient::build(builder).map_err(|err| {
                 err.with_operation("Builder::build")
                     .with_context("service", Scheme::Gcs)
             })?
@@ -262,7 +363,24 @@ impl Builder for GcsBuilder {
             .unwrap_or_else(|| DEFAULT_GCS_ENDPOINT.to_string());
         debug!("backend use endpoint: {endpoint}");
 
+        if self.config.workload_identity
+            && (self.config.credential.is_some() || self.config.credential_path.is_some())
+        {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "workload_identity can't be used with credential or credential_path",
+            )
+            .with_operation("Builder::build")
+            .with_context("service", Scheme::Gcs));
+        }
+
         let mut cred_loader = GoogleCredentialLoader::default();
+        // Token loader will fall back to the metadata server if there is no
+        // credential, which is how GKE Workload Identity serves tokens.
+        if self.config.workload_identity {
+            cred_loader = cred_loader.with_disable_env();
+            cred_loader = cred_loader.with_disable_well_known_location();
+        }
         if let Some(cred) = &self.config.credential {
             cred_loader = cred_loader.with_content(cred);
         }
@@ -305,6 +423,14 @@ impl Builder for GcsBuilder {
                 credential_loader: cred_loader,
                 predefined_acl: self.config.predefined_acl.clone(),
                 default_storage_class: self.config.default_storage_class.clone(),
+                kms_key_name: self.config.kms_key_name.clone(),
+                validate_bucket: self.config.validate_bucket,
+                bucket_validated: OnceCell::new(),
+                connections: self
+                    .config
+                    .max_connections
+                    .map(|v| Arc::new(Semaphore::new(v))),
+                request_timeout: self.request_timeout.unwrap_or(DEFAULT_REQUEST_TIMEOUT),
             }),
         };
 
@@ -337,23 +463,29 @@ impl Accessor for GcsBackend {
                 stat: true,
                 stat_with_if_match: true,
                 stat_with_if_none_match: true,
+                stat_with_version: true,
 
                 read: true,
                 read_can_next: true,
                 read_with_range: true,
                 read_with_if_match: true,
                 read_with_if_none_match: true,
+                read_with_version: true,
 
                 write: true,
                 write_can_empty: true,
                 write_can_multi: true,
+                write_can_append: true,
                 write_with_content_type: true,
+                write_with_content_md5: true,
+                write_with_custom_metadata: true,
+                write_with_acl: true,
                 // The buffer size should be a multiple of 256 KiB (256 x 1024 bytes), unless it's the last chunk that completes the upload.
                 // Larger chunk sizes typically make uploads faster, but note that there's a tradeoff between speed and memory usage.
                 // It's recommended that you use at least 8 MiB for the chunk size.
                 //
                 // Reference: [Perform resumable uploads](https://cloud.google.com/storage/docs/performing-resumable-uploads)
-                write_multi_align_size: Some(256 * 1024 * 1024),
+                write_multi_align_size: Some(256 * 1024),
 
                 delete: true,
                 copy: true,
@@ -363,6 +495,8 @@ impl Accessor for GcsBackend {
                 list_with_start_after: true,
                 list_with_recursive: true,
 
+                list_buckets: true,
+
                 batch: true,
                 batch_max_operations: Some(100),
                 presign: true,
@@ -376,6 +510,8 @@ impl Accessor for GcsBackend {
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        self.core.ensure_bucket_exists().await?;
+
         let resp = self.core.gcs_get_object_metadata(path, &args).await?;
 
         if !resp.status().is_success() {
@@ -391,6 +527,9 @@ impl Accessor for GcsBackend {
 
         m.set_etag(&meta.etag);
         m.set_content_md5(&meta.md5_hash);
+        if !meta.generation.is_empty() {
+            m.set_version(&meta.generation);
+        }
 
         let size = meta
             .size
@@ -402,11 +541,14 @@ impl Accessor for GcsBackend {
         }
 
         m.set_last_modified(parse_datetime_from_rfc3339(&meta.updated)?);
+        m.set_custom_metadata(meta.metadata);
 
         Ok(RpStat::new(m))
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        self.core.ensure_bucket_exists().await?;
+
         let resp = self.core.gcs_get_object(path, &args).await?;
 
         if resp.status().is_success() {
@@ -420,14 +562,22 @@ impl Accessor for GcsBackend {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.core.ensure_bucket_exists().await?;
+
         let concurrent = args.concurrent();
-        let w = GcsWriter::new(self.core.clone(), path, args);
-        let w = oio::RangeWriter::new(w, concurrent);
+        let w = GcsWriter::new(self.core.clone(), path, args.clone());
+        let w = if args.append() {
+            GcsWriters::Two(GcsAppendWriter::new(w))
+        } else {
+            GcsWriters::One(oio::RangeWriter::new(w, concurrent))
+        };
 
         Ok((RpWrite::default(), w))
     }
 
     async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+        self.core.ensure_bucket_exists().await?;
+
         let resp = self.core.gcs_delete_object(path).await?;
 
         // deleting not existing objects is ok
@@ -439,6 +589,8 @@ impl Accessor for GcsBackend {
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        self.core.ensure_bucket_exists().await?;
+
         let l = GcsLister::new(
             self.core.clone(),
             path,
@@ -451,6 +603,8 @@ impl Accessor for GcsBackend {
     }
 
     async fn copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
+        self.core.ensure_bucket_exists().await?;
+
         let resp = self.core.gcs_copy_object(from, to).await?;
 
         if resp.status().is_success() {
@@ -484,7 +638,15 @@ impl Accessor for GcsBackend {
         )))
     }
 
+    async fn list_buckets(&self, args: OpListBuckets) -> Result<RpListBuckets> {
+        let buckets = self.core.list_buckets(args.project()).await?;
+
+        Ok(RpListBuckets::new(buckets))
+    }
+
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        self.core.ensure_bucket_exists().await?;
+
         let ops = args.into_operation();
         if ops.len() > 100 {
             return Err(Error::new(
@@ -568,6 +730,14 @@ struct GetObjectJsonResponse {
     ///
     /// For example: `"contentType": "image/png",`
     content_type: String,
+    /// Generation of this object, used as the version.
+    ///
+    /// For example: `"generation": "1660563214863653"`
+    generation: String,
+    /// User defined metadata of this object.
+    ///
+    /// For example: `"metadata": {"location": "earth"}`
+    metadata: HashMap<String, String>,
 }
 
 #[cfg(test)]
@@ -593,7 +763,10 @@ mod tests {
   "etag": "CKWasoTgyPkCEAE=",
   "timeCreated": "2022-08-15T11:33:34.866Z",
   "updated": "2022-08-15T11:33:34.866Z",
-  "timeStorageClassUpdated": "2022-08-15T11:33:34.866Z"
+  "timeStorageClassUpdated": "2022-08-15T11:33:34.866Z",
+  "metadata": {
+    "location": "earth"
+  }
 }"#;
 
         let meta: GetObjectJsonResponse =
@@ -604,5 +777,22 @@ mod tests {
         assert_eq!(meta.md5_hash, "fHcEH1vPwA6eTPqxuasXcg==");
         assert_eq!(meta.etag, "CKWasoTgyPkCEAE=");
         assert_eq!(meta.content_type, "image/png");
+        assert_eq!(meta.generation, "1660563214863653");
+        assert_eq!(
+            meta.metadata,
+            HashMap::from([("location".to_string(), "earth".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_build_with_workload_identity_and_credential() {
+        let mut builder = GcsBuilder::default();
+        builder
+            .bucket("test")
+            .credential_path("/tmp/credential.json")
+            .workload_identity(true);
+
+        let err = builder.build().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
     }
 }
@@ -19,8 +19,10 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Write;
+use std::sync::Arc;
 use std::time::Duration;
 
+use backon::BackoffBuilder;
 use backon::ExponentialBuilder;
 use backon::Retryable;
 use http::header::CONTENT_LENGTH;
@@ -31,6 +33,8 @@ use http::header::IF_MATCH;
 use http::header::IF_NONE_MATCH;
 use http::Request;
 use http::Response;
+use http::StatusCode;
+use log::debug;
 use once_cell::sync::Lazy;
 use reqsign::GoogleCredential;
 use reqsign::GoogleCredentialLoader;
@@ -38,12 +42,24 @@ use reqsign::GoogleSigner;
 use reqsign::GoogleToken;
 use reqsign::GoogleTokenLoader;
 use serde::Deserialize;
+use serde::Serialize;
 use serde_json::json;
+use tokio::sync::OnceCell;
+use tokio::sync::Semaphore;
 
+use super::error::parse_error;
 use super::uri::percent_encode_path;
 use crate::raw::*;
 use crate::*;
 
+const X_GOOG_HASH: &str = "x-goog-hash";
+
+/// The max expiration of signed URLs allowed by gcs.
+const MAX_PRESIGN_EXPIRE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// The default timeout of every request sent to gcs.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
 pub struct GcsCore {
     pub endpoint: String,
     pub bucket: String,
@@ -56,6 +72,14 @@ pub struct GcsCore {
 
     pub predefined_acl: Option<String>,
     pub default_storage_class: Option<String>,
+    pub kms_key_name: Option<String>,
+
+    pub validate_bucket: bool,
+    pub bucket_validated: OnceCell<()>,
+
+    /// Limit the number of connections if max_connections is set.
+    pub connections: Option<Arc<Semaphore>>,
+    pub request_timeout: Duration,
 }
 
 impl Debug for GcsCore {
@@ -123,6 +147,14 @@ impl GcsCore {
     }
 
     pub async fn sign_query<T>(&self, req: &mut Request<T>, duration: Duration) -> Result<()> {
+        if duration > MAX_PRESIGN_EXPIRE {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "gcs signed url can't expire later than 7 days",
+            )
+            .with_context("expire", format!("{duration:?}")));
+        }
+
         let cred = self.load_credential()?;
 
         self.signer
@@ -142,7 +174,92 @@ impl GcsCore {
 
     #[inline]
     pub async fn send(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
-        self.client.send(req).await
+        // Stream bodies can't be sent again, so requests with them will
+        // not be retried.
+        let (parts, body) = req.into_parts();
+        if matches!(body, AsyncBody::Stream(_)) {
+            return self
+                .send_with_timeout(Request::from_parts(parts, body), 0)
+                .await;
+        }
+
+        let mut backoff = BACKOFF.build();
+        let mut retries = 0;
+        loop {
+            let mut req = Request::builder()
+                .method(parts.method.clone())
+                .uri(parts.uri.clone())
+                .version(parts.version)
+                .body(clone_body(&body))
+                .map_err(new_request_build_error)?;
+            *req.headers_mut() = parts.headers.clone();
+
+            let resp = self.send_with_timeout(req, retries).await?;
+            if !is_server_error(resp.status()) {
+                return Ok(resp);
+            }
+            let Some(dur) = backoff.next() else {
+                return Ok(resp);
+            };
+
+            retries += 1;
+            debug!(
+                "gcs request got {}, retrying in {dur:?}, retries: {retries}",
+                resp.status()
+            );
+            resp.into_body().consume().await?;
+            tokio::time::sleep(dur).await;
+        }
+    }
+
+    /// Send the request without retrying on server errors.
+    ///
+    /// Used by the requests that are not idempotent.
+    pub async fn send_once(&self, req: Request<AsyncBody>) -> Result<Response<IncomingAsyncBody>> {
+        self.send_with_timeout(req, 0).await
+    }
+
+    async fn send_with_timeout(
+        &self,
+        req: Request<AsyncBody>,
+        retries: usize,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        // Hold the permit until the response has been received, it's
+        // acquired per attempt so that backoff sleeps don't hold it.
+        let _permit = match &self.connections {
+            Some(s) => Some(s.acquire().await.expect("semaphore must be valid")),
+            None => None,
+        };
+
+        tokio::time::timeout(self.request_timeout, self.client.send(req))
+            .await
+            .map_err(|_| {
+                Error::new(ErrorKind::Unexpected, "gcs request timed out")
+                    .with_context("timeout", format!("{:?}", self.request_timeout))
+                    .with_context("retries", retries.to_string())
+                    .set_temporary()
+            })?
+    }
+}
+
+/// GCS may return these errors for transient failures.
+fn is_server_error(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Clone a body that is not a stream.
+fn clone_body(body: &AsyncBody) -> AsyncBody {
+    match body {
+        AsyncBody::Empty => AsyncBody::Empty,
+        AsyncBody::Bytes(bs) => AsyncBody::Bytes(bs.clone()),
+        AsyncBody::ChunkedBytes(bs) => AsyncBody::ChunkedBytes(bs.clone()),
+        AsyncBody::Stream(_) => unreachable!("stream body can't be cloned"),
     }
 }
 
@@ -150,12 +267,16 @@ impl GcsCore {
     pub fn gcs_get_object_request(&self, path: &str, args: &OpRead) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!(
+        let mut url = format!(
             "{}/storage/v1/b/{}/o/{}?alt=media",
             self.endpoint,
             self.bucket,
             percent_encode_path(&p)
         );
+        if let Some(version) = args.version() {
+            write!(url, "&generation={}", percent_encode_path(version))
+                .expect("write into string must succeed");
+        }
 
         let mut req = Request::get(&url);
 
@@ -184,7 +305,11 @@ impl GcsCore {
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!("{}/{}/{}", self.endpoint, self.bucket, p);
+        let mut url = format!("{}/{}/{}", self.endpoint, self.bucket, p);
+        if let Some(version) = args.version() {
+            write!(url, "?generation={}", percent_encode_path(version))
+                .expect("write into string must succeed");
+        }
 
         let mut req = Request::get(&url);
 
@@ -232,28 +357,40 @@ impl GcsCore {
         if let Some(cache_control) = op.cache_control() {
             metadata.insert("cacheControl", cache_control);
         }
+        // GCS will verify the uploaded content against the given md5.
+        if let Some(content_md5) = op.content_md5() {
+            metadata.insert("md5Hash", content_md5);
+        }
+
+        // User defined metadata can only be sent in multipart upload.
+        let is_multipart = !metadata.is_empty() || !op.custom_metadata().is_empty();
 
         let mut url = format!(
             "{}/upload/storage/v1/b/{}/o?uploadType={}&name={}",
             self.endpoint,
             self.bucket,
-            if metadata.is_empty() {
-                "media"
-            } else {
-                "multipart"
-            },
+            if is_multipart { "multipart" } else { "media" },
             percent_encode_path(&p)
         );
 
-        if let Some(acl) = &self.predefined_acl {
+        if let Some(acl) = op.acl().or(self.predefined_acl.as_deref()) {
             write!(&mut url, "&predefinedAcl={}", acl).unwrap();
         }
 
+        if let Some(kms_key_name) = &self.kms_key_name {
+            write!(
+                &mut url,
+                "&kmsKeyName={}",
+                percent_encode_path(kms_key_name)
+            )
+            .expect("write into string must succeed");
+        }
+
         let mut req = Request::post(&url);
 
         req = req.header(CONTENT_LENGTH, size.unwrap_or_default());
 
-        if metadata.is_empty() {
+        if !is_multipart {
             if let Some(content_type) = op.content_type() {
                 req = req.header(CONTENT_TYPE, content_type);
             }
@@ -261,6 +398,11 @@ impl GcsCore {
             let req = req.body(body).map_err(new_request_build_error)?;
             Ok(req)
         } else {
+            let mut metadata = json!(metadata);
+            if !op.custom_metadata().is_empty() {
+                metadata["metadata"] = json!(op.custom_metadata());
+            }
+
             let mut multipart = Multipart::new();
 
             multipart = multipart.part(
@@ -269,7 +411,7 @@ impl GcsCore {
                         CONTENT_TYPE,
                         "application/json; charset=UTF-8".parse().unwrap(),
                     )
-                    .content(json!(metadata).to_string()),
+                    .content(metadata.to_string()),
             );
 
             let mut media_part = FormDataPart::new("media");
@@ -320,7 +462,7 @@ impl GcsCore {
             req = req.header(CONTENT_TYPE, content_type);
         }
 
-        if let Some(acl) = &self.predefined_acl {
+        if let Some(acl) = args.acl().or(self.predefined_acl.as_deref()) {
             req = req.header("x-goog-acl", acl);
         }
 
@@ -328,6 +470,14 @@ impl GcsCore {
             req = req.header("x-goog-storage-class", storage_class);
         }
 
+        if let Some(kms_key_name) = &self.kms_key_name {
+            req = req.header("x-goog-encryption-kms-key-name", kms_key_name);
+        }
+
+        for (key, value) in args.custom_metadata() {
+            req = req.header(format!("x-goog-meta-{key}"), value);
+        }
+
         let req = req.body(body).map_err(new_request_build_error)?;
 
         Ok(req)
@@ -336,12 +486,16 @@ impl GcsCore {
     pub fn gcs_head_object_request(&self, path: &str, args: &OpStat) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!(
+        let mut url = format!(
             "{}/storage/v1/b/{}/o/{}",
             self.endpoint,
             self.bucket,
             percent_encode_path(&p)
         );
+        if let Some(version) = args.version() {
+            write!(url, "?generation={}", percent_encode_path(version))
+                .expect("write into string must succeed");
+        }
 
         let mut req = Request::get(&url);
 
@@ -368,7 +522,11 @@ impl GcsCore {
     ) -> Result<Request<AsyncBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!("{}/{}/{}", self.endpoint, self.bucket, p);
+        let mut url = format!("{}/{}/{}", self.endpoint, self.bucket, p);
+        if let Some(version) = args.version() {
+            write!(url, "?generation={}", percent_encode_path(version))
+                .expect("write into string must succeed");
+        }
 
         let mut req = Request::head(&url);
 
@@ -399,6 +557,101 @@ impl GcsCore {
         self.send(req).await
     }
 
+    /// Check whether the configured bucket exists.
+    pub async fn bucket_exists(&self) -> Result<bool> {
+        let url = format!(
+            "{}/storage/v1/b/{}",
+            self.endpoint,
+            percent_encode_path(&self.bucket)
+        );
+
+        let mut req = Request::get(&url)
+            .body(AsyncBody::Empty)
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+
+        let resp = self.send(req).await?;
+        match resp.status() {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(true)
+            }
+            StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                Ok(false)
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// List the names of all buckets accessible under given project.
+    ///
+    /// All pages will be fetched before returning.
+    pub async fn list_buckets(&self, project: &str) -> Result<Vec<String>> {
+        let mut buckets = Vec::new();
+        let mut page_token = String::new();
+
+        loop {
+            let mut url = format!(
+                "{}/storage/v1/b?project={}",
+                self.endpoint,
+                percent_encode_path(project)
+            );
+            if !page_token.is_empty() {
+                write!(url, "&pageToken={}", percent_encode_path(&page_token))
+                    .expect("write into string must succeed");
+            }
+
+            let mut req = Request::get(&url)
+                .body(AsyncBody::Empty)
+                .map_err(new_request_build_error)?;
+
+            self.sign(&mut req).await?;
+
+            let resp = self.send(req).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+
+            let bs = resp.into_body().bytes().await?;
+            let output: ListBucketsResponse =
+                serde_json::from_slice(&bs).map_err(new_json_deserialize_error)?;
+
+            buckets.extend(output.items.into_iter().map(|item| item.name));
+            match output.next_page_token {
+                Some(token) if !token.is_empty() => page_token = token,
+                _ => return Ok(buckets),
+            }
+        }
+    }
+
+    /// Make sure the bucket exists if `validate_bucket` is enabled.
+    ///
+    /// The check will only be sent once, following calls return directly
+    /// after it succeeded.
+    pub async fn ensure_bucket_exists(&self) -> Result<()> {
+        if !self.validate_bucket {
+            return Ok(());
+        }
+
+        self.bucket_validated
+            .get_or_try_init(|| async {
+                if self.bucket_exists().await? {
+                    Ok(())
+                } else {
+                    Err(
+                        Error::new(ErrorKind::BucketNotFound, "bucket doesn't exist")
+                            .with_context("service", Scheme::Gcs)
+                            .with_context("bucket", &self.bucket),
+                    )
+                }
+            })
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn gcs_delete_object(&self, path: &str) -> Result<Response<IncomingAsyncBody>> {
         let mut req = self.gcs_delete_object_request(path)?;
 
@@ -452,7 +705,7 @@ impl GcsCore {
         let source = build_abs_path(&self.root, from);
         let dest = build_abs_path(&self.root, to);
 
-        let req_uri = format!(
+        let mut req_uri = format!(
             "{}/storage/v1/b/{}/o/{}/copyTo/b/{}/o/{}",
             self.endpoint,
             self.bucket,
@@ -460,6 +713,14 @@ impl GcsCore {
             self.bucket,
             percent_encode_path(&dest)
         );
+        if let Some(kms_key_name) = &self.kms_key_name {
+            write!(
+                req_uri,
+                "?destinationKmsKeyName={}",
+                percent_encode_path(kms_key_name)
+            )
+            .expect("write into string must succeed");
+        }
 
         let mut req = Request::post(req_uri)
             .header(CONTENT_LENGTH, 0)
@@ -470,6 +731,56 @@ impl GcsCore {
         self.send(req).await
     }
 
+    /// Compose the source objects into the destination object.
+    ///
+    /// GCS allows at most 32 source objects in one compose request.
+    ///
+    /// Reference: [Objects: compose](https://cloud.google.com/storage/docs/json_api/v1/objects/compose)
+    pub async fn gcs_compose_object(
+        &self,
+        sources: &[String],
+        to: &str,
+        op: &OpWrite,
+    ) -> Result<Response<IncomingAsyncBody>> {
+        let dest = build_abs_path(&self.root, to);
+
+        let mut url = format!(
+            "{}/storage/v1/b/{}/o/{}/compose",
+            self.endpoint,
+            self.bucket,
+            percent_encode_path(&dest)
+        );
+        if let Some(kms_key_name) = &self.kms_key_name {
+            write!(url, "?kmsKeyName={}", percent_encode_path(kms_key_name))
+                .expect("write into string must succeed");
+        }
+
+        let req = ComposeRequest {
+            source_objects: sources
+                .iter()
+                .map(|p| ComposeSourceObject {
+                    name: build_abs_path(&self.root, p),
+                })
+                .collect(),
+            destination: ComposeDestination {
+                content_type: op.content_type().map(|v| v.to_string()),
+                cache_control: op.cache_control().map(|v| v.to_string()),
+                storage_class: self.default_storage_class.clone(),
+            },
+        };
+        let bs = serde_json::to_vec(&req).map_err(new_json_serialize_error)?;
+
+        let mut req = Request::post(&url)
+            .header(CONTENT_LENGTH, bs.len())
+            .header(CONTENT_TYPE, "application/json")
+            .body(AsyncBody::Bytes(bs.into()))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        // Compose is not idempotent, the target could be composed twice.
+        self.send_once(req).await
+    }
+
     pub async fn gcs_list_objects(
         &self,
         path: &str,
@@ -521,12 +832,20 @@ impl GcsCore {
     pub async fn gcs_initiate_resumable_upload(
         &self,
         path: &str,
+        op: &OpWrite,
     ) -> Result<Response<IncomingAsyncBody>> {
         let p = build_abs_path(&self.root, path);
-        let url = format!(
+        let mut url = format!(
             "{}/upload/storage/v1/b/{}/o?uploadType=resumable&name={}",
             self.endpoint, self.bucket, p
         );
+        if let Some(acl) = op.acl().or(self.predefined_acl.as_deref()) {
+            write!(url, "&predefinedAcl={}", acl).expect("write into string must succeed");
+        }
+        if let Some(kms_key_name) = &self.kms_key_name {
+            write!(url, "&kmsKeyName={}", percent_encode_path(kms_key_name))
+                .expect("write into string must succeed");
+        }
 
         let mut req = Request::post(&url)
             .header(CONTENT_LENGTH, 0)
@@ -534,7 +853,8 @@ impl GcsCore {
             .map_err(new_request_build_error)?;
 
         self.sign(&mut req).await?;
-        self.send(req).await
+        // Every initiate creates a new upload session, don't retry it.
+        self.send_once(req).await
     }
 
     pub fn gcs_upload_in_resumable_upload(
@@ -563,9 +883,18 @@ impl GcsCore {
         location: &str,
         written: u64,
         size: u64,
+        op: &OpWrite,
         body: AsyncBody,
     ) -> Result<Response<IncomingAsyncBody>> {
-        let mut req = Request::post(location)
+        let mut req = Request::post(location);
+
+        // GCS will verify the whole object against the given md5 while
+        // completing the upload.
+        if let Some(content_md5) = op.content_md5() {
+            req = req.header(X_GOOG_HASH, format!("md5={content_md5}"));
+        }
+
+        let mut req = req
             .header(CONTENT_LENGTH, size)
             .header(
                 CONTENT_RANGE,
@@ -616,6 +945,51 @@ pub struct ListResponse {
     pub items: Vec<ListResponseItem>,
 }
 
+/// Response JSON from GCS list buckets API.
+///
+/// refer to https://cloud.google.com/storage/docs/json_api/v1/buckets/list for details
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ListBucketsResponse {
+    /// The continuation token.
+    ///
+    /// If this is the last page of results, then no continuation token is returned.
+    pub next_page_token: Option<String>,
+    /// The list of buckets.
+    pub items: Vec<ListBucketsResponseItem>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ListBucketsResponseItem {
+    pub name: String,
+}
+
+/// Request JSON for GCS compose objects API.
+#[derive(Default, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComposeRequest {
+    pub source_objects: Vec<ComposeSourceObject>,
+    pub destination: ComposeDestination,
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComposeSourceObject {
+    pub name: String,
+}
+
+#[derive(Default, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComposeDestination {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_class: Option<String>,
+}
+
 #[derive(Default, Debug, Eq, PartialEq, Deserialize)]
 #[serde(default, rename_all = "camelCase")]
 pub struct ListResponseItem {
@@ -771,4 +1145,67 @@ mod tests {
         assert_eq!(output.items[1].updated, "2022-08-15T11:33:34.886Z");
         assert_eq!(output.prefixes, vec!["dir/", "test/"])
     }
+
+    #[test]
+    fn test_deserialize_list_buckets_response() {
+        let content = r#"
+    {
+  "kind": "storage#buckets",
+  "nextPageToken": "CgZidWNrZXQ=",
+  "items": [
+    {
+      "kind": "storage#bucket",
+      "id": "example",
+      "name": "example",
+      "projectNumber": "123456789",
+      "location": "US",
+      "storageClass": "STANDARD"
+    },
+    {
+      "kind": "storage#bucket",
+      "id": "example-logs",
+      "name": "example-logs",
+      "projectNumber": "123456789",
+      "location": "US",
+      "storageClass": "STANDARD"
+    }
+  ]
+}
+    "#;
+
+        let output: ListBucketsResponse =
+            serde_json::from_str(content).expect("JSON deserialize must succeed");
+        assert_eq!(output.next_page_token.as_deref(), Some("CgZidWNrZXQ="));
+        let names: Vec<_> = output.items.into_iter().map(|item| item.name).collect();
+        assert_eq!(names, vec!["example", "example-logs"]);
+
+        let output: ListBucketsResponse = serde_json::from_str(r#"{"kind": "storage#buckets"}"#)
+            .expect("JSON deserialize must succeed");
+        assert!(output.next_page_token.is_none());
+        assert!(output.items.is_empty());
+    }
+
+    #[test]
+    fn test_serialize_compose_request() {
+        let req = ComposeRequest {
+            source_objects: vec![
+                ComposeSourceObject {
+                    name: "a".to_string(),
+                },
+                ComposeSourceObject {
+                    name: "b".to_string(),
+                },
+            ],
+            destination: ComposeDestination {
+                content_type: Some("text/plain".to_string()),
+                ..Default::default()
+            },
+        };
+
+        let output = serde_json::to_string(&req).expect("JSON serialize must succeed");
+        assert_eq!(
+            output,
+            r#"{"sourceObjects":[{"name":"a"},{"name":"b"}],"destination":{"contentType":"text/plain"}}"#
+        )
+    }
 }
@@ -54,7 +54,7 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     let (parts, body) = resp.into_parts();
     let bs = body.bytes().await?;
 
-    let (kind, retryable) = match parts.status {
+    let (mut kind, retryable) = match parts.status {
         StatusCode::NOT_FOUND => (ErrorKind::NotFound, false),
         StatusCode::FORBIDDEN => (ErrorKind::PermissionDenied, false),
         StatusCode::PRECONDITION_FAILED | StatusCode::NOT_MODIFIED => {
@@ -64,11 +64,20 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
         | StatusCode::BAD_GATEWAY
         | StatusCode::SERVICE_UNAVAILABLE
         | StatusCode::GATEWAY_TIMEOUT => (ErrorKind::Unexpected, true),
+        StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
         _ => (ErrorKind::Unexpected, false),
     };
 
     let message = match de::from_slice::<GcsErrorResponse>(&bs) {
-        Ok(gcs_err) => format!("{gcs_err:?}"),
+        Ok(gcs_err) => {
+            if parts.status == StatusCode::BAD_REQUEST && is_hash_mismatch(&gcs_err.error) {
+                kind = ErrorKind::DataIntegrityCheckFailed;
+            }
+            if parts.status == StatusCode::NOT_FOUND && is_bucket_not_found(&gcs_err.error) {
+                kind = ErrorKind::BucketNotFound;
+            }
+            format!("{gcs_err:?}")
+        }
         Err(_) => String::from_utf8_lossy(&bs).into_owned(),
     };
 
@@ -83,8 +92,26 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
     Ok(err)
 }
 
+/// GCS returns `400 Bad Request` with message like `Provided MD5 hash "..."
+/// doesn't match calculated MD5 hash "..."` if the uploaded content doesn't
+/// match the given hash.
+fn is_hash_mismatch(err: &GcsError) -> bool {
+    err.message.contains("doesn't match calculated")
+}
+
+/// GCS returns `404 Not Found` for both missing objects and missing buckets,
+/// the latter carries reason `notFound` with message `The specified bucket
+/// does not exist.` while the former's message is `No such object: ...`.
+fn is_bucket_not_found(err: &GcsError) -> bool {
+    err.errors
+        .iter()
+        .any(|e| e.reason == "notFound" && e.message.contains("bucket does not exist"))
+}
+
 #[cfg(test)]
 mod tests {
+    use futures::stream;
+
     use super::*;
 
     #[test]
@@ -120,4 +147,70 @@ mod tests {
         assert_eq!(out.error.errors[0].location_type, "header");
         assert_eq!(out.error.errors[0].location, "Authorization");
     }
+
+    #[tokio::test]
+    async fn test_parse_error_rate_limited() {
+        let body = IncomingAsyncBody::new(
+            Box::new(oio::into_stream(stream::iter(vec![
+                Ok(bytes::Bytes::new()),
+            ]))),
+            None,
+        );
+        let resp = Response::builder()
+            .status(StatusCode::TOO_MANY_REQUESTS)
+            .body(body)
+            .unwrap();
+
+        let err = parse_error(resp).await.unwrap();
+
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+        assert!(err.is_temporary());
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_hash_mismatch() {
+        let bs = bytes::Bytes::from(
+            r#"{"error":{"code":400,"message":"Provided MD5 hash \"Ojk9c3dhfxgoKVVHYwFbHQ==\" doesn't match calculated MD5 hash \"i3lZ6DvqYRWRk6eFqRA+Ug==\"."}}"#,
+        );
+        let body =
+            IncomingAsyncBody::new(Box::new(oio::into_stream(stream::iter(vec![Ok(bs)]))), None);
+        let resp = Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(body)
+            .unwrap();
+
+        let err = parse_error(resp).await.unwrap();
+
+        assert_eq!(err.kind(), ErrorKind::DataIntegrityCheckFailed);
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_bucket_not_found() {
+        let cases = [
+            (
+                r#"{"error":{"code":404,"message":"The specified bucket does not exist.","errors":[{"message":"The specified bucket does not exist.","domain":"global","reason":"notFound"}]}}"#,
+                ErrorKind::BucketNotFound,
+            ),
+            (
+                r#"{"error":{"code":404,"message":"No such object: bucket/path","errors":[{"message":"No such object: bucket/path","domain":"global","reason":"notFound"}]}}"#,
+                ErrorKind::NotFound,
+            ),
+        ];
+
+        for (input, expected) in cases {
+            let bs = bytes::Bytes::from(input);
+            let body = IncomingAsyncBody::new(
+                Box::new(oio::into_stream(stream::iter(vec![Ok(bs)]))),
+                None,
+            );
+            let resp = Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(body)
+                .unwrap();
+
+            let err = parse_error(resp).await.unwrap();
+
+            assert_eq!(err.kind(), expected);
+        }
+    }
 }
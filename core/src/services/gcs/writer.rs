@@ -15,17 +15,25 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::mem;
 use std::sync::Arc;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
 
 use async_trait::async_trait;
 use http::StatusCode;
+use uuid::Uuid;
 
 use super::core::GcsCore;
 use super::error::parse_error;
 use crate::raw::*;
 use crate::*;
 
-pub type GcsWriters = oio::RangeWriter<GcsWriter>;
+pub type GcsWriters = TwoWays<oio::RangeWriter<GcsWriter>, GcsAppendWriter>;
+
+/// The max number of source objects in one compose request.
+const COMPOSE_MAX_SOURCES: usize = 32;
 
 pub struct GcsWriter {
     core: Arc<GcsCore>,
@@ -41,6 +49,113 @@ impl GcsWriter {
             op,
         }
     }
+
+    /// Upload the content as a temporary object next to the target
+    /// object and return its path.
+    async fn write_temp(&self, size: u64, body: AsyncBody) -> Result<String> {
+        let path = format!("{}.{}.append", self.path, Uuid::new_v4());
+        self.insert(&path, size, &OpWrite::default(), body).await?;
+        Ok(path)
+    }
+
+    /// Insert an object at given path.
+    ///
+    /// The path is passed as is since `gcs_insert_object_request` will
+    /// percent encode it.
+    async fn insert(&self, path: &str, size: u64, op: &OpWrite, body: AsyncBody) -> Result<()> {
+        let mut req = self
+            .core
+            .gcs_insert_object_request(path, Some(size), op, body)?;
+
+        self.core.sign(&mut req).await?;
+
+        let resp = self.core.send(req).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::CREATED | StatusCode::OK => {
+                resp.into_body().consume().await?;
+                Ok(())
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// Compose the temporary objects into the target object in order.
+    ///
+    /// The existing content of target object will be kept at the front.
+    async fn compose(&self, temps: &[String]) -> Result<()> {
+        let resp = self
+            .core
+            .gcs_get_object_metadata(&self.path, &OpStat::default())
+            .await?;
+
+        let mut exist = match resp.status() {
+            StatusCode::OK => {
+                resp.into_body().consume().await?;
+                true
+            }
+            StatusCode::NOT_FOUND => {
+                resp.into_body().consume().await?;
+                false
+            }
+            _ => return Err(parse_error(resp).await?),
+        };
+
+        // Create an empty object for appending nothing.
+        if temps.is_empty() {
+            if exist {
+                return Ok(());
+            }
+            return self.insert(&self.path, 0, &self.op, AsyncBody::Empty).await;
+        }
+
+        let mut temps = temps;
+        while !temps.is_empty() {
+            let mut sources = Vec::with_capacity(COMPOSE_MAX_SOURCES);
+            if exist {
+                sources.push(self.path.clone());
+            }
+            let n = (COMPOSE_MAX_SOURCES - sources.len()).min(temps.len());
+            sources.extend_from_slice(&temps[..n]);
+            temps = &temps[n..];
+
+            let resp = self
+                .core
+                .gcs_compose_object(&sources, &self.path, &self.op)
+                .await?;
+
+            match resp.status() {
+                StatusCode::OK => resp.into_body().consume().await?,
+                _ => return Err(parse_error(resp).await?),
+            }
+            exist = true;
+        }
+
+        Ok(())
+    }
+
+    /// Delete all the temporary objects, returns the first error if any.
+    async fn cleanup(&self, temps: &[String]) -> Result<()> {
+        let mut res = Ok(());
+        for path in temps {
+            let resp = self.core.gcs_delete_object(path).await?;
+
+            match resp.status() {
+                StatusCode::NO_CONTENT | StatusCode::OK | StatusCode::NOT_FOUND => {
+                    resp.into_body().consume().await?
+                }
+                _ => {
+                    let err = parse_error(resp).await?;
+                    if res.is_ok() {
+                        res = Err(err);
+                    }
+                }
+            }
+        }
+        res
+    }
 }
 
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
@@ -70,7 +185,10 @@ impl oio::RangeWrite for GcsWriter {
     }
 
     async fn initiate_range(&self) -> Result<String> {
-        let resp = self.core.gcs_initiate_resumable_upload(&self.path).await?;
+        let resp = self
+            .core
+            .gcs_initiate_resumable_upload(&self.path, &self.op)
+            .await?;
         let status = resp.status();
 
         match status {
@@ -120,7 +238,7 @@ impl oio::RangeWrite for GcsWriter {
     ) -> Result<()> {
         let resp = self
             .core
-            .gcs_complete_resumable_upload(location, written, size, body)
+            .gcs_complete_resumable_upload(location, written, size, &self.op, body)
             .await?;
 
         let status = resp.status();
@@ -147,3 +265,140 @@ impl oio::RangeWrite for GcsWriter {
         }
     }
 }
+
+/// GcsAppendWriter implements append for gcs via the compose API.
+///
+/// GCS doesn't support appending to an existing object directly. Instead,
+/// every write will be uploaded as a temporary object, and all of them will
+/// be composed into the target object while closing. Temporary objects will
+/// be removed whether the compose succeeded or not.
+pub struct GcsAppendWriter {
+    w: Arc<GcsWriter>,
+    state: State,
+
+    temps: Vec<String>,
+    written: u64,
+}
+
+enum State {
+    Idle,
+    Write(BoxedStaticFuture<(usize, Result<String>)>),
+    Close(BoxedStaticFuture<Result<()>>),
+    Abort(BoxedStaticFuture<Result<()>>),
+}
+
+/// # Safety
+///
+/// wasm32 is a special target that we only have one event-loop for this state.
+unsafe impl Send for State {}
+
+/// # Safety
+///
+/// We will only take `&mut Self` reference for State.
+unsafe impl Sync for State {}
+
+impl GcsAppendWriter {
+    /// Create a new GcsAppendWriter.
+    pub fn new(w: GcsWriter) -> Self {
+        Self {
+            w: Arc::new(w),
+            state: State::Idle,
+            temps: Vec::new(),
+            written: 0,
+        }
+    }
+}
+
+impl oio::Write for GcsAppendWriter {
+    fn poll_write(&mut self, cx: &mut Context<'_>, bs: &dyn oio::WriteBuf) -> Poll<Result<usize>> {
+        loop {
+            match &mut self.state {
+                State::Idle => {
+                    let w = self.w.clone();
+                    let size = bs.remaining();
+                    let bs = bs.bytes(size);
+
+                    self.state = State::Write(Box::pin(async move {
+                        let res = w.write_temp(size as u64, AsyncBody::Bytes(bs)).await;
+
+                        (size, res)
+                    }));
+                }
+                State::Write(fut) => {
+                    let (size, res) = ready!(fut.as_mut().poll(cx));
+                    self.state = State::Idle;
+
+                    self.temps.push(res?);
+                    self.written += size as u64;
+                    return Poll::Ready(Ok(size));
+                }
+                State::Close(_) => {
+                    unreachable!("GcsAppendWriter must not go into State::Close during poll_write")
+                }
+                State::Abort(_) => {
+                    unreachable!("GcsAppendWriter must not go into State::Abort during poll_write")
+                }
+            }
+        }
+    }
+
+    fn poll_close(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        loop {
+            match &mut self.state {
+                State::Idle => {
+                    let w = self.w.clone();
+                    let temps = mem::take(&mut self.temps);
+
+                    self.state = State::Close(Box::pin(async move {
+                        let res = w.compose(&temps).await;
+                        let cleanup = w.cleanup(&temps).await;
+
+                        // Return the compose error first since it's the root cause.
+                        res.and(cleanup)
+                    }));
+                }
+                State::Close(fut) => {
+                    let res = ready!(fut.as_mut().poll(cx));
+                    self.state = State::Idle;
+
+                    return Poll::Ready(res);
+                }
+                State::Write(_) => {
+                    unreachable!("GcsAppendWriter must not go into State::Write during poll_close")
+                }
+                State::Abort(_) => {
+                    unreachable!("GcsAppendWriter must not go into State::Abort during poll_close")
+                }
+            }
+        }
+    }
+
+    fn poll_abort(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        loop {
+            match &mut self.state {
+                State::Idle => {
+                    let w = self.w.clone();
+                    let temps = mem::take(&mut self.temps);
+
+                    self.state = State::Abort(Box::pin(async move { w.cleanup(&temps).await }));
+                }
+                State::Abort(fut) => {
+                    let res = ready!(fut.as_mut().poll(cx));
+                    self.state = State::Idle;
+
+                    return Poll::Ready(res);
+                }
+                State::Write(_) => {
+                    unreachable!("GcsAppendWriter must not go into State::Write during poll_abort")
+                }
+                State::Close(_) => {
+                    unreachable!("GcsAppendWriter must not go into State::Close during poll_abort")
+                }
+            }
+        }
+    }
+
+    fn written_bytes(&self) -> u64 {
+        self.written
+    }
+}
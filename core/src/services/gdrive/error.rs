@@ -48,6 +48,7 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
         | StatusCode::GATEWAY_TIMEOUT
         // Gdrive sometimes return METHOD_NOT_ALLOWED for our requests for abuse detection.
         | StatusCode::METHOD_NOT_ALLOWED => (ErrorKind::Unexpected, true),
+        StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
         _ => (ErrorKind::Unexpected, false),
     };
 
@@ -37,6 +37,7 @@ pub async fn parse_error(resp: Response<IncomingAsyncBody>) -> Result<Error> {
         | StatusCode::GATEWAY_TIMEOUT
         // IPFS Gateway will return `408 REQUEST_TIMEOUT` while `ipfs resolve -r` failed.
         | StatusCode::REQUEST_TIMEOUT => (ErrorKind::Unexpected, true),
+        StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
         _ => (ErrorKind::Unexpected, false),
     };
 
@@ -56,7 +56,25 @@ pub use self::etcd::EtcdConfig;
 #[cfg(feature = "services-fs")]
 mod fs;
 #[cfg(feature = "services-fs")]
+pub use fs::FramedReader;
+#[cfg(feature = "services-fs")]
+pub use fs::FramedWriter;
+#[cfg(feature = "services-fs")]
 pub use fs::Fs;
+#[cfg(feature = "services-fs")]
+pub use fs::FsBackend;
+#[cfg(feature = "services-fs")]
+pub use fs::FsEvent;
+#[cfg(feature = "services-fs")]
+pub use fs::FsEventKind;
+#[cfg(feature = "services-fs")]
+pub use fs::FsReader;
+#[cfg(feature = "services-fs")]
+pub use fs::FsWatcher;
+#[cfg(feature = "services-fs")]
+pub use fs::FsWriter;
+#[cfg(feature = "services-fs")]
+pub use fs::MmapReader;
 
 #[cfg(feature = "services-ftp")]
 mod ftp;
@@ -67,6 +67,10 @@ pub struct Capability {
     pub stat_with_override_content_disposition: bool,
     /// if operator supports read with override content type.
     pub stat_with_override_content_type: bool,
+    /// If operator supports stat with checksum.
+    pub stat_with_checksum: bool,
+    /// If operator supports stat with version.
+    pub stat_with_version: bool,
 
     /// If operator supports read.
     pub read: bool,
@@ -86,6 +90,10 @@ pub struct Capability {
     pub read_with_override_content_disposition: bool,
     /// if operator supports read with override content type.
     pub read_with_override_content_type: bool,
+    /// If operator supports read with checksum.
+    pub read_with_checksum: bool,
+    /// If operator supports read with version.
+    pub read_with_version: bool,
 
     /// If operator supports write.
     pub write: bool,
@@ -95,12 +103,20 @@ pub struct Capability {
     pub write_can_empty: bool,
     /// If operator supports write by append.
     pub write_can_append: bool,
+    /// If operator supports write with if not exists.
+    pub write_with_if_not_exists: bool,
     /// If operator supports write with content type.
     pub write_with_content_type: bool,
     /// If operator supports write with content disposition.
     pub write_with_content_disposition: bool,
     /// If operator supports write with cache control.
     pub write_with_cache_control: bool,
+    /// If operator supports write with content md5.
+    pub write_with_content_md5: bool,
+    /// If operator supports write with user defined metadata.
+    pub write_with_custom_metadata: bool,
+    /// If operator supports write with acl.
+    pub write_with_acl: bool,
     /// write_multi_max_size is the max size that services support in write_multi.
     ///
     /// For example, AWS S3 supports 5GiB as max in write_multi.
@@ -130,6 +146,33 @@ pub struct Capability {
     /// If operator supports rename.
     pub rename: bool,
 
+    /// If operator supports hard link.
+    pub hard_link: bool,
+
+    /// If operator supports symlink.
+    pub symlink: bool,
+
+    /// If operator supports set permissions.
+    pub set_permissions: bool,
+
+    /// If operator supports statvfs.
+    pub statvfs: bool,
+
+    /// If operator supports truncate.
+    pub truncate: bool,
+
+    /// If operator supports fallocate.
+    pub fallocate: bool,
+
+    /// If operator supports rename without replacing existing file.
+    pub rename_noreplace: bool,
+
+    /// If operator supports setxattr, getxattr and listxattr.
+    pub xattr: bool,
+
+    /// If operator supports list buckets.
+    pub list_buckets: bool,
+
     /// If operator supports list.
     pub list: bool,
     /// If backend supports list with limit.
@@ -138,6 +181,8 @@ pub struct Capability {
     pub list_with_start_after: bool,
     /// If backend supports list with recursive.
     pub list_with_recursive: bool,
+    /// If backend supports list with skip hidden.
+    pub list_with_skip_hidden: bool,
 
     /// If operator supports presign.
     pub presign: bool,
@@ -184,6 +229,33 @@ impl Debug for Capability {
         if self.rename {
             s.push("Rename");
         }
+        if self.hard_link {
+            s.push("HardLink");
+        }
+        if self.symlink {
+            s.push("Symlink");
+        }
+        if self.set_permissions {
+            s.push("SetPermissions");
+        }
+        if self.statvfs {
+            s.push("Statvfs");
+        }
+        if self.truncate {
+            s.push("Truncate");
+        }
+        if self.fallocate {
+            s.push("Fallocate");
+        }
+        if self.rename_noreplace {
+            s.push("RenameNoreplace");
+        }
+        if self.xattr {
+            s.push("Xattr");
+        }
+        if self.list_buckets {
+            s.push("ListBuckets");
+        }
         if self.list {
             s.push("List");
         }
@@ -52,7 +52,7 @@ use crate::*;
 ///         EntryMode::DIR => {
 ///             println!("Handling dir {}", entry.path())
 ///         }
-///         EntryMode::Unknown => continue,
+///         EntryMode::Symlink | EntryMode::Unknown => continue,
 ///     }
 /// }
 /// # Ok(())
@@ -135,7 +135,7 @@ impl Entry {
     ///         EntryMode::DIR => {
     ///             println!("Handling dir {}", entry.path())
     ///         }
-    ///         EntryMode::Unknown => continue,
+    ///         EntryMode::Symlink | EntryMode::Unknown => continue,
     ///     }
     /// }
     /// # Ok(())
@@ -180,7 +180,7 @@ impl Entry {
     ///         EntryMode::DIR => {
     ///             println!("Handling dir {}", path)
     ///         }
-    ///         EntryMode::Unknown => continue,
+    ///         EntryMode::Symlink | EntryMode::Unknown => continue,
     ///     }
     /// }
     /// # Ok(())
@@ -60,6 +60,11 @@ pub enum ErrorKind {
     ConfigInvalid,
     /// The given path is not found.
     NotFound,
+    /// The bucket (or container) of the given path is not found.
+    ///
+    /// Services that can't tell a missing bucket from a missing path will
+    /// return [`ErrorKind::NotFound`] instead.
+    BucketNotFound,
     /// The given path doesn't have enough permission for this operation
     PermissionDenied,
     /// The given path is a directory.
@@ -105,6 +110,11 @@ pub enum ErrorKind {
     ///
     /// For example, user try to seek to a negative position
     InvalidInput,
+    /// The content doesn't match its checksum.
+    ///
+    /// For example, the md5 of content read from service is not the same
+    /// as the one returned by `stat`.
+    DataIntegrityCheckFailed,
 }
 
 impl ErrorKind {
@@ -127,6 +137,7 @@ impl From<ErrorKind> for &'static str {
             ErrorKind::Unsupported => "Unsupported",
             ErrorKind::ConfigInvalid => "ConfigInvalid",
             ErrorKind::NotFound => "NotFound",
+            ErrorKind::BucketNotFound => "BucketNotFound",
             ErrorKind::PermissionDenied => "PermissionDenied",
             ErrorKind::IsADirectory => "IsADirectory",
             ErrorKind::NotADirectory => "NotADirectory",
@@ -137,6 +148,7 @@ impl From<ErrorKind> for &'static str {
             ErrorKind::ContentTruncated => "ContentTruncated",
             ErrorKind::ContentIncomplete => "ContentIncomplete",
             ErrorKind::InvalidInput => "InvalidInput",
+            ErrorKind::DataIntegrityCheckFailed => "DataIntegrityCheckFailed",
         }
     }
 }
@@ -416,7 +428,7 @@ impl Error {
 impl From<Error> for io::Error {
     fn from(err: Error) -> Self {
         let kind = match err.kind() {
-            ErrorKind::NotFound => io::ErrorKind::NotFound,
+            ErrorKind::NotFound | ErrorKind::BucketNotFound => io::ErrorKind::NotFound,
             ErrorKind::PermissionDenied => io::ErrorKind::PermissionDenied,
             ErrorKind::InvalidInput => io::ErrorKind::InvalidInput,
             _ => io::ErrorKind::Other,
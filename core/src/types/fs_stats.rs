@@ -0,0 +1,37 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+/// FsStats carries the space and inode statistics of the file system
+/// that backs an operator.
+///
+/// All sizes are counted in blocks of [`FsStats::block_size`] bytes, so the
+/// available space in bytes is `blocks_available * block_size`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FsStats {
+    /// The size of a block in bytes.
+    pub block_size: u64,
+    /// Total blocks of the file system.
+    pub blocks_total: u64,
+    /// Free blocks of the file system.
+    pub blocks_free: u64,
+    /// Free blocks that are available to unprivileged users.
+    pub blocks_available: u64,
+    /// Total inodes of the file system.
+    pub inode_total: u64,
+    /// Free inodes of the file system.
+    pub inode_free: u64,
+}
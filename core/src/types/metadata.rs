@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+
 use chrono::prelude::*;
 use flagset::flags;
 use flagset::FlagSet;
@@ -45,6 +47,20 @@ pub struct Metadata {
     etag: Option<String>,
     last_modified: Option<DateTime<Utc>>,
     version: Option<String>,
+    /// Rarely used metadata are boxed to keep the size of metadata small.
+    extra: Option<Box<MetadataExtra>>,
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+struct MetadataExtra {
+    atime: Option<DateTime<Utc>>,
+    ctime: Option<DateTime<Utc>>,
+    symlink_target: Option<String>,
+    custom_metadata: Option<HashMap<String, String>>,
+    #[cfg(unix)]
+    unix_permissions: Option<u32>,
+    #[cfg(unix)]
+    inode: Option<u64>,
 }
 
 impl Metadata {
@@ -71,9 +87,18 @@ impl Metadata {
             etag: None,
             content_disposition: None,
             version: None,
+            extra: None,
         }
     }
 
+    fn extra(&self) -> Option<&MetadataExtra> {
+        self.extra.as_deref()
+    }
+
+    fn extra_mut(&mut self) -> &mut MetadataExtra {
+        self.extra.get_or_insert_with(Default::default)
+    }
+
     /// Get the metakey from metadata.
     ///
     /// This value describes which metadata has been set.
@@ -119,6 +144,11 @@ impl Metadata {
         matches!(self.mode, EntryMode::DIR)
     }
 
+    /// Returns `true` if this metadata is for a symbolic link.
+    pub fn is_symlink(&self) -> bool {
+        matches!(self.mode, EntryMode::Symlink)
+    }
+
     /// Set mode for entry.
     pub fn set_mode(&mut self, v: EntryMode) -> &mut Self {
         self.mode = v;
@@ -351,6 +381,67 @@ impl Metadata {
         self
     }
 
+    /// Last access time of this entry.
+    ///
+    /// # Panics
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::Atime`], otherwise it will panic.
+    pub fn atime(&self) -> Option<DateTime<Utc>> {
+        debug_assert!(
+            self.metakey.contains(Metakey::Atime) || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: atime, maybe a bug"
+        );
+
+        self.extra().and_then(|v| v.atime)
+    }
+
+    /// Set last access time of this entry.
+    pub fn set_atime(&mut self, v: DateTime<Utc>) -> &mut Self {
+        self.extra_mut().atime = Some(v);
+        self.metakey |= Metakey::Atime;
+        self
+    }
+
+    /// Set last access time of this entry.
+    pub fn with_atime(mut self, v: DateTime<Utc>) -> Self {
+        self.extra_mut().atime = Some(v);
+        self.metakey |= Metakey::Atime;
+        self
+    }
+
+    /// Last status change time of this entry.
+    ///
+    /// Unlike `last_modified`, this will also be updated while the metadata of
+    /// this entry like permissions has been changed.
+    ///
+    /// # Panics
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::Ctime`], otherwise it will panic.
+    pub fn ctime(&self) -> Option<DateTime<Utc>> {
+        debug_assert!(
+            self.metakey.contains(Metakey::Ctime) || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: ctime, maybe a bug"
+        );
+
+        self.extra().and_then(|v| v.ctime)
+    }
+
+    /// Set last status change time of this entry.
+    pub fn set_ctime(&mut self, v: DateTime<Utc>) -> &mut Self {
+        self.extra_mut().ctime = Some(v);
+        self.metakey |= Metakey::Ctime;
+        self
+    }
+
+    /// Set last status change time of this entry.
+    pub fn with_ctime(mut self, v: DateTime<Utc>) -> Self {
+        self.extra_mut().ctime = Some(v);
+        self.metakey |= Metakey::Ctime;
+        self
+    }
+
     /// ETag of this entry.
     ///
     /// `ETag` is defined by [RFC 7232](https://httpwg.org/specs/rfc7232.html#header.etag)
@@ -510,6 +601,149 @@ impl Metadata {
         self.metakey |= Metakey::Version;
         self
     }
+
+    /// Symlink target of this entry.
+    ///
+    /// The target is returned AS-IS as read from the link, it could be a relative
+    /// path or point to somewhere outside of the backend's root.
+    ///
+    /// It's only set when [`Metadata::mode`] is [`EntryMode::Symlink`].
+    ///
+    /// # Panics
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::SymlinkTarget`], otherwise it will panic.
+    pub fn symlink_target(&self) -> Option<&str> {
+        debug_assert!(
+            self.metakey.contains(Metakey::SymlinkTarget)
+                || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: symlink_target, maybe a bug"
+        );
+
+        self.extra().and_then(|v| v.symlink_target.as_deref())
+    }
+
+    /// Set symlink target of this entry.
+    ///
+    /// The target is returned AS-IS as read from the link, it could be a relative
+    /// path or point to somewhere outside of the backend's root.
+    pub fn with_symlink_target(mut self, v: String) -> Self {
+        self.extra_mut().symlink_target = Some(v);
+        self.metakey |= Metakey::SymlinkTarget;
+        self
+    }
+
+    /// Set symlink target of this entry.
+    ///
+    /// The target is returned AS-IS as read from the link, it could be a relative
+    /// path or point to somewhere outside of the backend's root.
+    pub fn set_symlink_target(&mut self, v: &str) -> &mut Self {
+        self.extra_mut().symlink_target = Some(v.to_string());
+        self.metakey |= Metakey::SymlinkTarget;
+        self
+    }
+
+    /// User defined metadata of this entry.
+    ///
+    /// # Panics
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::CustomMetadata`], otherwise it will panic.
+    pub fn custom_metadata(&self) -> Option<&HashMap<String, String>> {
+        debug_assert!(
+            self.metakey.contains(Metakey::CustomMetadata)
+                || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: custom_metadata, maybe a bug"
+        );
+
+        self.extra().and_then(|v| v.custom_metadata.as_ref())
+    }
+
+    /// Set user defined metadata of this entry.
+    pub fn with_custom_metadata(mut self, v: HashMap<String, String>) -> Self {
+        self.extra_mut().custom_metadata = Some(v);
+        self.metakey |= Metakey::CustomMetadata;
+        self
+    }
+
+    /// Set user defined metadata of this entry.
+    pub fn set_custom_metadata(&mut self, v: HashMap<String, String>) -> &mut Self {
+        self.extra_mut().custom_metadata = Some(v);
+        self.metakey |= Metakey::CustomMetadata;
+        self
+    }
+
+    /// Unix permissions of this entry.
+    ///
+    /// The value only contains the permission bits (including setuid, setgid
+    /// and sticky bits) of `st_mode`, for example `0o644` for a file readable
+    /// by everyone and writable by the owner. File type is not included.
+    ///
+    /// # Panics
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::UnixPermissions`], otherwise it will panic.
+    #[cfg(unix)]
+    pub fn unix_permissions(&self) -> Option<u32> {
+        debug_assert!(
+            self.metakey.contains(Metakey::UnixPermissions)
+                || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: unix_permissions, maybe a bug"
+        );
+
+        self.extra().and_then(|v| v.unix_permissions)
+    }
+
+    /// Set unix permissions of this entry.
+    #[cfg(unix)]
+    pub fn with_unix_permissions(mut self, v: u32) -> Self {
+        self.extra_mut().unix_permissions = Some(v);
+        self.metakey |= Metakey::UnixPermissions;
+        self
+    }
+
+    /// Set unix permissions of this entry.
+    #[cfg(unix)]
+    pub fn set_unix_permissions(&mut self, v: u32) -> &mut Self {
+        self.extra_mut().unix_permissions = Some(v);
+        self.metakey |= Metakey::UnixPermissions;
+        self
+    }
+
+    /// Inode number of this entry.
+    ///
+    /// Paths with the same inode number on the same file system point to the
+    /// same file, for example hard links.
+    ///
+    /// # Panics
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::Inode`], otherwise it will panic.
+    #[cfg(unix)]
+    pub fn inode(&self) -> Option<u64> {
+        debug_assert!(
+            self.metakey.contains(Metakey::Inode) || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: inode, maybe a bug"
+        );
+
+        self.extra().and_then(|v| v.inode)
+    }
+
+    /// Set inode number of this entry.
+    #[cfg(unix)]
+    pub fn with_inode(mut self, v: u64) -> Self {
+        self.extra_mut().inode = Some(v);
+        self.metakey |= Metakey::Inode;
+        self
+    }
+
+    /// Set inode number of this entry.
+    #[cfg(unix)]
+    pub fn set_inode(&mut self, v: u64) -> &mut Self {
+        self.extra_mut().inode = Some(v);
+        self.metakey |= Metakey::Inode;
+        self
+    }
 }
 
 flags! {
@@ -549,7 +783,23 @@ flags! {
         Etag,
         /// Key for last modified.
         LastModified,
+        /// Key for last access time.
+        Atime,
+        /// Key for last status change time.
+        Ctime,
         /// Key for version.
         Version,
+        /// Key for symlink target.
+        SymlinkTarget,
+        /// Key for user defined metadata.
+        CustomMetadata,
+        /// Key for unix permissions.
+        ///
+        /// Only available on unix platforms.
+        UnixPermissions,
+        /// Key for inode.
+        ///
+        /// Only available on unix platforms.
+        Inode,
     }
 }
@@ -58,3 +58,6 @@ pub use scheme::Scheme;
 
 mod capability;
 pub use capability::Capability;
+
+mod fs_stats;
+pub use fs_stats::FsStats;
@@ -27,10 +27,14 @@ use futures::TryStreamExt;
 
 use super::BlockingOperator;
 use crate::operator_futures::*;
+use crate::raw::oio::Read;
 use crate::raw::oio::WriteExt;
 use crate::raw::*;
 use crate::*;
 
+/// The default chunk size of [`Operator::copy_cross_service`].
+const DEFAULT_COPY_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
 /// Operator is the entry for all public async APIs.
 ///
 /// Developer should manipulate the data from storage service through Operator only by right.
@@ -87,7 +91,7 @@ impl Operator {
         Self { accessor, limit }
     }
 
-    pub(super) fn into_inner(self) -> FusedAccessor {
+    pub(crate) fn into_inner(self) -> FusedAccessor {
         self.accessor
     }
 
@@ -805,6 +809,106 @@ impl Operator {
         Ok(())
     }
 
+    /// Copy a file from `from` of this operator to `to` of `dst` operator.
+    ///
+    /// # Notes
+    ///
+    /// - `from` and `to` must be a file.
+    /// - `to` will be overwritten if it exists.
+    /// - Content is streamed in chunks of 8 MiB, use
+    ///   [`Operator::copy_cross_service_with_chunk`] to change it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator, dst: Operator) -> Result<()> {
+    /// op.copy_cross_service("path/to/file", &dst, "path/to/file")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_cross_service(&self, from: &str, dst: &Operator, to: &str) -> Result<()> {
+        self.copy_cross_service_with_chunk(from, dst, to, DEFAULT_COPY_CHUNK_SIZE)
+            .await
+    }
+
+    /// Copy a file from `from` of this operator to `to` of `dst` operator,
+    /// reading and writing at most `chunk` bytes at a time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator, dst: Operator) -> Result<()> {
+    /// op.copy_cross_service_with_chunk("path/to/file", &dst, "path/to/file", 4 * 1024 * 1024)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_cross_service_with_chunk(
+        &self,
+        from: &str,
+        dst: &Operator,
+        to: &str,
+        chunk: usize,
+    ) -> Result<()> {
+        let from = normalize_path(from);
+        let to = normalize_path(to);
+
+        if !validate_path(&from, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "from path is a directory")
+                    .with_operation("Operator::copy_cross_service")
+                    .with_context("service", self.info().scheme())
+                    .with_context("from", from),
+            );
+        }
+        if !validate_path(&to, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "to path is a directory")
+                    .with_operation("Operator::copy_cross_service")
+                    .with_context("service", dst.info().scheme())
+                    .with_context("to", to),
+            );
+        }
+        if chunk == 0 {
+            return Err(
+                Error::new(ErrorKind::InvalidInput, "chunk size must be greater than 0")
+                    .with_operation("Operator::copy_cross_service"),
+            );
+        }
+
+        let (_, mut r) = self.inner().read(&from, OpRead::new()).await?;
+        let (_, mut w) = dst.inner().write(&to, OpWrite::new()).await?;
+
+        loop {
+            let bs = match r.read(chunk).await {
+                Ok(bs) => bs,
+                Err(err) => {
+                    // Ignore the abort error since the read error is more important.
+                    let _ = w.abort().await;
+                    return Err(err);
+                }
+            };
+            if bs.is_empty() {
+                break;
+            }
+            if let Err(err) = w.write_all(bs).await {
+                let _ = w.abort().await;
+                return Err(err);
+            }
+        }
+
+        w.close().await
+    }
+
     /// Rename a file from `from` to `to`.
     ///
     /// # Notes
@@ -863,6 +967,391 @@ impl Operator {
         Ok(())
     }
 
+    /// Rename a file from `from` to `to` only if `to` doesn't exist.
+    ///
+    /// # Notes
+    ///
+    /// - `from` and `to` must be a file.
+    /// - If `to` exists, an `AlreadyExists` error will occur and nothing
+    ///   will be changed.
+    /// - The check and rename are atomic, so it can be used to implement
+    ///   locks or leases on top of the storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.rename_noreplace("path/to/file", "path/to/file2").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rename_noreplace(&self, from: &str, to: &str) -> Result<()> {
+        let from = normalize_path(from);
+
+        if !validate_path(&from, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "from path is a directory")
+                    .with_operation("Operator::rename_noreplace")
+                    .with_context("service", self.info().scheme())
+                    .with_context("from", from),
+            );
+        }
+
+        let to = normalize_path(to);
+
+        if !validate_path(&to, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "to path is a directory")
+                    .with_operation("Operator::rename_noreplace")
+                    .with_context("service", self.info().scheme())
+                    .with_context("to", to),
+            );
+        }
+
+        if from == to {
+            return Err(
+                Error::new(ErrorKind::IsSameFile, "from and to paths are same")
+                    .with_operation("Operator::rename_noreplace")
+                    .with_context("service", self.info().scheme())
+                    .with_context("from", from)
+                    .with_context("to", to),
+            );
+        }
+
+        self.inner()
+            .rename_noreplace(&from, &to, OpRenameNoreplace::new())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Set the extended attribute `name` of path to `value`.
+    ///
+    /// # Notes
+    ///
+    /// - Existing attribute with the same name will be overwritten.
+    /// - Attribute names are usually namespaced like `user.xxx` on linux.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.setxattr("path/to/file", "user.tag", "hot").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn setxattr(&self, path: &str, name: &str, value: impl Into<Bytes>) -> Result<()> {
+        let path = normalize_path(path);
+
+        self.inner()
+            .setxattr(&path, OpSetxattr::new(name, value.into()))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get the value of extended attribute `name` of path.
+    ///
+    /// # Notes
+    ///
+    /// - A `NotFound` error will occur if the attribute doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let value = op.getxattr("path/to/file", "user.tag").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn getxattr(&self, path: &str, name: &str) -> Result<Bytes> {
+        let path = normalize_path(path);
+
+        let rp = self.inner().getxattr(&path, OpGetxattr::new(name)).await?;
+
+        Ok(rp.into_value())
+    }
+
+    /// List the names of all extended attributes of path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let names = op.listxattr("path/to/file").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn listxattr(&self, path: &str) -> Result<Vec<String>> {
+        let path = normalize_path(path);
+
+        let rp = self.inner().listxattr(&path, OpListxattr::new()).await?;
+
+        Ok(rp.into_names())
+    }
+
+    /// List the names of all buckets accessible under given project.
+    ///
+    /// This allows selecting buckets at runtime instead of hardcoding
+    /// them in configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let buckets = op.list_buckets("my-project").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn list_buckets(&self, project: &str) -> Result<Vec<String>> {
+        let rp = self
+            .inner()
+            .list_buckets(OpListBuckets::new(project))
+            .await?;
+
+        Ok(rp.into_buckets())
+    }
+
+    /// Create a hard link at `to` which points to the file at `from`.
+    ///
+    /// # Notes
+    ///
+    /// - `from` and `to` must be a file.
+    /// - If `to` already exists, an `AlreadyExists` error will occur.
+    /// - If `from` and `to` are the same, an `IsSameFile` error will occur.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.hard_link("path/to/file", "path/to/file2").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn hard_link(&self, from: &str, to: &str) -> Result<()> {
+        let from = normalize_path(from);
+
+        if !validate_path(&from, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "from path is a directory")
+                    .with_operation("Operator::hard_link")
+                    .with_context("service", self.info().scheme())
+                    .with_context("from", from),
+            );
+        }
+
+        let to = normalize_path(to);
+
+        if !validate_path(&to, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "to path is a directory")
+                    .with_operation("Operator::hard_link")
+                    .with_context("service", self.info().scheme())
+                    .with_context("to", to),
+            );
+        }
+
+        if from == to {
+            return Err(
+                Error::new(ErrorKind::IsSameFile, "from and to paths are same")
+                    .with_operation("Operator::hard_link")
+                    .with_context("service", self.info().scheme())
+                    .with_context("from", from)
+                    .with_context("to", to),
+            );
+        }
+
+        self.inner()
+            .hard_link(&from, &to, OpHardLink::new())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Create a symlink at `link` which points to `target`.
+    ///
+    /// # Notes
+    ///
+    /// - `link` must be a file.
+    /// - `target` is stored as is and will not be checked, it can point to
+    ///   anywhere, even outside of the root of services.
+    /// - If `link` already exists, an `AlreadyExists` error will occur.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.symlink("../path/to/file", "path/to/link").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn symlink(&self, target: &str, link: &str) -> Result<()> {
+        let link = normalize_path(link);
+
+        if !validate_path(&link, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "link path is a directory")
+                    .with_operation("Operator::symlink")
+                    .with_context("service", self.info().scheme())
+                    .with_context("link", link),
+            );
+        }
+
+        self.inner()
+            .symlink(target, &link, OpSymlink::new())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Set the unix permission mode of given path, like `0o644`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.set_permissions("path/to/file", 0o600).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_permissions(&self, path: &str, mode: u32) -> Result<()> {
+        let path = normalize_path(path);
+
+        self.inner()
+            .set_permissions(&path, OpSetPermissions::new(mode))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Fetch the space and inode stats of the file system that backs
+    /// this operator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let stats = op.statvfs().await?;
+    /// let available = stats.blocks_available * stats.block_size;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn statvfs(&self) -> Result<FsStats> {
+        let rp = self.inner().statvfs(OpStatvfs::new()).await?;
+
+        Ok(rp.into_stats())
+    }
+
+    /// Truncate or extend the file at given path to `len` bytes.
+    ///
+    /// # Notes
+    ///
+    /// - `path` must be a file that exists.
+    /// - Extended part of the file will be filled with zeros.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.truncate("path/to/file", 1024).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn truncate(&self, path: &str, len: u64) -> Result<()> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "truncate path is a directory")
+                    .with_operation("Operator::truncate")
+                    .with_context("service", self.info().scheme())
+                    .with_context("path", path),
+            );
+        }
+
+        self.inner().truncate(&path, OpTruncate::new(len)).await?;
+
+        Ok(())
+    }
+
+    /// Pre-allocate disk space of `len` bytes for the file at given path
+    /// without changing its size.
+    ///
+    /// # Notes
+    ///
+    /// - The file will be created if it doesn't exist.
+    /// - `write` truncates the file which releases the allocated space, so
+    ///   this should be followed by appending writes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # #[tokio::main]
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.fallocate("path/to/file", 1024 * 1024).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fallocate(&self, path: &str, len: u64) -> Result<()> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "fallocate path is a directory")
+                    .with_operation("Operator::fallocate")
+                    .with_context("service", self.info().scheme())
+                    .with_context("path", path),
+            );
+        }
+
+        self.inner().fallocate(&path, OpFallocate::new(len)).await?;
+
+        Ok(())
+    }
+
     /// Write multiple bytes into path.
     ///
     /// # Notes
@@ -1535,7 +2024,7 @@ impl Operator {
     ///         EntryMode::DIR => {
     ///             println!("Handling dir {}", entry.path())
     ///         }
-    ///         EntryMode::Unknown => continue,
+    ///         EntryMode::Symlink | EntryMode::Unknown => continue,
     ///     }
     /// }
     /// # Ok(())
@@ -1566,7 +2055,7 @@ impl Operator {
     ///         EntryMode::DIR => {
     ///             println!("Handling dir {}", entry.path())
     ///         }
-    ///         EntryMode::Unknown => continue,
+    ///         EntryMode::Symlink | EntryMode::Unknown => continue,
     ///     }
     /// }
     /// # Ok(())
@@ -1668,7 +2157,7 @@ impl Operator {
     ///         EntryMode::DIR => {
     ///             println!("Handling dir {}", entry.path())
     ///         }
-    ///         EntryMode::Unknown => continue,
+    ///         EntryMode::Symlink | EntryMode::Unknown => continue,
     ///     }
     /// }
     /// # Ok(())
@@ -1697,7 +2186,7 @@ impl Operator {
     ///         EntryMode::DIR => {
     ///             println!("Handling dir like start a new list via meta.path()")
     ///         }
-    ///         EntryMode::Unknown => continue,
+    ///         EntryMode::Symlink | EntryMode::Unknown => continue,
     ///     }
     /// }
     /// # Ok(())
@@ -1724,7 +2213,7 @@ impl Operator {
     ///         EntryMode::DIR => {
     ///             println!("Handling dir like start a new list via meta.path()")
     ///         }
-    ///         EntryMode::Unknown => continue,
+    ///         EntryMode::Symlink | EntryMode::Unknown => continue,
     ///     }
     /// }
     /// # Ok(())
@@ -1783,7 +2272,7 @@ impl Operator {
     ///         EntryMode::DIR => {
     ///             println!("Handling dir like start a new list via meta.path()")
     ///         }
-    ///         EntryMode::Unknown => continue,
+    ///         EntryMode::Symlink | EntryMode::Unknown => continue,
     ///     }
     /// }
     /// # Ok(())
@@ -1878,7 +2367,7 @@ impl Operator {
     ///         EntryMode::DIR => {
     ///             println!("Handling dir {}", entry.path())
     ///         }
-    ///         EntryMode::Unknown => continue,
+    ///         EntryMode::Symlink | EntryMode::Unknown => continue,
     ///     }
     /// }
     /// # Ok(())
@@ -1906,7 +2395,7 @@ impl Operator {
     ///         EntryMode::DIR => {
     ///             println!("Handling dir {}", entry.path())
     ///         }
-    ///         EntryMode::Unknown => continue,
+    ///         EntryMode::Symlink | EntryMode::Unknown => continue,
     ///     }
     /// }
     /// # Ok(())
@@ -1941,7 +2430,7 @@ impl Operator {
     ///         EntryMode::DIR => {
     ///             println!("Handling dir {}", entry.path())
     ///         }
-    ///         EntryMode::Unknown => continue,
+    ///         EntryMode::Symlink | EntryMode::Unknown => continue,
     ///     }
     /// }
     /// # Ok(())
@@ -88,6 +88,21 @@ impl FunctionWrite {
         self
     }
 
+    /// Set the if_not_exists of op.
+    ///
+    /// If the if_not_exists is set, the write will fail with `AlreadyExists`
+    /// if the path already exists.
+    ///
+    /// # Notes
+    ///
+    /// Service could return `Unsupported` if the underlying storage does not support it.
+    pub fn if_not_exists(mut self, v: bool) -> Self {
+        self.0 = self
+            .0
+            .map_args(|(args, bs)| (args.with_if_not_exists(v), bs));
+        self
+    }
+
     /// Set the buffer size of op.
     ///
     /// If buffer size is set, the data will be buffered by the underlying writer.
@@ -125,6 +140,28 @@ impl FunctionWrite {
         self
     }
 
+    /// Set the content md5 (base64 encoded) of option
+    ///
+    /// Services will return `DataIntegrityCheckFailed` if the written
+    /// content doesn't match it.
+    pub fn content_md5(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_content_md5(v), bs));
+        self
+    }
+
+    /// Set the acl of option
+    ///
+    /// The acl is a predefined ACL of services like `private` or `publicRead`,
+    /// which overrides the default ACL of services for this write.
+    ///
+    /// # Notes
+    ///
+    /// Service could return `Unsupported` if the underlying storage does not support it.
+    pub fn acl(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|(args, bs)| (args.with_acl(v), bs));
+        self
+    }
+
     /// Call the function to consume all the input and generate a
     /// result.
     pub fn call(self) -> Result<()> {
@@ -154,6 +191,19 @@ impl FunctionWriter {
         self
     }
 
+    /// Set the if_not_exists of op.
+    ///
+    /// If the if_not_exists is set, the write will fail with `AlreadyExists`
+    /// if the path already exists.
+    ///
+    /// # Notes
+    ///
+    /// Service could return `Unsupported` if the underlying storage does not support it.
+    pub fn if_not_exists(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_if_not_exists(v));
+        self
+    }
+
     /// Set the buffer size of op.
     ///
     /// If buffer size is set, the data will be buffered by the underlying writer.
@@ -185,6 +235,28 @@ impl FunctionWriter {
         self
     }
 
+    /// Set the content md5 (base64 encoded) of option
+    ///
+    /// Services will return `DataIntegrityCheckFailed` if the written
+    /// content doesn't match it.
+    pub fn content_md5(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_content_md5(v));
+        self
+    }
+
+    /// Set the acl of option
+    ///
+    /// The acl is a predefined ACL of services like `private` or `publicRead`,
+    /// which overrides the default ACL of services for this write.
+    ///
+    /// # Notes
+    ///
+    /// Service could return `Unsupported` if the underlying storage does not support it.
+    pub fn acl(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_acl(v));
+        self
+    }
+
     /// Call the function to consume all the input and generate a
     /// result.
     pub fn call(self) -> Result<BlockingWriter> {
@@ -244,6 +316,18 @@ impl FunctionList {
         self
     }
 
+    /// Limit the depth of recursive list.
+    ///
+    /// - `0` means no entries will be listed.
+    /// - `1` means only the entries under the given path, like non-recursive list.
+    /// - `n` means entries at most `n` levels under the given path.
+    ///
+    /// Default to unlimited.
+    pub fn max_depth(mut self, v: usize) -> Self {
+        self.0 = self.0.map_args(|args| args.with_max_depth(v));
+        self
+    }
+
     /// Metakey is used to control which meta should be returned.
     ///
     /// Lister will make sure the result for specified meta is **known**:
@@ -297,6 +381,18 @@ impl FunctionLister {
         self
     }
 
+    /// Limit the depth of recursive list.
+    ///
+    /// - `0` means no entries will be listed.
+    /// - `1` means only the entries under the given path, like non-recursive list.
+    /// - `n` means entries at most `n` levels under the given path.
+    ///
+    /// Default to unlimited.
+    pub fn max_depth(mut self, v: usize) -> Self {
+        self.0 = self.0.map_args(|args| args.with_max_depth(v));
+        self
+    }
+
     /// Metakey is used to control which meta should be returned.
     ///
     /// Lister will make sure the result for specified meta is **known**:
@@ -390,6 +486,12 @@ impl FunctionReader {
         self
     }
 
+    /// Set whether to verify the content md5 for this operation.
+    pub fn checksum(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_checksum(v));
+        self
+    }
+
     /// Call the function to consume all the input and generate a
     /// result.
     pub fn call(self) -> Result<BlockingReader> {
@@ -427,6 +529,12 @@ impl FunctionStat {
         self
     }
 
+    /// Set whether to calculate the content md5 for this operation.
+    pub fn checksum(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_checksum(v));
+        self
+    }
+
     /// Call the function to consume all the input and generate a
     /// result.
     pub fn call(self) -> Result<Metadata> {
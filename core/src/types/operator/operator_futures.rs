@@ -19,6 +19,7 @@
 //!
 //! By using futures, users can add more options for operation.
 
+use std::collections::HashMap;
 use std::future::IntoFuture;
 use std::ops::RangeBounds;
 use std::time::Duration;
@@ -109,6 +110,11 @@ impl<F> FutureStat<F> {
     pub fn version(self, v: &str) -> Self {
         self.map(|args| args.with_version(v))
     }
+
+    /// Set whether to calculate the content md5 for this operation.
+    pub fn checksum(self, v: bool) -> Self {
+        self.map(|args| args.with_checksum(v))
+    }
 }
 
 /// Future that generated by [`Operator::presign_stat_with`].
@@ -247,6 +253,11 @@ impl<F> FutureRead<F> {
     pub fn version(self, v: &str) -> Self {
         self.map(|args| args.with_version(v))
     }
+
+    /// Set whether to verify the content md5 for this operation.
+    pub fn checksum(self, v: bool) -> Self {
+        self.map(|args| args.with_checksum(v))
+    }
 }
 
 /// Future that generated by [`Operator::write_with`].
@@ -266,6 +277,18 @@ impl<F> FutureWrite<F> {
         self.map(|(args, bs)| (args.with_append(v), bs))
     }
 
+    /// Set the if_not_exists of op.
+    ///
+    /// If the if_not_exists is set, the write will fail with `AlreadyExists`
+    /// if the path already exists.
+    ///
+    /// # Notes
+    ///
+    /// Service could return `Unsupported` if the underlying storage does not support it.
+    pub fn if_not_exists(self, v: bool) -> Self {
+        self.map(|(args, bs)| (args.with_if_not_exists(v), bs))
+    }
+
     /// Set the buffer size of op.
     ///
     /// If buffer size is set, the data will be buffered by the underlying writer.
@@ -297,6 +320,34 @@ impl<F> FutureWrite<F> {
     pub fn content_disposition(self, v: &str) -> Self {
         self.map(|(args, bs)| (args.with_content_disposition(v), bs))
     }
+
+    /// Set the content md5 (base64 encoded) of option
+    ///
+    /// Services will return `DataIntegrityCheckFailed` if the written
+    /// content doesn't match it.
+    pub fn content_md5(self, v: &str) -> Self {
+        self.map(|(args, bs)| (args.with_content_md5(v), bs))
+    }
+
+    /// Set the acl of option
+    ///
+    /// The acl is a predefined ACL of services like `private` or `publicRead`,
+    /// which overrides the default ACL of services for this write.
+    ///
+    /// # Notes
+    ///
+    /// Service could return `Unsupported` if the underlying storage does not support it.
+    pub fn acl(self, v: &str) -> Self {
+        self.map(|(args, bs)| (args.with_acl(v), bs))
+    }
+
+    /// Set the user defined metadata of option
+    ///
+    /// Refer to [`Capability::write_with_custom_metadata`] to check if
+    /// services support it.
+    pub fn custom_metadata(self, v: HashMap<String, String>) -> Self {
+        self.map(|(args, bs)| (args.with_custom_metadata(v), bs))
+    }
 }
 
 /// Future that generated by [`Operator::writer_with`].
@@ -316,6 +367,18 @@ impl<F> FutureWriter<F> {
         self.map(|args| args.with_append(v))
     }
 
+    /// Set the if_not_exists of op.
+    ///
+    /// If the if_not_exists is set, the write will fail with `AlreadyExists`
+    /// if the path already exists.
+    ///
+    /// # Notes
+    ///
+    /// Service could return `Unsupported` if the underlying storage does not support it.
+    pub fn if_not_exists(self, v: bool) -> Self {
+        self.map(|args| args.with_if_not_exists(v))
+    }
+
     /// Set the buffer size of op.
     ///
     /// If buffer size is set, the data will be buffered by the underlying writer.
@@ -354,6 +417,34 @@ impl<F> FutureWriter<F> {
     pub fn content_disposition(self, v: &str) -> Self {
         self.map(|args| args.with_content_disposition(v))
     }
+
+    /// Set the content md5 (base64 encoded) of option
+    ///
+    /// Services will return `DataIntegrityCheckFailed` if the written
+    /// content doesn't match it.
+    pub fn content_md5(self, v: &str) -> Self {
+        self.map(|args| args.with_content_md5(v))
+    }
+
+    /// Set the acl of option
+    ///
+    /// The acl is a predefined ACL of services like `private` or `publicRead`,
+    /// which overrides the default ACL of services for this write.
+    ///
+    /// # Notes
+    ///
+    /// Service could return `Unsupported` if the underlying storage does not support it.
+    pub fn acl(self, v: &str) -> Self {
+        self.map(|args| args.with_acl(v))
+    }
+
+    /// Set the user defined metadata of option
+    ///
+    /// Refer to [`Capability::write_with_custom_metadata`] to check if
+    /// services support it.
+    pub fn custom_metadata(self, v: HashMap<String, String>) -> Self {
+        self.map(|args| args.with_custom_metadata(v))
+    }
 }
 
 /// Future that generated by [`Operator::delete_with`].
@@ -398,6 +489,17 @@ impl<F> FutureList<F> {
         self.map(|args| args.with_recursive(v))
     }
 
+    /// Limit the depth of recursive list.
+    ///
+    /// - `0` means no entries will be listed.
+    /// - `1` means only the entries under the given path, like non-recursive list.
+    /// - `n` means entries at most `n` levels under the given path.
+    ///
+    /// Default to unlimited.
+    pub fn max_depth(self, v: usize) -> Self {
+        self.map(|args| args.with_max_depth(v))
+    }
+
     /// Metakey is used to control which meta should be returned.
     ///
     /// Lister will make sure the result for specified meta is **known**:
@@ -418,4 +520,12 @@ impl<F> FutureList<F> {
     pub fn concurrent(self, v: usize) -> Self {
         self.map(|args| args.with_concurrent(v))
     }
+
+    /// Skip entries whose name starts with `.`.
+    ///
+    /// Refer to [`Capability::list_with_skip_hidden`] to check if services
+    /// support it.
+    pub fn skip_hidden(self, v: bool) -> Self {
+        self.map(|args| args.with_skip_hidden(v))
+    }
 }
@@ -91,13 +91,7 @@ impl Writer {
 
     /// Write into inner writer.
     pub async fn write(&mut self, bs: impl Into<Bytes>) -> Result<()> {
-        let mut bs = bs.into();
-        while bs.remaining() > 0 {
-            let n = self.inner.write(&bs).await?;
-            bs.advance(n);
-        }
-
-        Ok(())
+        self.inner.write_all(bs.into()).await
     }
 
     /// Sink into writer.
@@ -137,12 +131,10 @@ impl Writer {
         let mut sink_from = Box::pin(sink_from);
         let mut written = 0;
         while let Some(bs) = sink_from.try_next().await? {
-            let mut bs = bs.into();
-            while bs.remaining() > 0 {
-                let n = self.inner.write(&bs).await?;
-                bs.advance(n);
-                written += n as u64;
-            }
+            let bs = bs.into();
+            let n = bs.len() as u64;
+            self.inner.write_all(bs).await?;
+            written += n;
         }
         Ok(written)
     }
@@ -207,6 +199,13 @@ impl Writer {
     pub async fn close(&mut self) -> Result<()> {
         self.inner.close().await
     }
+
+    /// Get the number of bytes that have been accepted by this writer.
+    ///
+    /// Returns `0` if the underlying service doesn't track it.
+    pub fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
 }
 
 impl AsyncWrite for Writer {
@@ -289,6 +288,13 @@ impl BlockingWriter {
     pub fn close(&mut self) -> Result<()> {
         self.inner.close()
     }
+
+    /// Get the number of bytes that have been accepted by this writer.
+    ///
+    /// Returns `0` if the underlying service doesn't track it.
+    pub fn written_bytes(&self) -> u64 {
+        self.inner.written_bytes()
+    }
 }
 
 impl io::Write for BlockingWriter {
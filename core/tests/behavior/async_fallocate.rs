@@ -0,0 +1,60 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use anyhow::Result;
+
+use crate::*;
+
+pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
+    let cap = op.info().full_capability();
+
+    if cap.read && cap.write && cap.stat && cap.fallocate {
+        tests.extend(async_trials!(
+            op,
+            test_fallocate_new_file,
+            test_fallocate_existing_file
+        ))
+    }
+}
+
+/// Fallocate a non-existing path should create an empty file.
+pub async fn test_fallocate_new_file(op: Operator) -> Result<()> {
+    let path = TEST_FIXTURE.new_file_path();
+
+    op.fallocate(&path, 1024 * 1024).await?;
+
+    // The size of file is kept, only the space is reserved.
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(meta.content_length(), 0);
+
+    Ok(())
+}
+
+/// Fallocate an existing file should keep its content.
+pub async fn test_fallocate_existing_file(op: Operator) -> Result<()> {
+    let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&path, content.clone()).await?;
+
+    op.fallocate(&path, size as u64 + 1024 * 1024).await?;
+
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(meta.content_length(), size as u64);
+    let actual = op.read(&path).await.expect("read must succeed");
+    assert_eq!(actual, content);
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use anyhow::Result;
+
+use crate::*;
+
+pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
+    let cap = op.info().full_capability();
+
+    if cap.read && cap.write && cap.stat && cap.hard_link {
+        tests.extend(async_trials!(
+            op,
+            test_hard_link,
+            test_hard_link_nested,
+            test_hard_link_non_existing_source,
+            test_hard_link_existing_target
+        ))
+    }
+}
+
+/// Hard link a file and test the content of link.
+pub async fn test_hard_link(op: Operator) -> Result<()> {
+    let (source_path, content, _) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&source_path, content.clone()).await?;
+
+    let target_path = TEST_FIXTURE.new_file_path();
+    op.hard_link(&source_path, &target_path).await?;
+
+    let target_content = op.read(&target_path).await.expect("read must succeed");
+    assert_eq!(target_content, content);
+
+    // Both paths point to the same inode if the service exposes it.
+    let source_meta = op.stat(&source_path).await?;
+    let target_meta = op.stat(&target_path).await?;
+    if source_meta.inode().is_some() {
+        assert_eq!(source_meta.inode(), target_meta.inode());
+    }
+
+    // The link is kept after the source has been deleted.
+    op.delete(&source_path).await?;
+    let target_content = op.read(&target_path).await.expect("read must succeed");
+    assert_eq!(target_content, content);
+
+    Ok(())
+}
+
+/// Hard link a file to a nested path whose parents don't exist.
+pub async fn test_hard_link_nested(op: Operator) -> Result<()> {
+    let (source_path, content, _) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&source_path, content.clone()).await?;
+
+    let parent = TEST_FIXTURE.new_dir_path();
+    let target_path = format!("{parent}{}", uuid::Uuid::new_v4());
+    op.hard_link(&source_path, &target_path).await?;
+
+    let target_content = op.read(&target_path).await.expect("read must succeed");
+    assert_eq!(target_content, content);
+
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Hard link a non-existing source should return an error.
+pub async fn test_hard_link_non_existing_source(op: Operator) -> Result<()> {
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let target_path = uuid::Uuid::new_v4().to_string();
+
+    let err = op
+        .hard_link(&source_path, &target_path)
+        .await
+        .expect_err("hard_link must fail");
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+    Ok(())
+}
+
+/// Hard link to an existing target should return an error.
+pub async fn test_hard_link_existing_target(op: Operator) -> Result<()> {
+    let (source_path, content, _) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&source_path, content).await?;
+
+    let (target_path, target_content, _) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&target_path, target_content.clone()).await?;
+
+    let err = op
+        .hard_link(&source_path, &target_path)
+        .await
+        .expect_err("hard_link must fail");
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+
+    // The existing target is kept as is.
+    let content = op.read(&target_path).await.expect("read must succeed");
+    assert_eq!(content, target_content);
+
+    Ok(())
+}
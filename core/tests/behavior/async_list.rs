@@ -52,6 +52,10 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
         ))
     }
 
+    if cap.read && cap.write && cap.list && cap.list_with_skip_hidden {
+        tests.extend(async_trials!(op, test_list_with_skip_hidden))
+    }
+
     if cap.read && !cap.write && cap.list {
         tests.extend(async_trials!(op, test_list_only))
     }
@@ -521,6 +525,36 @@ pub async fn test_list_dir_with_recursive(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// List dir with skip_hidden should skip hidden entries and their children.
+pub async fn test_list_with_skip_hidden(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+
+    let paths = ["x/a", "x/.b", "x/.c/d", "x/e/.f", "x/e/g"];
+    for path in paths.iter() {
+        op.write(&format!("{parent}/{path}"), "test_scan").await?;
+    }
+    let mut actual = op
+        .list_with(&format!("{parent}/x/"))
+        .recursive(true)
+        .skip_hidden(true)
+        .await?
+        .into_iter()
+        .map(|v| {
+            v.path()
+                .strip_prefix(&format!("{parent}/"))
+                .unwrap()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    actual.sort();
+
+    let expected = vec!["x/a", "x/e/", "x/e/g"];
+    assert_eq!(actual, expected);
+
+    op.remove_all(&format!("{parent}/")).await?;
+    Ok(())
+}
+
 // same as test_list_dir_with_recursive except listing 'x' instead of 'x/'
 pub async fn test_list_dir_with_recursive_no_trailing_slash(op: Operator) -> Result<()> {
     let parent = uuid::Uuid::new_v4().to_string();
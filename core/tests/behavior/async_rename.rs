@@ -36,6 +36,15 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_rename_overwrite
         ))
     }
+
+    if cap.read && cap.write && cap.stat && cap.rename_noreplace {
+        tests.extend(async_trials!(
+            op,
+            test_rename_noreplace_file,
+            test_rename_noreplace_non_existing_source,
+            test_rename_noreplace_existing_target
+        ))
+    }
 }
 
 /// Rename a file and test with stat.
@@ -196,3 +205,79 @@ pub async fn test_rename_overwrite(op: Operator) -> Result<()> {
     op.delete(&target_path).await.expect("delete must succeed");
     Ok(())
 }
+
+/// Rename a file without replacing to a nested path and test with stat.
+pub async fn test_rename_noreplace_file(op: Operator) -> Result<()> {
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes(op.info().full_capability());
+
+    op.write(&source_path, source_content.clone()).await?;
+
+    let parent = uuid::Uuid::new_v4().to_string();
+    let target_path = format!("{parent}/{}", uuid::Uuid::new_v4());
+
+    op.rename_noreplace(&source_path, &target_path).await?;
+
+    let err = op.stat(&source_path).await.expect_err("stat must fail");
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+
+    let target_content = op.read(&target_path).await.expect("read must succeed");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(target_content)),
+        format!("{:x}", Sha256::digest(&source_content)),
+    );
+
+    op.delete(&target_path).await.expect("delete must succeed");
+    op.delete(&format!("{parent}/"))
+        .await
+        .expect("delete must succeed");
+    Ok(())
+}
+
+/// Rename without replacing a nonexistent source should return an error.
+pub async fn test_rename_noreplace_non_existing_source(op: Operator) -> Result<()> {
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let target_path = uuid::Uuid::new_v4().to_string();
+
+    let err = op
+        .rename_noreplace(&source_path, &target_path)
+        .await
+        .expect_err("rename_noreplace must fail");
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+    Ok(())
+}
+
+/// Rename without replacing to an existing target should return an error
+/// and keep both files.
+pub async fn test_rename_noreplace_existing_target(op: Operator) -> Result<()> {
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes(op.info().full_capability());
+
+    op.write(&source_path, source_content.clone()).await?;
+
+    let target_path = uuid::Uuid::new_v4().to_string();
+    let (target_content, _) = gen_bytes(op.info().full_capability());
+
+    op.write(&target_path, target_content.clone()).await?;
+
+    let err = op
+        .rename_noreplace(&source_path, &target_path)
+        .await
+        .expect_err("rename_noreplace must fail");
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+
+    let content = op.read(&source_path).await.expect("read must succeed");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(content)),
+        format!("{:x}", Sha256::digest(&source_content)),
+    );
+    let content = op.read(&target_path).await.expect("read must succeed");
+    assert_eq!(
+        format!("{:x}", Sha256::digest(content)),
+        format!("{:x}", Sha256::digest(&target_content)),
+    );
+
+    op.delete(&source_path).await.expect("delete must succeed");
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
@@ -0,0 +1,60 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use anyhow::Result;
+
+use crate::*;
+
+pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
+    let cap = op.info().full_capability();
+
+    if cap.write && cap.stat && cap.set_permissions {
+        tests.extend(async_trials!(
+            op,
+            test_set_permissions,
+            test_set_permissions_non_existing_path
+        ))
+    }
+}
+
+/// Set permissions of a file and test with stat.
+pub async fn test_set_permissions(op: Operator) -> Result<()> {
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&path, content).await?;
+
+    op.set_permissions(&path, 0o600).await?;
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(meta.unix_permissions(), Some(0o600));
+
+    op.set_permissions(&path, 0o644).await?;
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(meta.unix_permissions(), Some(0o644));
+
+    Ok(())
+}
+
+/// Set permissions of a non-existing path should return an error.
+pub async fn test_set_permissions_non_existing_path(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+
+    let err = op
+        .set_permissions(&path, 0o600)
+        .await
+        .expect_err("set_permissions must fail");
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+    Ok(())
+}
@@ -0,0 +1,40 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use anyhow::Result;
+
+use crate::*;
+
+pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
+    let cap = op.info().full_capability();
+
+    if cap.statvfs {
+        tests.extend(async_trials!(op, test_statvfs))
+    }
+}
+
+/// Fetch the stats of file system and check they are consistent.
+pub async fn test_statvfs(op: Operator) -> Result<()> {
+    let stats = op.statvfs().await?;
+
+    assert!(stats.block_size > 0);
+    assert!(stats.blocks_free <= stats.blocks_total);
+    assert!(stats.blocks_available <= stats.blocks_free);
+    assert!(stats.inode_free <= stats.inode_total);
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use anyhow::Result;
+
+use crate::*;
+
+pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
+    let cap = op.info().full_capability();
+
+    if cap.read && cap.write && cap.symlink {
+        tests.extend(async_trials!(
+            op,
+            test_symlink,
+            test_symlink_dangling,
+            test_symlink_existing_link
+        ))
+    }
+}
+
+/// Create a symlink to a file and read through it.
+pub async fn test_symlink(op: Operator) -> Result<()> {
+    let (target_path, content, _) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&target_path, content.clone()).await?;
+
+    let link_path = TEST_FIXTURE.new_file_path();
+    op.symlink(&target_path, &link_path).await?;
+
+    let link_content = op.read(&link_path).await.expect("read must succeed");
+    assert_eq!(link_content, content);
+
+    Ok(())
+}
+
+/// Create a symlink to a non-existing target should succeed.
+pub async fn test_symlink_dangling(op: Operator) -> Result<()> {
+    let target_path = uuid::Uuid::new_v4().to_string();
+    let link_path = TEST_FIXTURE.new_file_path();
+
+    op.symlink(&target_path, &link_path).await?;
+
+    let err = op.read(&link_path).await.expect_err("read must fail");
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+
+    Ok(())
+}
+
+/// Create a symlink at an existing path should return an error.
+pub async fn test_symlink_existing_link(op: Operator) -> Result<()> {
+    let (target_path, content, _) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&target_path, content).await?;
+
+    let (link_path, link_content, _) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&link_path, link_content.clone()).await?;
+
+    let err = op
+        .symlink(&target_path, &link_path)
+        .await
+        .expect_err("symlink must fail");
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+
+    let content = op.read(&link_path).await.expect("read must succeed");
+    assert_eq!(content, link_content);
+
+    Ok(())
+}
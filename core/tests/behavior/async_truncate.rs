@@ -0,0 +1,75 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use anyhow::Result;
+
+use crate::*;
+
+pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
+    let cap = op.info().full_capability();
+
+    if cap.read && cap.write && cap.truncate {
+        tests.extend(async_trials!(
+            op,
+            test_truncate_shrink,
+            test_truncate_extend,
+            test_truncate_non_existing_path
+        ))
+    }
+}
+
+/// Truncate a file to a smaller size should keep the head of content.
+pub async fn test_truncate_shrink(op: Operator) -> Result<()> {
+    let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&path, content.clone()).await?;
+
+    let len = size / 2;
+    op.truncate(&path, len as u64).await?;
+
+    let actual = op.read(&path).await.expect("read must succeed");
+    assert_eq!(actual, content[..len]);
+
+    Ok(())
+}
+
+/// Truncate a file to a larger size should fill the extended part with zeros.
+pub async fn test_truncate_extend(op: Operator) -> Result<()> {
+    let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&path, content.clone()).await?;
+
+    op.truncate(&path, size as u64 + 8).await?;
+
+    let actual = op.read(&path).await.expect("read must succeed");
+    assert_eq!(actual.len(), size + 8);
+    assert_eq!(actual[..size], content);
+    assert!(actual[size..].iter().all(|b| *b == 0));
+
+    Ok(())
+}
+
+/// Truncate a non-existing path should return an error.
+pub async fn test_truncate_non_existing_path(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+
+    let err = op.truncate(&path, 0).await.expect_err("truncate must fail");
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+
+    // Truncate must not create the file.
+    let err = op.stat(&path).await.expect_err("stat must fail");
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+    Ok(())
+}
@@ -35,12 +35,14 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
         tests.extend(async_trials!(
             op,
             test_write_only,
+            test_write_overwrite_with_shorter_content,
             test_write_with_empty_content,
             test_write_with_dir_path,
             test_write_with_special_chars,
             test_write_with_cache_control,
             test_write_with_content_type,
             test_write_with_content_disposition,
+            test_write_with_if_not_exists,
             test_writer_write,
             test_writer_write_with_concurrent,
             test_writer_sink,
@@ -75,6 +77,20 @@ pub async fn test_write_only(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Overwrite a file with shorter content should not leave stale bytes.
+pub async fn test_write_overwrite_with_shorter_content(op: Operator) -> Result<()> {
+    let path = TEST_FIXTURE.new_file_path();
+
+    op.write(&path, "Hello, World! This is the old content.")
+        .await?;
+    op.write(&path, "Hello, World!").await?;
+
+    let bs = op.read(&path).await.expect("read must succeed");
+    assert_eq!(bs, b"Hello, World!");
+
+    Ok(())
+}
+
 /// Write a file with empty content.
 pub async fn test_write_with_empty_content(op: Operator) -> Result<()> {
     if !op.info().full_capability().write_can_empty {
@@ -204,6 +220,28 @@ pub async fn test_write_with_content_disposition(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Write a file with if_not_exists should fail if the file already exists.
+pub async fn test_write_with_if_not_exists(op: Operator) -> Result<()> {
+    if !op.info().full_capability().write_with_if_not_exists {
+        return Ok(());
+    }
+
+    let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
+
+    op.write_with(&path, content.clone())
+        .if_not_exists(true)
+        .await?;
+
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(meta.content_length(), size as u64);
+
+    let res = op.write_with(&path, content).if_not_exists(true).await;
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::AlreadyExists);
+
+    Ok(())
+}
+
 /// Delete existing file should succeed.
 pub async fn test_writer_abort(op: Operator) -> Result<()> {
     let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
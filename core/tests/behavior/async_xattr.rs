@@ -0,0 +1,90 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use anyhow::Result;
+
+use crate::*;
+
+pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
+    let cap = op.info().full_capability();
+
+    if cap.write && cap.xattr {
+        tests.extend(async_trials!(
+            op,
+            test_xattr,
+            test_getxattr_not_exist,
+            test_xattr_non_existing_path
+        ))
+    }
+}
+
+/// Set an xattr and read it back with getxattr and listxattr.
+pub async fn test_xattr(op: Operator) -> Result<()> {
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&path, content).await?;
+
+    match op.setxattr(&path, "user.tag", "hot").await {
+        Ok(()) => {}
+        // Skip if the underlying storage doesn't support user xattrs, like
+        // tmpfs on old kernels.
+        Err(err) if err.kind() == ErrorKind::Unsupported => return Ok(()),
+        Err(err) => return Err(err.into()),
+    }
+
+    let value = op.getxattr(&path, "user.tag").await?;
+    assert_eq!(value, "hot");
+
+    let names = op.listxattr(&path).await?;
+    assert!(names.contains(&"user.tag".to_string()));
+
+    // Set again should replace the value.
+    op.setxattr(&path, "user.tag", "cold").await?;
+    let value = op.getxattr(&path, "user.tag").await?;
+    assert_eq!(value, "cold");
+
+    Ok(())
+}
+
+/// Get a non-existing xattr should return an error.
+pub async fn test_getxattr_not_exist(op: Operator) -> Result<()> {
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&path, content).await?;
+
+    let err = op
+        .getxattr(&path, "user.not_exist")
+        .await
+        .expect_err("getxattr must fail");
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+
+    Ok(())
+}
+
+/// Xattr operations on a non-existing path should return an error.
+pub async fn test_xattr_non_existing_path(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+
+    let err = op
+        .setxattr(&path, "user.tag", "hot")
+        .await
+        .expect_err("setxattr must fail");
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+
+    let err = op.listxattr(&path).await.expect_err("listxattr must fail");
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+
+    Ok(())
+}
@@ -24,13 +24,20 @@ pub use utils::*;
 mod async_copy;
 mod async_create_dir;
 mod async_delete;
+mod async_fallocate;
 mod async_fuzz;
+mod async_hard_link;
 mod async_list;
 mod async_presign;
 mod async_read;
 mod async_rename;
+mod async_set_permissions;
 mod async_stat;
+mod async_statvfs;
+mod async_symlink;
+mod async_truncate;
 mod async_write;
+mod async_xattr;
 
 // Blocking test cases
 mod blocking_copy;
@@ -65,13 +72,20 @@ fn main() -> anyhow::Result<()> {
     async_copy::tests(&op, &mut tests);
     async_create_dir::tests(&op, &mut tests);
     async_delete::tests(&op, &mut tests);
+    async_fallocate::tests(&op, &mut tests);
     async_fuzz::tests(&op, &mut tests);
+    async_hard_link::tests(&op, &mut tests);
     async_list::tests(&op, &mut tests);
     async_presign::tests(&op, &mut tests);
     async_read::tests(&op, &mut tests);
     async_rename::tests(&op, &mut tests);
+    async_set_permissions::tests(&op, &mut tests);
     async_stat::tests(&op, &mut tests);
+    async_statvfs::tests(&op, &mut tests);
+    async_symlink::tests(&op, &mut tests);
+    async_truncate::tests(&op, &mut tests);
     async_write::tests(&op, &mut tests);
+    async_xattr::tests(&op, &mut tests);
 
     blocking_copy::tests(&op, &mut tests);
     blocking_create_dir::tests(&op, &mut tests);